@@ -9,6 +9,7 @@ use axum::{
 /// 参数:
 /// - state: 应用程序状态，包含共享的服务实例
 /// - page: 可选的页码参数，通过URL路径传入
+///
 /// 返回:
 /// - Json包装的Archive向量，包含归档列表数据
 pub async fn archive_posts(
@@ -24,3 +25,42 @@ pub async fn archive_posts(
     let html = post_service.render_archives(page, per_page).await?;
     Ok(Html(html))
 }
+
+/// 处理完整归档索引的请求 `/archives/`
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+///
+/// 返回:
+/// - 渲染后的归档索引页面，包含每年、每月的文章数
+pub async fn archive_index(State(state): State<AppState>) -> Result<Html<String>, AppError> {
+    Ok(Html(state.post_service.render_archive_index().await?))
+}
+
+/// 处理年度归档的请求，如 `/archives/2025/`
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+/// - year: 年份
+///
+/// 返回:
+/// - 按月份分组的文章列表，该年没有文章时返回404
+pub async fn year_archive(
+    State(state): State<AppState>,
+    Path(year): Path<u32>,
+) -> Result<Html<String>, AppError> {
+    Ok(Html(state.post_service.render_year_archive(year).await?))
+}
+
+/// 处理月度归档的请求，如 `/archives/2025/01/`
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+/// - year: 年份
+/// - month: 月份
+///
+/// 返回:
+/// - 当月的文章列表与月历，该月没有文章时返回404
+pub async fn month_archive(
+    State(state): State<AppState>,
+    Path((year, month)): Path<(u32, u32)>,
+) -> Result<Html<String>, AppError> {
+    Ok(Html(state.post_service.render_month_archive(year, month).await?))
+}
@@ -0,0 +1,14 @@
+// 导入所需的模块和类型
+use crate::{error::AppError, models::GraphData, routes::AppState};
+use axum::extract::State;
+use axum::Json;
+
+/// 返回文章关系图数据，用于可视化展示
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+///
+/// 返回:
+/// - JSON格式的文章节点，以及文章间的链接与共享标签关系
+pub async fn graph_json(State(state): State<AppState>) -> Result<Json<GraphData>, AppError> {
+    Ok(Json(state.post_service.graph().await?))
+}
@@ -0,0 +1,16 @@
+// 导入所需的模块和类型
+use crate::{error::AppError, routes::AppState};
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+
+/// 返回代码高亮主题对应的CSS样式表
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+///
+/// 返回:
+/// - 包含CSS内容的HTTP响应，设置Content-Type为text/css
+pub async fn highlight_css(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let css = state.post_service.highlight_css()?;
+    Ok(([(header::CONTENT_TYPE, "text/css; charset=utf-8")], css))
+}
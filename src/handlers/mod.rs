@@ -1,10 +1,6 @@
-/// 分类相关处理模块
-/// 提供分类列表和分类文章的处理函数
-pub mod category;
-
-/// 标签相关处理模块
-/// 提供标签列表和标签文章的处理函数
-pub mod tag;
+/// 分类法相关处理模块
+/// 提供标签、分类等分类法的词项列表、词项文章与订阅源的处理函数
+pub mod taxonomy;
 
 /// RSS相关处理模块
 /// 提供生成RSS订阅源的处理函数
@@ -15,7 +11,7 @@ pub mod rss;
 pub mod sitemap;
 
 /// 归档相关处理模块
-/// 提供文章归档和分页显示的处理函数
+/// 提供文章归档、分页显示以及按年、按月归档的处理函数
 pub mod archive;
 
 pub mod post;
@@ -24,14 +20,25 @@ pub mod refresh;
 pub mod upload;
 /// 友链相关处理模块
 pub mod friends;
+/// 代码高亮样式表处理模块
+pub mod highlight;
+/// 文章关系图处理模块
+pub mod graph;
+/// 站内搜索处理模块
+pub mod search;
+/// 写作统计处理模块
+pub mod stats;
 
 // 导出处理函数，使其可以在其他模块中直接使用
-pub use archive::archive_posts;
-pub use category::{categories_index, category_posts, category_posts_with_page};
-pub use post::{post_detail, render_index};
+pub use archive::{archive_index, archive_posts, month_archive, year_archive};
+pub use post::{filter_posts, post_detail, random_post, render_index};
 pub use refresh::refresh_posts;
 pub use rss::rss_feed;
 pub use sitemap::sitemap_xml;
-pub use tag::{tag_posts, tag_posts_with_page, tags_index};
+pub use taxonomy::{taxonomy_terms, term_feed, term_posts, term_posts_with_page};
 pub use upload::upload_file;
-pub use friends::render_friend_links;
\ No newline at end of file
+pub use friends::render_friend_links;
+pub use highlight::highlight_css;
+pub use graph::graph_json;
+pub use search::{search, search_index_json, search_suggest};
+pub use stats::{stats_json, stats_page, year_review};
\ No newline at end of file
@@ -1,10 +1,13 @@
 // 导入所需的模块和类型
+use crate::models::query::{PostQuery, PostSort, TermMatch};
 use crate::{error::AppError, routes::AppState};
 use anyhow::Result;
 use axum::{
-    extract::{Path, State},
-    response::Html,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
 };
+use serde::{Deserialize, Serialize};
 
 /// 处理文章详情页面的请求
 /// 
@@ -29,6 +32,28 @@ pub async fn post_detail(
     Ok(Html(html))
 }
 
+/// 处理随机文章的请求 `/random/`
+///
+/// # 功能说明
+/// - 从已发布文章中随机选取一篇，草稿不会被选中
+/// - 302临时跳转到该文章，并禁止缓存，保证每次访问结果不同
+/// - 没有已发布文章时重定向到404错误页面
+pub async fn random_post(State(state): State<AppState>) -> Result<Response, AppError> {
+    let url = state
+        .post_service
+        .random_post()
+        .await?
+        .ok_or_else(|| AppError::NotFound("/random/".to_string()))?;
+    Ok((
+        StatusCode::FOUND,
+        [
+            (header::LOCATION, format!("/post/{}/", url)),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+    )
+        .into_response())
+}
+
 /// 处理博客首页的渲染请求
 /// 
 /// # 功能说明
@@ -60,3 +85,92 @@ pub async fn render_index(
     // 将渲染结果包装为HTML响应返回
     Ok(Html(html))
 }
+
+
+/// 组合筛选文章列表的查询参数，空字符串视为未填写
+///
+/// # 字段说明
+/// * `tag` - 标签，多个以英文逗号分隔
+/// * `match` - 多个标签的匹配方式，`any`（默认）或 `all`
+/// * `category` - 分类，层级分类包含所有下级
+/// * `year` - 发布年份
+/// * `author` - 作者，对应 Front Matter 中的 `author`
+/// * `series` - 系列，对应 Front Matter 中的 `series`
+/// * `q` - 标题或正文包含的文本
+/// * `sort` - 排序方式：`date`（默认）、`updated`、`title`、`weight`
+/// * `page` - 页码，从1开始
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PostFilterParams {
+    pub tag: String,
+    #[serde(rename = "match")]
+    pub mode: String,
+    pub category: String,
+    pub year: String,
+    pub author: String,
+    pub series: String,
+    pub q: String,
+    pub sort: String,
+    #[serde(skip_serializing)]
+    pub page: Option<usize>,
+}
+
+impl PostFilterParams {
+    /// 由查询参数生成文章查询条件，无法识别的匹配方式与排序方式使用默认值，
+    /// 无法识别的年份不匹配任何文章
+    fn to_query(&self) -> PostQuery {
+        let mut query = PostQuery::new();
+        for tag in self.tag.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            query = query.tag(tag);
+        }
+        if self.mode == "all" {
+            query = query.term_match("tags", TermMatch::All);
+        }
+        if !self.category.trim().is_empty() {
+            query = query.category(self.category.trim());
+        }
+        let year = self.year.trim();
+        if !year.is_empty() {
+            // 超出 i32 的年份同样超出日期范围
+            query = query.year(year.parse().unwrap_or(i32::MAX));
+        }
+        if !self.author.trim().is_empty() {
+            query = query.author(self.author.trim());
+        }
+        if !self.series.trim().is_empty() {
+            query = query.series(self.series.trim());
+        }
+        if !self.q.trim().is_empty() {
+            query = query.text(self.q.trim());
+        }
+        let sort = match self.sort.as_str() {
+            "updated" => PostSort::Updated,
+            "title" => PostSort::Title,
+            "weight" => PostSort::Weight,
+            _ => PostSort::Date,
+        };
+        query.sort(sort)
+    }
+}
+
+/// 处理组合筛选的文章列表请求，如 `/posts/?tag=Rust&category=技术&year=2024&sort=date`
+///
+/// # 参数
+/// - state: 应用程序状态，包含文章服务实例
+/// - params: 筛选、排序与页码参数
+///
+/// # 返回值
+/// - 成功：返回HTML格式的文章列表
+/// - 失败：返回AppError错误类型
+pub async fn filter_posts(
+    State(state): State<AppState>,
+    Query(params): Query<PostFilterParams>,
+) -> Result<Html<String>, AppError> {
+    let per_page = 10;
+    let page = params.page.unwrap_or(1).max(1);
+    let html = state
+        .post_service
+        .render_posts(params.to_query(), &params, page, per_page)
+        .await?;
+    Ok(Html(html))
+}
@@ -5,6 +5,7 @@ use axum::{extract::State, response::IntoResponse};
 /// 刷新所有文章数据的处理函数
 /// 参数:
 /// - state: 应用程序状态，包含文章服务实例
+///
 /// 返回:
 /// - 刷新操作的结果信息字符串
 pub async fn refresh_posts(State(state): State<AppState>) -> impl IntoResponse {
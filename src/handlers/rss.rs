@@ -7,6 +7,7 @@ use axum::response::IntoResponse;
 /// 生成并返回网站的RSS订阅源
 /// 参数:
 /// - state: 应用程序状态，包含RSS服务实例
+///
 /// 返回:
 /// - 包含XML格式的RSS内容的HTTP响应
 pub async fn rss_feed(State(state): State<AppState>) -> impl IntoResponse {
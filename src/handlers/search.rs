@@ -0,0 +1,93 @@
+// 导入所需的模块和类型
+use crate::{error::AppError, routes::AppState};
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use crate::models::Suggestion;
+use serde::Deserialize;
+
+/// 搜索请求的查询参数
+///
+/// # 字段说明
+/// * `q` - 查询语句
+/// * `page` - 页码，从1开始，默认为1
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    #[serde(default)]
+    pub q: String,
+    pub page: Option<usize>,
+}
+
+/// 处理站内搜索请求，返回搜索结果页面
+/// 参数:
+/// - state: 应用程序状态，包含搜索服务实例
+/// - params: 查询语句与页码
+///
+/// 返回:
+/// - 渲染后的搜索结果页面
+pub async fn search(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Html<String>, AppError> {
+    let html = state
+        .search_service
+        .render_search(&params.q, params.page.unwrap_or(1))
+        .await?;
+    Ok(Html(html))
+}
+
+/// 输入建议请求的查询参数
+#[derive(Debug, Deserialize)]
+pub struct SuggestParams {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// 处理输入建议请求，按汉字、全拼或拼音首字母匹配文章标题与标签
+/// 参数:
+/// - state: 应用程序状态，包含搜索服务实例
+/// - params: 用户输入
+///
+/// 返回:
+/// - Json包装的建议列表
+pub async fn search_suggest(
+    State(state): State<AppState>,
+    Query(params): Query<SuggestParams>,
+) -> Result<Json<Vec<Suggestion>>, AppError> {
+    Ok(Json(state.search_service.suggest(&params.q).await?))
+}
+
+/// 返回供静态镜像在浏览器端检索的搜索索引
+/// 参数:
+/// - state: 应用程序状态，包含文章服务实例
+/// - headers: 请求头，`If-None-Match` 与当前ETag一致时返回304
+///
+/// 返回:
+/// - JSON格式的文章标题、地址、日期、标签、分类、摘要与正文分词
+pub async fn search_index_json(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let export = state.post_service.search_export().await?;
+    let cache_headers = [
+        (header::ETAG, export.etag.clone()),
+        // 每次使用前向服务器确认，内容未变时只需一次304响应
+        (header::CACHE_CONTROL, "public, no-cache".to_string()),
+    ];
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| export.matches(value));
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "application/json; charset=utf-8")],
+        export.json.clone(),
+    )
+        .into_response())
+}
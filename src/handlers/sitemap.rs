@@ -7,6 +7,7 @@ use axum::response::IntoResponse;
 /// 生成并返回网站的sitemap.xml文件
 /// 参数:
 /// - state: 应用程序状态，包含站点地图服务实例
+///
 /// 返回:
 /// - 包含XML内容的HTTP响应，设置正确的Content-Type
 pub async fn sitemap_xml(State(state): State<AppState>) -> impl IntoResponse {
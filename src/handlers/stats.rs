@@ -0,0 +1,41 @@
+// 导入所需的模块和类型
+use crate::{error::AppError, models::SiteStats, routes::AppState};
+use axum::{
+    extract::{Path, State},
+    response::Html,
+    Json,
+};
+
+/// 处理统计页面的请求 `/stats/`
+/// 参数:
+/// - state: 应用程序状态，包含统计服务实例
+///
+/// 返回:
+/// - 渲染后的统计页面，包含每年、每月的写作量、常用词项、最长文章与写作热力图
+pub async fn stats_page(State(state): State<AppState>) -> Result<Html<String>, AppError> {
+    Ok(Html(state.stats_service.render_stats().await?))
+}
+
+/// 返回JSON格式的写作统计 `/stats.json`
+/// 参数:
+/// - state: 应用程序状态，包含统计服务实例
+///
+/// 返回:
+/// - 与统计页面相同的数据
+pub async fn stats_json(State(state): State<AppState>) -> Result<Json<SiteStats>, AppError> {
+    Ok(Json(state.stats_service.stats().await?))
+}
+
+/// 处理年度回顾的请求，如 `/stats/2025/`
+/// 参数:
+/// - state: 应用程序状态，包含统计服务实例
+/// - year: 年份
+///
+/// 返回:
+/// - 当年的文章列表与写作统计，该年没有文章时返回404
+pub async fn year_review(
+    State(state): State<AppState>,
+    Path(year): Path<u32>,
+) -> Result<Html<String>, AppError> {
+    Ok(Html(state.stats_service.render_year_review(year).await?))
+}
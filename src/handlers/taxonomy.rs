@@ -0,0 +1,108 @@
+// 导入所需的模块和类型
+use crate::models::taxonomy::TermOrder;
+use crate::services::taxonomy_service::TermPath;
+use crate::{error::AppError, routes::AppState};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+
+/// 词项列表的查询参数
+///
+/// # 字段说明
+/// * `sort` - 排序方式，`count`（默认，按文章数）或 `pinyin`（按拼音首字母分组）
+#[derive(Debug, Deserialize)]
+pub struct TermListParams {
+    #[serde(default)]
+    pub sort: TermOrder,
+}
+
+/// 301永久跳转，用于将原始名称的词项地址跳转到别名地址
+fn moved_permanently(location: String) -> Response {
+    (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, location)]).into_response()
+}
+
+/// 处理分类法首页请求，如 `/tags/`、`/categories/`，返回所有词项及其文章数量
+/// 参数:
+/// - state: 应用程序状态，包含分类法服务实例
+/// - taxonomy: 分类法名称
+/// - params: 排序方式，如 `/tags/?sort=pinyin`
+///
+/// 返回:
+/// - 渲染后的词项列表页面，未配置的分类法返回404
+pub async fn taxonomy_terms(
+    State(state): State<AppState>,
+    Path(taxonomy): Path<String>,
+    Query(params): Query<TermListParams>,
+) -> Result<Html<String>, AppError> {
+    let html = state.taxonomy_service.render_terms(&taxonomy, params.sort).await?;
+    Ok(Html(html))
+}
+
+/// 处理不带页码的词项文章列表，如 `/tags/rust/`
+pub async fn term_posts(
+    state: State<AppState>,
+    Path((taxonomy, term)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    term_posts_with_page(state, Path((taxonomy, term, 1))).await
+}
+
+/// 处理带页码的词项文章列表，如 `/tags/rust/page/2/`
+/// 参数:
+/// - state: 应用程序状态，包含分类法服务实例
+/// - taxonomy: 分类法名称
+/// - term: 词项别名；使用原始名称（如 `/tags/蓝桉树/`）时301跳转到别名地址
+/// - page: 页码
+pub async fn term_posts_with_page(
+    State(state): State<AppState>,
+    Path((taxonomy, term, page)): Path<(String, String, usize)>,
+) -> Result<Response, AppError> {
+    let config = state.taxonomy_service.taxonomy(&taxonomy)?;
+    let term = match state.taxonomy_service.resolve_term(config, &term).await? {
+        TermPath::Term(term) => term,
+        TermPath::Moved(slug) if page > 1 => {
+            return Ok(moved_permanently(format!("/{}/{}/page/{}/", taxonomy, slug, page)))
+        }
+        TermPath::Moved(slug) => return Ok(moved_permanently(format!("/{}/{}/", taxonomy, slug))),
+    };
+    let html = state
+        .taxonomy_service
+        .render_term_posts(&taxonomy, &term, page)
+        .await?;
+    Ok(Html(html).into_response())
+}
+
+/// 生成并返回词项的RSS订阅源，如 `/tags/rust/index.xml`
+/// 参数:
+/// - state: 应用程序状态，包含分类法服务与RSS服务实例
+/// - taxonomy: 分类法名称
+/// - term: 词项别名；使用原始名称时301跳转到别名地址
+///
+/// 返回:
+/// - XML格式的RSS内容，未配置的分类法或未开启订阅源时返回404
+pub async fn term_feed(
+    State(state): State<AppState>,
+    Path((taxonomy, term)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let taxonomy = state.taxonomy_service.taxonomy(&taxonomy)?;
+    if !taxonomy.feed {
+        return Err(AppError::NotFound(format!(
+            "/{}/{}/index.xml",
+            taxonomy.name, term
+        )));
+    }
+    let term = match state.taxonomy_service.resolve_term(taxonomy, &term).await? {
+        TermPath::Term(term) => term,
+        TermPath::Moved(slug) => {
+            return Ok(moved_permanently(format!("/{}/{}/index.xml", taxonomy.name, slug)))
+        }
+    };
+    let posts = state.taxonomy_service.get_posts_by_term(taxonomy, &term).await;
+    let meta = state.taxonomy_service.get_term_meta(taxonomy, &term).await;
+    let xml = state
+        .rss_service
+        .generate_term_feed_xml(taxonomy, &term, &posts, meta.as_ref());
+    Ok(([(header::CONTENT_TYPE, "application/xml")], xml).into_response())
+}
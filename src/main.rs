@@ -3,7 +3,7 @@ use axum::{middleware as axum_middleware, Router};
 use dotenv::dotenv;
 use models::Site;
 use services::{
-    CategoryService, PostService, RssService, SitemapService, TagService, TemplateService,
+    PostService, RssService, SearchService, SitemapService, StatsService, TaxonomyService, TemplateService,
 };
 use std::{net::SocketAddr, sync::Arc};
 use tower_http::trace::TraceLayer;
@@ -23,8 +23,6 @@ mod utils;
 async fn main() -> Result<()> {
     // 加载环境变量
     dotenv().ok();
-    // 从环境变量加载站点配置
-    let site = Site::from_env();
     // 从环境变量获取日志级别
     let log_level = match std::env::var("LOG_LEVEL")
         .unwrap_or_else(|_| "INFO".to_string())
@@ -40,22 +38,33 @@ async fn main() -> Result<()> {
     };
     // 初始化日志系统
     tracing_subscriber::fmt().with_max_level(log_level).init();
+    // 从环境变量加载站点配置，需在日志系统之后以便输出配置警告
+    let site = Site::from_env();
 
     let template_service = Arc::new(TemplateService::new()?);
-    let post_service = Arc::new(PostService::new(Arc::clone(&template_service)));
-    let tag_service = Arc::new(TagService::new(
+    let post_service = Arc::new(PostService::new(
         Arc::clone(&template_service),
-        Arc::clone(&post_service),
+        site.clone(),
     ));
-    let category_service = Arc::new(CategoryService::new(
+    let taxonomy_service = Arc::new(TaxonomyService::new(
         Arc::clone(&template_service),
         Arc::clone(&post_service),
+        site.clone(),
     ));
     let rss_service = Arc::new(RssService::new(Arc::clone(&post_service), site.clone()));
+    let search_service = Arc::new(SearchService::new(
+        Arc::clone(&template_service),
+        Arc::clone(&post_service),
+        site.clone(),
+    ));
+    let stats_service = Arc::new(StatsService::new(
+        Arc::clone(&template_service),
+        Arc::clone(&post_service),
+        site.clone(),
+    ));
     let sitemap_service = Arc::new(SitemapService::new(
         Arc::clone(&post_service),
-        Arc::clone(&tag_service),
-        Arc::clone(&category_service),
+        Arc::clone(&taxonomy_service),
         site,
     ));
     let upload_service = Arc::new(services::UploadService::new());
@@ -67,15 +76,16 @@ async fn main() -> Result<()> {
     post_service.load_all_posts().await?;
 
     let app = Router::new()
-        .merge(routes::create_router(
-            category_service,
-            tag_service,
+        .merge(routes::create_router(routes::AppState {
+            taxonomy_service,
             rss_service,
             sitemap_service,
             post_service,
             upload_service,
             friend_service,
-        ))
+            search_service,
+            stats_service,
+        }))
         .layer(axum_middleware::from_fn(middleware::logging))
         .layer(TraceLayer::new_for_http());
 
@@ -1,3 +1,5 @@
+use super::Post;
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
 
 /// 文章归档结构体
@@ -8,10 +10,13 @@ use serde::{Deserialize, Serialize};
 /// 
 /// # 字段说明
 /// * `year` - 归档年份
-/// * `posts` - 该年份下的所有文章列表
+/// * `month` - 按月分组时的月份，按年分组时为空
+/// * `posts` - 该年份（月份）下的所有文章列表
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Archive {
     pub year: u32,
+    #[serde(default)]
+    pub month: Option<u32>,
     pub posts: Vec<ArchivePost>,
 }
 
@@ -30,3 +35,282 @@ pub struct ArchivePost {
     pub title: String,
     pub url: String,
 }
+
+impl ArchivePost {
+    /// 由文章生成归档条目，日期为 `MM-DD` 格式
+    pub fn new(post: &Post) -> Self {
+        Self {
+            date: post.front_matter.date.format("%m-%d").to_string(),
+            title: post.front_matter.title.clone(),
+            url: post.url.clone(),
+        }
+    }
+}
+
+/// 按年份（`by_month` 为true时按年月）将文章分组，保持文章原有顺序
+///
+/// # 参数
+/// * `posts` - 按日期降序排列的已发布文章
+/// * `by_month` - 是否按月份分组
+pub fn group_posts<'a>(posts: impl IntoIterator<Item = &'a Post>, by_month: bool) -> Vec<Archive> {
+    let mut archives: Vec<Archive> = Vec::new();
+    for post in posts {
+        let date = post.front_matter.date;
+        let year = date.year() as u32;
+        let month = by_month.then(|| date.month());
+        let archive_post = ArchivePost::new(post);
+        // 尝试将文章添加到已存在的分组中
+        if let Some(last) = archives.last_mut() {
+            if last.year == year && last.month == month {
+                last.posts.push(archive_post);
+                continue;
+            }
+        }
+        archives.push(Archive {
+            year,
+            month,
+            posts: vec![archive_post],
+        });
+    }
+    archives
+}
+
+/// 某月的文章数
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct MonthCount {
+    pub month: u32,
+    pub count: usize,
+}
+
+/// 某年的文章数，包含各月份的文章数
+///
+/// # 字段说明
+/// * `year` - 年份
+/// * `count` - 全年文章数
+/// * `months` - 有文章的月份，按月份降序排列
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct YearCount {
+    pub year: u32,
+    pub count: usize,
+    pub months: Vec<MonthCount>,
+}
+
+/// 统计每年、每月的文章数，按时间降序排列
+///
+/// # 参数
+/// * `posts` - 已发布文章
+pub fn count_by_month<'a>(posts: impl IntoIterator<Item = &'a Post>) -> Vec<YearCount> {
+    let mut years: Vec<YearCount> = Vec::new();
+    for post in posts {
+        let date = post.front_matter.date;
+        let (year, month) = (date.year() as u32, date.month());
+        let index = match years.iter().position(|y| y.year == year) {
+            Some(index) => index,
+            None => {
+                years.push(YearCount {
+                    year,
+                    count: 0,
+                    months: Vec::new(),
+                });
+                years.len() - 1
+            }
+        };
+        let entry = &mut years[index];
+        entry.count += 1;
+        match entry.months.iter_mut().find(|m| m.month == month) {
+            Some(count) => count.count += 1,
+            None => entry.months.push(MonthCount { month, count: 1 }),
+        }
+    }
+    years.sort_by_key(|y| std::cmp::Reverse(y.year));
+    for year in &mut years {
+        year.months.sort_by_key(|m| std::cmp::Reverse(m.month));
+    }
+    years
+}
+
+/// 日历中的一天
+///
+/// # 字段说明
+/// * `day` - 日期
+/// * `posts` - 当天发布的文章，没有文章时为空
+#[derive(Debug, Serialize, Clone)]
+pub struct CalendarDay {
+    pub day: u32,
+    pub posts: Vec<ArchivePost>,
+}
+
+/// 月历视图
+///
+/// # 字段说明
+/// * `year` - 年份
+/// * `month` - 月份
+/// * `weeks` - 按周排列的日期，每周从周一开始，不属于本月的格子为空
+#[derive(Debug, Serialize, Clone)]
+pub struct Calendar {
+    pub year: u32,
+    pub month: u32,
+    pub weeks: Vec<Vec<Option<CalendarDay>>>,
+}
+
+impl Calendar {
+    /// 生成月历，标记每天发布的文章
+    ///
+    /// # 参数
+    /// * `year` - 年份
+    /// * `month` - 月份，1–12
+    /// * `posts` - 已发布文章，只取该月的文章
+    ///
+    /// # 返回
+    /// * `Option<Calendar>` - 年月无效时返回 `None`
+    pub fn new<'a>(year: u32, month: u32, posts: impl IntoIterator<Item = &'a Post>) -> Option<Self> {
+        let first = NaiveDate::from_ymd_opt(year as i32, month, 1)?;
+        let next = if month == 12 {
+            NaiveDate::from_ymd_opt(year as i32 + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(year as i32, month + 1, 1)?
+        };
+        let days = (next - first).num_days() as u32;
+
+        let mut cells: Vec<Option<CalendarDay>> =
+            (0..first.weekday().num_days_from_monday()).map(|_| None).collect();
+        cells.extend((1..=days).map(|day| Some(CalendarDay { day, posts: Vec::new() })));
+        while !cells.len().is_multiple_of(7) {
+            cells.push(None);
+        }
+
+        let offset = first.weekday().num_days_from_monday() as usize;
+        for post in posts {
+            let date = post.front_matter.date;
+            if date.year() as u32 != year || date.month() != month {
+                continue;
+            }
+            if let Some(Some(day)) = cells.get_mut(offset + date.day() as usize - 1) {
+                day.posts.push(ArchivePost::new(post));
+            }
+        }
+
+        Some(Self {
+            year,
+            month,
+            weeks: cells.chunks(7).map(<[_]>::to_vec).collect(),
+        })
+    }
+}
+
+/// 往年今日发布的文章
+///
+/// # 字段说明
+/// * `year` - 发布年份
+/// * `years_ago` - 距离参照日期的年数
+/// * `post` - 文章
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OnThisDay {
+    pub year: u32,
+    pub years_ago: u32,
+    pub post: ArchivePost,
+}
+
+/// 查找往年同月同日发布的文章，按年份降序排列
+///
+/// # 参数
+/// * `posts` - 按日期降序排列的文章，草稿会被排除
+/// * `date` - 参照日期，只取早于该年份的文章
+pub fn on_this_day<'a>(posts: impl IntoIterator<Item = &'a Post>, date: NaiveDate) -> Vec<OnThisDay> {
+    posts
+        .into_iter()
+        .filter(|post| !post.front_matter.draft)
+        .filter_map(|post| {
+            let published = post.front_matter.date;
+            let same_day = published.month() == date.month() && published.day() == date.day();
+            (same_day && published.year() < date.year()).then(|| OnThisDay {
+                year: published.year() as u32,
+                years_ago: (date.year() - published.year()) as u32,
+                post: ArchivePost::new(post),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+
+    fn posts() -> Vec<Post> {
+        vec![
+            post("d", "date: 2025-02-03T08:00:00+08:00", ""),
+            post("c", "date: 2025-01-22T08:00:00+08:00", ""),
+            post("b", "date: 2025-01-01T08:00:00+08:00", ""),
+            post("a", "date: 2024-12-31T08:00:00+08:00", ""),
+        ]
+    }
+
+    #[test]
+    fn test_group_posts() {
+        let posts = posts();
+        let years = group_posts(&posts, false);
+        assert_eq!(years.len(), 2);
+        assert_eq!((years[0].year, years[0].month, years[0].posts.len()), (2025, None, 3));
+        let months = group_posts(&posts, true);
+        let keys: Vec<(u32, Option<u32>)> = months.iter().map(|a| (a.year, a.month)).collect();
+        assert_eq!(keys, [(2025, Some(2)), (2025, Some(1)), (2024, Some(12))]);
+        assert_eq!(months[1].posts[0].date, "01-22");
+    }
+
+    #[test]
+    fn test_count_by_month() {
+        let counts = count_by_month(&posts());
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].year, 2025);
+        assert_eq!(counts[0].count, 3);
+        assert_eq!(
+            counts[0].months,
+            [MonthCount { month: 2, count: 1 }, MonthCount { month: 1, count: 2 }]
+        );
+        assert_eq!(counts[1].months, [MonthCount { month: 12, count: 1 }]);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let posts = posts();
+        // 2025年1月1日是周三
+        let calendar = Calendar::new(2025, 1, &posts).unwrap();
+        assert_eq!(calendar.weeks.len(), 5);
+        assert!(calendar.weeks.iter().all(|week| week.len() == 7));
+        assert!(calendar.weeks[0][..2].iter().all(Option::is_none));
+        let first = calendar.weeks[0][2].as_ref().unwrap();
+        assert_eq!((first.day, first.posts[0].url.as_str()), (1, "b"));
+        let day22 = calendar.weeks[3][2].as_ref().unwrap();
+        assert_eq!((day22.day, day22.posts.len()), (22, 1));
+        assert!(calendar.weeks[4][4].as_ref().unwrap().posts.is_empty());
+        assert!(calendar.weeks[4][5].is_none());
+
+        // 2025年2月1日是周六，2024年12月1日是周日
+        assert_eq!(Calendar::new(2025, 2, &posts).unwrap().weeks.len(), 5);
+        assert_eq!(Calendar::new(2024, 12, &posts).unwrap().weeks.len(), 6);
+        assert!(Calendar::new(2025, 13, &posts).is_none());
+        assert!(Calendar::new(2025, 0, &posts).is_none());
+    }
+
+    #[test]
+    fn test_on_this_day() {
+        let mut posts = posts();
+        posts.push(post("old", "date: 2023-01-22T20:00:00+08:00", ""));
+        let mut draft = post("draft", "date: 2022-01-22T08:00:00+08:00", "");
+        draft.front_matter.draft = true;
+        posts.push(draft);
+
+        let found = on_this_day(&posts, NaiveDate::from_ymd_opt(2026, 1, 22).unwrap());
+        let found: Vec<(u32, u32, &str)> = found
+            .iter()
+            .map(|d| (d.year, d.years_ago, d.post.url.as_str()))
+            .collect();
+        assert_eq!(found, [(2025, 1, "c"), (2023, 3, "old")]);
+        // 只取参照日期之前的年份
+        let found = on_this_day(&posts, NaiveDate::from_ymd_opt(2025, 1, 22).unwrap());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].post.url, "old");
+        assert!(on_this_day(&posts, NaiveDate::from_ymd_opt(2025, 1, 23).unwrap()).is_empty());
+    }
+}
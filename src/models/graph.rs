@@ -0,0 +1,322 @@
+use super::{Post, WikiIndex};
+use crate::utils::obsidian::{self, Piece};
+use crate::utils::shortcode;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd, TextMergeStream};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// 引用上下文在链接之前保留的字符数
+const CONTEXT_BEFORE: usize = 40;
+/// 引用上下文在链接之后保留的字符数
+const CONTEXT_AFTER: usize = 60;
+
+/// 引用当前文章的一篇文章
+///
+/// # 字段说明
+/// * `title` - 引用方文章标题
+/// * `url` - 引用方文章访问地址
+/// * `context` - 链接所在段落中链接附近的文本
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Backlink {
+    pub title: String,
+    pub url: String,
+    pub context: String,
+}
+
+/// 文章关系图数据，供 `/graph.json` 输出
+///
+/// # 字段说明
+/// * `nodes` - 已发布的文章
+/// * `links` - 文章之间的链接与共享标签关系
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GraphData {
+    pub nodes: Vec<GraphNode>,
+    pub links: Vec<GraphLink>,
+}
+
+/// 关系图中的文章节点
+///
+/// # 字段说明
+/// * `id` - 文章URL标识
+/// * `title` - 文章标题
+/// * `url` - 文章访问地址
+/// * `tags` - 文章标签
+/// * `backlinks` - 被其他文章引用的次数
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub tags: Vec<String>,
+    pub backlinks: usize,
+}
+
+/// 关系图中的一条边
+///
+/// # 字段说明
+/// * `source` / `target` - 两端文章的URL标识
+/// * `kind` - `link` 表示 source 链接到 target，`tag` 表示两篇文章有共同标签
+/// * `tags` - 共同标签，仅 `tag` 类型有值
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphLink {
+    pub source: String,
+    pub target: String,
+    #[serde(rename = "type")]
+    pub kind: GraphLinkKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// 关系图边的类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphLinkKind {
+    Link,
+    Tag,
+}
+
+/// 文章之间的链接关系
+///
+/// 在加载文章时从所有文章中提取站内链接（Markdown 链接与双链），
+/// 构建反向链接表与关系图。
+#[derive(Debug, Default)]
+pub struct LinkGraph {
+    backlinks: HashMap<String, Vec<Backlink>>,
+    data: GraphData,
+}
+
+impl LinkGraph {
+    /// 根据双链索引中的已发布文章构建链接关系
+    ///
+    /// # 参数
+    /// * `wiki` - 双链索引
+    /// * `site_url` - 站点地址，以其开头的绝对链接视为站内链接
+    pub fn build(wiki: &WikiIndex, site_url: &str) -> Self {
+        let posts = wiki.posts();
+        let mut backlinks: HashMap<String, Vec<Backlink>> = HashMap::new();
+        let mut links = Vec::new();
+
+        for post in posts {
+            for (target, context) in extract_links(post, wiki, site_url) {
+                links.push(GraphLink {
+                    source: post.url.clone(),
+                    target: target.clone(),
+                    kind: GraphLinkKind::Link,
+                    tags: Vec::new(),
+                });
+                backlinks.entry(target).or_default().push(Backlink {
+                    title: post.front_matter.title.clone(),
+                    url: format!("/post/{}/", post.url),
+                    context,
+                });
+            }
+        }
+
+        let tag_sets: Vec<BTreeSet<&str>> = posts
+            .iter()
+            .map(|post| {
+                post.front_matter
+                    .tags
+                    .iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect()
+            })
+            .collect();
+        for (i, a) in posts.iter().enumerate() {
+            for (j, b) in posts.iter().enumerate().skip(i + 1) {
+                let shared: Vec<String> = tag_sets[i]
+                    .intersection(&tag_sets[j])
+                    .map(|tag| tag.to_string())
+                    .collect();
+                if !shared.is_empty() {
+                    links.push(GraphLink {
+                        source: a.url.clone(),
+                        target: b.url.clone(),
+                        kind: GraphLinkKind::Tag,
+                        tags: shared,
+                    });
+                }
+            }
+        }
+
+        let nodes = posts
+            .iter()
+            .map(|post| GraphNode {
+                id: post.url.clone(),
+                title: post.front_matter.title.clone(),
+                url: format!("/post/{}/", post.url),
+                tags: post.front_matter.tags.clone().unwrap_or_default(),
+                backlinks: backlinks.get(&post.url).map_or(0, Vec::len),
+            })
+            .collect();
+
+        Self {
+            backlinks,
+            data: GraphData { nodes, links },
+        }
+    }
+
+    /// 引用指定文章的文章列表，按引用方发布日期降序
+    pub fn backlinks(&self, url: &str) -> Vec<Backlink> {
+        self.backlinks.get(url).cloned().unwrap_or_default()
+    }
+
+    /// 关系图数据
+    pub fn data(&self) -> &GraphData {
+        &self.data
+    }
+}
+
+/// 提取文章中指向其他文章的链接，每个目标只保留第一次出现的上下文
+///
+/// # 返回
+/// * `Vec<(String, String)>` - 目标文章URL标识与链接上下文
+fn extract_links(post: &Post, wiki: &WikiIndex, site_url: &str) -> Vec<(String, String)> {
+    let content = shortcode::strip(&post.content);
+    let mut links: Vec<(String, String)> = Vec::new();
+    // 当前段落的纯文本，以及其中链接的目标与字符位置
+    let mut block = String::new();
+    let mut pending: Vec<(String, usize)> = Vec::new();
+    let mut code_depth = 0;
+
+    let mut flush = |block: &mut String, pending: &mut Vec<(String, usize)>| {
+        for (target, offset) in pending.drain(..) {
+            if target != post.url && !links.iter().any(|(t, _)| *t == target) {
+                links.push((target, context(block, offset)));
+            }
+        }
+        block.clear();
+    };
+
+    for event in TextMergeStream::new(Parser::new_ext(&content, Options::ENABLE_TABLES)) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => code_depth += 1,
+            Event::End(TagEnd::CodeBlock) => code_depth -= 1,
+            Event::Text(text) if code_depth == 0 => {
+                for piece in obsidian::split_wikilinks(&text) {
+                    match piece {
+                        Piece::Text(text) => block.push_str(text),
+                        Piece::Link(link) => {
+                            if let Some(target) = (!WikiIndex::is_attachment(link.target))
+                                .then(|| wiki.resolve(link.target))
+                                .flatten()
+                            {
+                                pending.push((target.url.clone(), block.chars().count()));
+                            }
+                            block.push_str(link.alias.unwrap_or(link.target));
+                        }
+                    }
+                }
+            }
+            Event::Code(text) => block.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => block.push(' '),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some(target) = internal_target(&dest_url, site_url, wiki) {
+                    pending.push((target, block.chars().count()));
+                }
+            }
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::TableCell
+                | TagEnd::BlockQuote(_),
+            ) => flush(&mut block, &mut pending),
+            _ => {}
+        }
+    }
+    flush(&mut block, &mut pending);
+    links
+}
+
+/// 判断链接是否指向站内文章，返回文章URL标识
+fn internal_target(dest_url: &str, site_url: &str, wiki: &WikiIndex) -> Option<String> {
+    let site_url = site_url.trim_end_matches('/');
+    let path = match dest_url.strip_prefix(site_url) {
+        Some(path) if !site_url.is_empty() => path,
+        _ => dest_url,
+    };
+    let slug = path.strip_prefix("/post/")?.split(['/', '#', '?']).next()?;
+    wiki.by_url(slug).map(|post| post.url.clone())
+}
+
+/// 截取链接附近的文本作为上下文
+fn context(block: &str, offset: usize) -> String {
+    let chars: Vec<char> = block.chars().collect();
+    let start = offset.saturating_sub(CONTEXT_BEFORE);
+    let end = (offset + CONTEXT_AFTER).min(chars.len());
+    let text: String = chars[start..end].iter().collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        text.trim(),
+        if end < chars.len() { "…" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+    use std::path::Path;
+
+    fn graph() -> LinkGraph {
+        let wiki = WikiIndex::new(
+            vec![
+                post("a", "title: 文章A\ntags: [rust]", "开头提到了 [[文章B|B]]，又提到 [B](/post/b/#x)。"),
+                post(
+                    "b",
+                    "title: 文章B\ntags: [rust, web]",
+                    "见 [A](https://example.com/post/a/) 与 [[b]]\n\n```\n[[文章C]]\n```\n",
+                ),
+                post("c", "title: 文章C\ntags: [web]", "外链 [A](https://other.com/post/a/)"),
+            ],
+            Path::new("static"),
+        );
+        LinkGraph::build(&wiki, "https://example.com/")
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let graph = graph();
+        assert_eq!(
+            graph.backlinks("b"),
+            vec![Backlink {
+                title: "文章A".to_string(),
+                url: "/post/a/".to_string(),
+                context: "开头提到了 B，又提到 B。".to_string(),
+            }]
+        );
+        // 自引用、代码块与外站链接不计入
+        let backlinks_a = graph.backlinks("a");
+        assert_eq!(backlinks_a.len(), 1);
+        assert_eq!(backlinks_a[0].url, "/post/b/");
+        assert!(graph.backlinks("c").is_empty());
+    }
+
+    #[test]
+    fn test_graph_data() {
+        let graph = graph();
+        let data = graph.data();
+        assert_eq!(data.nodes.len(), 3);
+        assert_eq!(data.nodes[1].backlinks, 1);
+        let tag_links: Vec<_> = data
+            .links
+            .iter()
+            .filter(|l| l.kind == GraphLinkKind::Tag)
+            .map(|l| (l.source.as_str(), l.target.as_str(), l.tags.join(",")))
+            .collect();
+        assert_eq!(
+            tag_links,
+            vec![("a", "b", "rust".to_string()), ("b", "c", "web".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_context_is_truncated() {
+        let long = "字".repeat(100);
+        assert_eq!(context(&long, 50), format!("…{}", "字".repeat(90)));
+        assert_eq!(context(&long, 20), format!("{}…", "字".repeat(80)));
+    }
+}
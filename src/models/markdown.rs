@@ -0,0 +1,401 @@
+use pulldown_cmark::Options;
+use serde::de::{self, IntoDeserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Markdown 扩展语法
+///
+/// 对应 pulldown-cmark 的解析选项，在 config.toml 与文章 Front Matter 中
+/// 使用 snake_case 名称书写，例如 `tables`、`smart_punctuation`。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownExtension {
+    /// 表格
+    Tables,
+    /// 脚注
+    Footnotes,
+    /// 删除线 `~~text~~`
+    Strikethrough,
+    /// 任务列表 `- [x] item`
+    Tasklists,
+    /// 智能标点：直引号转弯引号、`--` 转破折号等
+    SmartPunctuation,
+    /// 标题属性 `# 标题 {#id .class}`
+    HeadingAttributes,
+    /// 定义列表
+    DefinitionList,
+    /// GFM 引用块提示 `> [!NOTE]`
+    Gfm,
+    /// 数学公式 `$...$` 与 `$$...$$`，在服务端转换为 MathML。
+    /// 默认不启用，避免正文中的金额等 `$` 被误识别为公式
+    Math,
+}
+
+impl MarkdownExtension {
+    /// 转换为对应的 pulldown-cmark 解析选项
+    fn option(self) -> Options {
+        match self {
+            Self::Tables => Options::ENABLE_TABLES,
+            Self::Footnotes => Options::ENABLE_FOOTNOTES,
+            Self::Strikethrough => Options::ENABLE_STRIKETHROUGH,
+            Self::Tasklists => Options::ENABLE_TASKLISTS,
+            Self::SmartPunctuation => Options::ENABLE_SMART_PUNCTUATION,
+            Self::HeadingAttributes => Options::ENABLE_HEADING_ATTRIBUTES,
+            Self::DefinitionList => Options::ENABLE_DEFINITION_LIST,
+            Self::Gfm => Options::ENABLE_GFM,
+            Self::Math => Options::ENABLE_MATH,
+        }
+    }
+
+    /// 在配置中书写的名称
+    fn name(self) -> &'static str {
+        match self {
+            Self::Tables => "tables",
+            Self::Footnotes => "footnotes",
+            Self::Strikethrough => "strikethrough",
+            Self::Tasklists => "tasklists",
+            Self::SmartPunctuation => "smart_punctuation",
+            Self::HeadingAttributes => "heading_attributes",
+            Self::DefinitionList => "definition_list",
+            Self::Gfm => "gfm",
+            Self::Math => "math",
+        }
+    }
+}
+
+/// 文章对全站扩展语法的调整
+///
+/// 在 Front Matter 的 `markdown_extensions` 中书写，在全站配置的基础上增减：
+/// `math` 或 `+math` 额外启用，`-smart_punctuation` 停用，未提及的扩展沿用全站配置。
+/// 例如 `markdown_extensions: [math, -smart_punctuation]`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionOverride {
+    /// 启用扩展语法
+    Enable(MarkdownExtension),
+    /// 停用扩展语法
+    Disable(MarkdownExtension),
+}
+
+impl ExtensionOverride {
+    /// 将调整应用到解析选项上
+    pub fn apply(self, options: Options) -> Options {
+        match self {
+            Self::Enable(ext) => options | ext.option(),
+            Self::Disable(ext) => options - ext.option(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtensionOverride {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let (disable, name) = match value.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, value.strip_prefix('+').unwrap_or(&value)),
+        };
+        let ext = MarkdownExtension::deserialize(name.into_deserializer())
+            .map_err(|e: de::value::Error| de::Error::custom(e))?;
+        Ok(if disable { Self::Disable(ext) } else { Self::Enable(ext) })
+    }
+}
+
+impl Serialize for ExtensionOverride {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Enable(ext) => serializer.serialize_str(ext.name()),
+            Self::Disable(ext) => serializer.serialize_str(&format!("-{}", ext.name())),
+        }
+    }
+}
+
+/// Markdown 渲染配置
+///
+/// # 字段说明
+/// * `extensions` - 全站启用的扩展语法，文章可在 Front Matter 中通过
+///   `markdown_extensions` 增减，见 [`ExtensionOverride`]
+/// * `toc_min_level` - 目录包含的最高标题层级，默认为2（h2）
+/// * `toc_max_level` - 目录包含的最低标题层级，默认为3（h3）
+/// * `heading_anchors` - 是否在标题后追加锚点链接，默认关闭
+/// * `highlight` - 代码块语法高亮配置
+/// * `images` - 正文图片的响应式处理配置
+/// * `links` - 正文外部链接的属性配置
+/// * `sanitize` - 正文HTML的过滤策略
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkdownConfig {
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<MarkdownExtension>,
+    #[serde(default = "default_toc_min_level")]
+    pub toc_min_level: u8,
+    #[serde(default = "default_toc_max_level")]
+    pub toc_max_level: u8,
+    #[serde(default)]
+    pub heading_anchors: bool,
+    #[serde(default)]
+    pub highlight: HighlightConfig,
+    #[serde(default)]
+    pub images: ImageConfig,
+    #[serde(default)]
+    pub links: LinkConfig,
+    #[serde(default)]
+    pub sanitize: SanitizeConfig,
+}
+
+impl Default for MarkdownConfig {
+    fn default() -> Self {
+        Self {
+            extensions: default_extensions(),
+            toc_min_level: default_toc_min_level(),
+            toc_max_level: default_toc_max_level(),
+            heading_anchors: false,
+            highlight: HighlightConfig::default(),
+            images: ImageConfig::default(),
+            links: LinkConfig::default(),
+            sanitize: SanitizeConfig::default(),
+        }
+    }
+}
+
+fn default_toc_min_level() -> u8 {
+    2
+}
+
+fn default_toc_max_level() -> u8 {
+    3
+}
+
+/// 代码高亮的输出方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HighlightMode {
+    /// 颜色以内联样式写入HTML
+    Inline,
+    /// 输出CSS类名，配色由 `/highlight.css` 样式表提供
+    Classes,
+}
+
+/// 代码块语法高亮配置
+///
+/// # 字段说明
+/// * `enabled` - 是否在服务端高亮代码块，默认开启
+/// * `theme` - 配色主题名称，如 `InspiredGitHub`、`base16-ocean.dark`、`Solarized (light)`
+/// * `mode` - 输出方式，默认为 `classes`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HighlightConfig {
+    #[serde(default = "default_highlight_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_highlight_theme")]
+    pub theme: String,
+    #[serde(default = "default_highlight_mode")]
+    pub mode: HighlightMode,
+}
+
+impl Default for HighlightConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_highlight_enabled(),
+            theme: default_highlight_theme(),
+            mode: default_highlight_mode(),
+        }
+    }
+}
+
+fn default_highlight_enabled() -> bool {
+    true
+}
+
+fn default_highlight_theme() -> String {
+    "InspiredGitHub".to_string()
+}
+
+fn default_highlight_mode() -> HighlightMode {
+    HighlightMode::Classes
+}
+
+/// 正文图片处理配置
+///
+/// # 字段说明
+/// * `enabled` - 是否处理 `static/` 下的本地图片，默认开启
+/// * `widths` - 生成的缩略图宽度（像素），不超过原图宽度的才会生成
+/// * `webp` - 是否额外生成WebP格式，体积不小于原格式时不使用
+/// * `quality` - JPEG缩略图的压缩质量（1-100）
+/// * `sizes` - `<img>` 的 `sizes` 属性，描述图片在页面中的显示宽度
+/// * `cache_dir` - 缩略图缓存目录，通过 `/images/` 路径访问
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageConfig {
+    #[serde(default = "default_images_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_image_widths")]
+    pub widths: Vec<u32>,
+    #[serde(default = "default_images_webp")]
+    pub webp: bool,
+    #[serde(default = "default_image_quality")]
+    pub quality: u8,
+    #[serde(default = "default_image_sizes")]
+    pub sizes: String,
+    #[serde(default = "default_image_cache_dir")]
+    pub cache_dir: String,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_images_enabled(),
+            widths: default_image_widths(),
+            webp: default_images_webp(),
+            quality: default_image_quality(),
+            sizes: default_image_sizes(),
+            cache_dir: default_image_cache_dir(),
+        }
+    }
+}
+
+fn default_images_enabled() -> bool {
+    true
+}
+
+fn default_image_widths() -> Vec<u32> {
+    vec![480, 800, 1200]
+}
+
+fn default_images_webp() -> bool {
+    true
+}
+
+fn default_image_quality() -> u8 {
+    80
+}
+
+fn default_image_sizes() -> String {
+    "(max-width: 800px) 100vw, 800px".to_string()
+}
+
+fn default_image_cache_dir() -> String {
+    "cache/images".to_string()
+}
+
+/// 正文外部链接配置
+///
+/// 链接地址为 `http(s)://` 或 `//` 开头且主机名与站点地址不同时视为外部链接。
+///
+/// # 字段说明
+/// * `enabled` - 是否处理外部链接，默认开启
+/// * `rel` - 外部链接的 `rel` 属性值，默认为 `nofollow noopener noreferrer`
+/// * `target` - 外部链接的 `target` 属性，默认为 `_blank`，为空时不设置
+/// * `class` - 外部链接的CSS类名，用于显示外链标识，为空时不设置
+/// * `allowlist` - 信任的域名（含子域名），这些域名的链接不添加 `nofollow`
+/// * `trust_friends` - 是否将 `static/friends.yaml` 中的友链域名加入信任列表，默认开启
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LinkConfig {
+    #[serde(default = "default_links_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_link_rel")]
+    pub rel: Vec<String>,
+    #[serde(default = "default_link_target")]
+    pub target: String,
+    #[serde(default = "default_link_class")]
+    pub class: String,
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+    #[serde(default = "default_trust_friends")]
+    pub trust_friends: bool,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_links_enabled(),
+            rel: default_link_rel(),
+            target: default_link_target(),
+            class: default_link_class(),
+            allowlist: Vec::new(),
+            trust_friends: default_trust_friends(),
+        }
+    }
+}
+
+fn default_links_enabled() -> bool {
+    true
+}
+
+fn default_link_rel() -> Vec<String> {
+    vec![
+        "nofollow".to_string(),
+        "noopener".to_string(),
+        "noreferrer".to_string(),
+    ]
+}
+
+fn default_link_target() -> String {
+    "_blank".to_string()
+}
+
+fn default_link_class() -> String {
+    "external-link".to_string()
+}
+
+fn default_trust_friends() -> bool {
+    true
+}
+
+/// 正文HTML的过滤策略
+///
+/// 两种策略都会移除 `<script>`、事件属性与 `javascript:` 链接等危险内容，
+/// 区别在于允许保留的标签与属性。
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlPolicy {
+    /// 不过滤，原样输出
+    Off,
+    /// 宽松：额外允许 `<iframe>`、`<video>`、`<audio>` 与任意 `style` 属性
+    Trusted,
+    /// 严格：只允许渲染器自身生成的标签，`style` 属性仅保留对齐与文字颜色等样式
+    Strict,
+}
+
+/// 正文HTML过滤配置
+///
+/// # 字段说明
+/// * `posts` - 站长撰写的文章（`post/` 目录）使用的策略，默认为 `trusted`
+/// * `uploads` - 通过 `/upload/` 上传的文章（`post/uploads/` 目录）使用的策略，默认为 `strict`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizeConfig {
+    #[serde(default = "default_posts_policy")]
+    pub posts: HtmlPolicy,
+    #[serde(default = "default_uploads_policy")]
+    pub uploads: HtmlPolicy,
+}
+
+impl Default for SanitizeConfig {
+    fn default() -> Self {
+        Self {
+            posts: default_posts_policy(),
+            uploads: default_uploads_policy(),
+        }
+    }
+}
+
+fn default_posts_policy() -> HtmlPolicy {
+    HtmlPolicy::Trusted
+}
+
+fn default_uploads_policy() -> HtmlPolicy {
+    HtmlPolicy::Strict
+}
+
+/// 默认启用的扩展语法，与 GitHub Flavored Markdown 保持兼容
+fn default_extensions() -> Vec<MarkdownExtension> {
+    vec![
+        MarkdownExtension::Tables,
+        MarkdownExtension::Footnotes,
+        MarkdownExtension::Strikethrough,
+        MarkdownExtension::Tasklists,
+        MarkdownExtension::SmartPunctuation,
+        MarkdownExtension::HeadingAttributes,
+    ]
+}
+
+/// 将扩展语法列表合并为 pulldown-cmark 解析选项
+pub fn to_options(extensions: &[MarkdownExtension]) -> Options {
+    extensions
+        .iter()
+        .fold(Options::empty(), |options, ext| options | ext.option())
+}
@@ -1,34 +1,67 @@
-/// 博客系统核心数据模型模块
-/// 
-/// # 模块说明
-/// * `post` - 博客文章相关模型
-/// * `tag` - 标签相关模型
-/// * `category` - 分类相关模型
-/// * `site` - 站点配置模型
-/// * `rss` - RSS订阅相关模型
-/// * `sitemap` - 站点地图相关模型
-/// * `archive` - 文章归档相关模型
-/// * `response` - HTTP响应相关模型
-/// * `page` - 分页相关模型
+//! 博客系统核心数据模型模块
+//! 
+//! # 模块说明
+//! * `post` - 博客文章相关模型
+//! * `taxonomy` - 分类法（标签、分类及自定义分类法）模型
+//! * `term_meta` - 词项说明页面（`_index.md`）
+//! * `site` - 站点配置模型
+//! * `rss` - RSS订阅相关模型
+//! * `sitemap` - 站点地图相关模型
+//! * `archive` - 文章归档相关模型
+//! * `response` - HTTP响应相关模型
+//! * `page` - 分页相关模型
+//! * `markdown` - Markdown渲染配置模型
+//! * `toc` - 文章目录模型
+//! * `wiki` - Obsidian 双链索引
+//! * `graph` - 文章链接关系与反向链接
+//! * `search` - 站内搜索索引
+//! * `search_export` - 供静态镜像使用的搜索索引
+//! * `suggest` - 拼音输入建议索引
+//! * `related` - 相关文章推荐
+//! * `query` - 文章查询条件（筛选、排序与分页）
+//! * `stats` - 写作统计与年度回顾
+//! * `test_util` - 测试用的文章构造工具
 
 pub mod post;
-pub mod tag;
-pub mod category;
+pub mod taxonomy;
+pub mod term_meta;
 pub mod site;
 pub mod rss;
 pub mod sitemap;
 pub mod archive;
 pub mod page;
 pub mod friend;
+pub mod markdown;
+pub mod toc;
+pub mod wiki;
+pub mod graph;
+pub mod search;
+pub mod search_export;
+pub mod suggest;
+pub mod related;
+pub mod query;
+pub mod stats;
+#[cfg(test)]
+pub(crate) mod test_util;
 
 // 导出常用类型，方便其他模块使用
 pub use post::Post;
 pub use post::FrontMatter;
-pub use tag::TagCount;
-pub use category::CategoryCount;
+pub use post::WordCount;
+pub use taxonomy::{TaxonomyConfig, TermAliases, TermCount, TermSlugs};
+pub use term_meta::{TermMeta, TermMetaIndex};
 pub use site::Site;
 pub use rss::{RssItem, RssFeed};
 pub use sitemap::{Sitemap,SitemapUrl};
-pub use archive::{Archive,ArchivePost};
+pub use archive::{Archive, Calendar, OnThisDay};
 pub use page::Page;
-pub use friend::FriendLink;
\ No newline at end of file
+pub use friend::FriendLink;
+pub use markdown::MarkdownConfig;
+pub use toc::TocItem;
+pub use wiki::WikiIndex;
+pub use graph::{Backlink, GraphData, LinkGraph};
+pub use search::{SearchConfig, SearchIndex};
+pub use search_export::SearchExport;
+pub use suggest::{SuggestIndex, Suggestion};
+pub use related::{RelatedConfig, RelatedIndex};
+pub use stats::{SiteStats, YearReview};
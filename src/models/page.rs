@@ -60,36 +60,35 @@ impl Page {
         }
     }
 
-    /// 通过直接指定总页数来创建分页实例（向后兼容的方法）
-    /// 
-    /// # 实现说明
-    /// 1. 确保当前页在有效范围内
-    /// 2. 计算上一页和下一页
-    /// 3. 通过总页数估算总条数（假设每页10条）
-    /// 
+    /// 由 `usize` 类型的总条数、页码与每页条数创建分页实例
+    ///
+    /// 超出 `u16` 范围的页码按最后一页处理，不会截断回绕到第0页
+    ///
     /// # 参数说明
-    /// * `count` - 直接指定的总页数
-    /// * `current` - 当前请求的页码
-    /// 
-    /// # 特殊说明
-    /// 这是一个为了保持与旧版本兼容的方法，优先使用 new() 方法
-    pub fn from_count(count: u16, current: u16) -> Self {
-        // 确保当前页不超过总页数
-        let current = if current > count { count } else { current };
-        // 计算上一页
-        let prev = if current > 1 { Some(current - 1) } else { None };
-        // 计算下一页
-        let next = if current < count {
-            Some(current + 1)
-        } else {
-            None
-        };
+    /// * `total` - 数据总条数
+    /// * `current` - 当前请求的页码，从1开始
+    /// * `per_page` - 每页显示的记录数
+    pub fn clamped(total: usize, current: usize, per_page: usize) -> Self {
+        let to_u16 = |n: usize| u16::try_from(n).unwrap_or(u16::MAX);
+        Self::new(
+            u32::try_from(total).unwrap_or(u32::MAX),
+            to_u16(current.max(1)),
+            to_u16(per_page.max(1)),
+        )
+    }
+}
 
-        Self {
-            count,
-            current,
-            prev,
-            next,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamped() {
+        let page = Page::clamped(25, 2, 10);
+        assert_eq!((page.count, page.current, page.prev, page.next), (3, 2, Some(1), Some(3)));
+        // 超出范围的页码按最后一页处理
+        assert_eq!(Page::clamped(25, 65536, 10).current, 3);
+        assert_eq!(Page::clamped(25, usize::MAX, 10).current, 3);
+        assert_eq!(Page::clamped(25, 0, 10).current, 1);
     }
 }
@@ -1,10 +1,16 @@
 // 导入所需的外部模块和类型
+use crate::models::markdown::{self, ExtensionOverride, MarkdownConfig}; // 导入Markdown渲染配置
 use crate::utils::html::strip_html_tags; // 导入HTML标签清理工具
-use crate::utils::{date_format, pinyin}; // 导入日期格式化和拼音转换工具
+use crate::models::toc::TocItem; // 导入目录结构
+use crate::models::wiki::WikiIndex; // 导入双链索引
+use crate::utils::shortcode::{self, ShortcodeRenderer}; // 导入短代码展开工具
+use crate::utils::links::{self, LinkPolicy}; // 导入外部链接处理工具
+use crate::utils::sanitize; // 导入HTML过滤工具
+use crate::utils::{date_format, heading, highlight, images, math, obsidian}; // 导入日期格式化、标题处理、代码高亮、图片处理、公式转换和 Obsidian 语法工具
 use chrono::{DateTime, FixedOffset}; // 导入时间处理相关类型
-use pulldown_cmark::{html, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd}; // 导入Markdown解析器
-use pulldown_cmark_toc::TableOfContents; // 导入目录生成工具
+use pulldown_cmark::{html, Event, Options, Parser, Tag, TagEnd, TextMergeStream}; // 导入Markdown解析器
 use serde::{Deserialize, Serialize}; // 导入序列化和反序列化trait
+use std::collections::BTreeMap; // 导入有序映射，保存 Front Matter 的其他字段
 
 /// 文章头部信息（Front Matter）结构体
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -18,6 +24,55 @@ pub struct FrontMatter {
     pub categories: Option<Vec<String>>, // 可选的文章分类列表
     #[serde(default)]
     pub tags: Option<Vec<String>>, // 可选的文章标签列表
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown_extensions: Option<Vec<ExtensionOverride>>, // 可选的Markdown扩展语法调整，在全站配置的基础上增减
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>, // 其他字段，自定义分类法（如 topics）的取值从这里读取
+}
+
+impl FrontMatter {
+    /// 获取文章在指定分类法下的取值
+    ///
+    /// `tags` 与 `categories` 读取对应字段，其他分类法读取同名的自定义字段，
+    /// 字段可以是字符串列表或单个字符串。
+    ///
+    /// # 参数
+    ///
+    /// * `taxonomy` - 分类法名称
+    pub fn terms(&self, taxonomy: &str) -> Vec<&str> {
+        let values = match taxonomy {
+            "tags" => self.tags.as_ref(),
+            "categories" => self.categories.as_ref(),
+            _ => {
+                return match self.extra.get(taxonomy) {
+                    Some(serde_yaml::Value::Sequence(values)) => {
+                        values.iter().filter_map(serde_yaml::Value::as_str).collect()
+                    }
+                    Some(serde_yaml::Value::String(value)) => vec![value.as_str()],
+                    _ => Vec::new(),
+                }
+            }
+        };
+        values.into_iter().flatten().map(String::as_str).collect()
+    }
+
+    /// 替换文章在指定分类法下的取值，与 [`Self::terms`] 读取的字段相同
+    ///
+    /// # 参数
+    ///
+    /// * `taxonomy` - 分类法名称
+    /// * `terms` - 新的取值
+    pub fn set_terms(&mut self, taxonomy: &str, terms: Vec<String>) {
+        match taxonomy {
+            "tags" => self.tags = Some(terms),
+            "categories" => self.categories = Some(terms),
+            _ => {
+                let values = terms.into_iter().map(serde_yaml::Value::String).collect();
+                self.extra
+                    .insert(taxonomy.to_string(), serde_yaml::Value::Sequence(values));
+            }
+        }
+    }
 }
 
 // 定义draft字段的默认值函数
@@ -31,48 +86,78 @@ pub struct Post {
     pub front_matter: FrontMatter, // 文章的元数据信息
     pub content: String,           // 文章的主体内容
     pub url: String,               // 文章的URL地址
+    #[serde(default = "default_trusted")]
+    pub trusted: bool,             // 是否为站长撰写的文章，上传的文章为false，决定正文HTML的过滤策略
+}
+
+fn default_trusted() -> bool {
+    true
 }
 
 impl Post {
-    /// 统计文章中包含的汉字数量
+    /// 获取文章使用的 Markdown 解析选项
+    ///
+    /// 以全站配置的扩展语法为基础，再应用文章 Front Matter 中 `markdown_extensions`
+    /// 的增减，例如 `[math]` 只额外启用公式，表格、脚注等仍沿用全站配置。
+    ///
+    /// # 参数
     ///
-    /// 通过遍历文章内容的每个字符，判断是否为汉字（Unicode 范围：\u4e00-\u9fff）来进行计数。
+    /// * `config` - 全站 Markdown 渲染配置
     ///
     /// # 返回值
     ///
-    /// * `usize` - 文章中汉字的总数
-    pub fn count_chinese_chars(&self) -> usize {
-        // 使用迭代器过滤出汉字并计数
-        self.content.chars().filter(|c| c.is_chinese()).count()
+    /// * `Options` - pulldown-cmark 解析选项
+    pub fn markdown_options(&self, config: &MarkdownConfig) -> Options {
+        self.front_matter
+            .markdown_extensions
+            .iter()
+            .flatten()
+            .fold(markdown::to_options(&config.extensions), |options, ext| ext.apply(options))
     }
 
-    /// 计算阅读文章所需的预估时间
+    /// 统计文章字数
     ///
-    /// 基于文章的汉字数量和给定的阅读速度，计算出预计阅读时间。
-    /// 如果计算结果小于1分钟，则返回1分钟作为最小阅读时间。
+    /// 使用 Markdown 解析器遍历正文文本，汉字（含 CJK 扩展区）按单字计数，
+    /// 拉丁文字按单词计数。代码块、数学公式、短代码标签与 Front Matter 不计入字数。
     ///
     /// # 参数
     ///
-    /// * `speed` - 阅读速度，表示每分钟可以阅读的汉字数量
+    /// * `config` - 全站 Markdown 渲染配置
     ///
     /// # 返回值
     ///
-    /// * `u16` - 预计阅读时间，单位为分钟
-    pub fn read_time(&self, speed: u16) -> u16 {
-        let count = self.count_chinese_chars() as u16; // 获取汉字总数
-        let time = count / speed; // 计算阅读时间
-        if time == 0 {
-            1 // 如果计算结果为0，返回1分钟
-        } else {
-            time // 否则返回计算得到的时间
+    /// * `WordCount` - 汉字数与单词数的统计结果
+    pub fn word_count(&self, config: &MarkdownConfig) -> WordCount {
+        let options =
+            self.markdown_options(config) | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+        // 智能标点会把 `don't` 拆成多个文本事件，合并后再统计
+        let content = shortcode::strip(&self.content);
+        let parser = TextMergeStream::new(Parser::new_ext(&content, options));
+
+        let mut count = WordCount::default();
+        let mut skip_depth = 0; // 处于代码块或元数据块内部时大于0
+        for event in parser {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) | Event::Start(Tag::MetadataBlock(_)) => {
+                    skip_depth += 1;
+                }
+                Event::End(TagEnd::CodeBlock) | Event::End(TagEnd::MetadataBlock(_)) => {
+                    skip_depth -= 1;
+                }
+                Event::Text(text) | Event::Code(text) if skip_depth == 0 => {
+                    count.add_text(&text);
+                }
+                _ => {}
+            }
         }
+        count
     }
 
     /// 生成文章的描述摘要
     ///
     /// 将文章的 Markdown 内容转换为纯文本，并截取指定长度作为描述。
     /// 处理流程：
-    /// 1. Markdown 转换为 HTML
+    /// 1. Markdown 转换为 HTML（数学公式与短代码标签不计入描述）
     /// 2. 清除 HTML 标签
     /// 3. 清理文本（去除多余空白、合并行）
     /// 4. 截取指定长度
@@ -80,13 +165,16 @@ impl Post {
     /// # 参数
     ///
     /// * `count` - 需要截取的字符数量
+    /// * `config` - 全站 Markdown 渲染配置
     ///
     /// # 返回值
     ///
     /// * `String` - 处理后的文章描述。如果处理过程中出现错误，则返回空字符串
-    pub fn generate_description(&self, count: usize) -> String {
+    pub fn generate_description(&self, count: usize, config: &MarkdownConfig) -> String {
         // 创建Markdown解析器实例
-        let parser = Parser::new(self.content.as_str());
+        let content = shortcode::strip(&self.content);
+        let parser = Parser::new_ext(&content, self.markdown_options(config))
+            .filter(|event| !matches!(event, Event::InlineMath(_) | Event::DisplayMath(_)));
         let mut html_output = String::new();
         // 将Markdown内容转换为HTML
         html::push_html(&mut html_output, parser);
@@ -108,130 +196,456 @@ impl Post {
         }
     }
 
-    /// 生成文章的HTML内容，为标题添加ID属性
+    /// 渲染文章正文，生成HTML内容与目录
     ///
     /// 处理流程：
-    /// 1. 初始化解析器
-    ///    - 不需要特殊的解析选项
-    ///    - 创建基本的Markdown解析器实例
+    /// 1. 展开短代码，`{{% %}}` 短代码的输出参与后续 Markdown 解析
+    /// 2. 根据全站配置与文章设置确定启用的扩展语法，创建解析器，
+    ///    处理 Obsidian 双链、嵌入与提示框
+    /// 3. 对围栏代码块进行语法高亮，将数学公式转换为 MathML，为图片添加尺寸与缩略图，
+    ///    为外部链接添加 `rel`、`target` 等属性
+    /// 4. 处理标题：生成唯一ID、保留行内标记，按配置追加锚点链接
+    /// 5. 输出HTML，替换回 `{{< >}}` 短代码的输出
+    /// 6. 按文章来源过滤其中的危险标签与属性（短代码输出含作者填写的参数与内容，一并过滤），
+    ///    并以同一批标题数据构建目录树
     ///
-    /// 2. 标题处理策略：
-    ///    - 跟踪h2和h3级别的标题
-    ///    - 收集标题文本内容
-    ///    - 将标题文本转换为拼音作为ID
-    ///    - 生成带有ID属性的HTML标题标签
+    /// # 参数
+    /// * `config` - 全站 Markdown 渲染配置
+    /// * `shortcodes` - 短代码渲染器
+    /// * `wiki` - 双链索引，用于解析 `[[文章]]` 与嵌入
+    /// * `links` - 外部链接处理策略
     ///
-    /// 3. 事件处理逻辑：
-    ///    - 标题开始：记录当前标题级别，准备收集内容
-    ///    - 文本内容：当在标题中时，累积文本内容
-    ///    - 标题结束：生成带ID的完整标题HTML
+    /// # 返回值
+    /// * `RenderedContent` - 正文HTML与目录树
+    pub fn render(
+        &self,
+        config: &MarkdownConfig,
+        shortcodes: &impl ShortcodeRenderer,
+        wiki: &WikiIndex,
+        links: &LinkPolicy,
+    ) -> RenderedContent {
+        self.render_nested(config, shortcodes, wiki, links, true)
+    }
+
+    /// 渲染不属于任何文章的可信 Markdown 正文，如词项说明页面 `_index.md` 的正文
     ///
-    /// 4. 其他内容保持不变
+    /// 与 [`Self::render`] 使用同一渲染管线，按站长撰写的文章过滤HTML，
+    /// 使用全站配置的扩展语法。
     ///
-    /// # 返回值
-    /// * `String` - 转换后的HTML内容
-    pub fn generate_html(&self) -> String {
-        // 创建基本的解析器选项和解析器实例
-        let options = Options::empty();
-        let parser = Parser::new_ext(&self.content, options);
-
-        // 用于追踪标题状态的变量
-        let mut current_heading_content = String::new(); // 收集当前标题的文本内容
-        let mut current_heading_level = None; // 记录当前处理的标题级别
-
-        // 转换事件流，处理标题并添加ID属性
-        let parser = parser.filter_map(|event| {
-            match event {
-                // 处理标题开始标签
-                Event::Start(Tag::Heading {
-                    level,
-                    id: _,
-                    classes: _,
-                    attrs: _,
-                }) if level == HeadingLevel::H2
-                    || level == HeadingLevel::H3
-                    || level == HeadingLevel::H4 =>
-                {
-                    // 重置标题收集状态
-                    current_heading_content.clear();
-                    current_heading_level = Some(level);
-                    None // 关键：不输出原始的开始标签
-                }
-                // 收集标题文本内容
-                Event::Text(text) if current_heading_level.is_some() => {
-                    current_heading_content.push_str(&text); // 累积标题文本
-                    None // 关键：暂存文本，不立即输出
-                }
-                // 处理标题结束标签
-                Event::End(TagEnd::Heading(level)) if current_heading_level == Some(level) => {
-                    // 生成拼音ID
-                    let id = pinyin::to_pinyin(current_heading_content.as_str()).join("-");
-                    // 构造带ID的HTML标题
-                    let heading_html = format!(
-                        r#"<h{} id="{}">{}</h{}>"#,
-                        level as u8, id, current_heading_content, level as u8
-                    );
-
-                    // 重置状态并输出处理后的HTML
-                    current_heading_level = None;
-                    current_heading_content.clear();
-                    Some(Event::Html(CowStr::from(heading_html)))
-                }
-                // 保持其他内容不变
-                other => Some(other),
-            }
-        });
+    /// # 参数
+    /// * `content` - Markdown 正文
+    /// * `config` - 全站 Markdown 渲染配置
+    /// * `shortcodes` - 短代码渲染器
+    /// * `wiki` - 双链索引
+    /// * `links` - 外部链接处理策略
+    pub fn render_trusted(
+        content: &str,
+        config: &MarkdownConfig,
+        shortcodes: &impl ShortcodeRenderer,
+        wiki: &WikiIndex,
+        links: &LinkPolicy,
+    ) -> RenderedContent {
+        let post = Post {
+            front_matter: FrontMatter {
+                title: String::new(),
+                date: DateTime::default(),
+                draft: false,
+                categories: None,
+                tags: None,
+                markdown_extensions: None,
+                extra: BTreeMap::new(),
+            },
+            content: content.to_string(),
+            url: String::new(),
+            trusted: true,
+        };
+        post.render(config, shortcodes, wiki, links)
+    }
+
+    /// 渲染文章正文，`embed_notes` 为false时不再展开被嵌入的文章，避免循环嵌入
+    fn render_nested(
+        &self,
+        config: &MarkdownConfig,
+        shortcodes: &impl ShortcodeRenderer,
+        wiki: &WikiIndex,
+        links: &LinkPolicy,
+        embed_notes: bool,
+    ) -> RenderedContent {
+        let expanded = shortcode::expand(&self.content, shortcodes);
+        let parser = Parser::new_ext(&expanded.markdown, self.markdown_options(config));
+        let embed_note =
+            |post: &Post| post.render_nested(config, shortcodes, wiki, links, false).html;
+        let embed_note: Option<&dyn Fn(&Post) -> String> =
+            if embed_notes { Some(&embed_note) } else { None };
+        let events = obsidian::process_obsidian(parser, wiki, embed_note);
+        let events = highlight::process_code_blocks(events.into_iter(), &config.highlight);
+        let events = math::process_math(events.into_iter());
+        let events = images::process_images(events.into_iter(), &config.images);
+        let events = links::process_links(events.into_iter(), links);
+        let (events, headings) =
+            heading::process_headings(events.into_iter(), config.heading_anchors);
 
-        // 生成最终的HTML文档
         let mut html_output = String::new();
-        html::push_html(&mut html_output, parser);
-        html_output
+        html::push_html(&mut html_output, events.into_iter());
+        let policy = if self.trusted {
+            config.sanitize.posts
+        } else {
+            config.sanitize.uploads
+        };
+        let html_output = sanitize::sanitize(&expanded.restore(html_output), policy);
+
+        RenderedContent {
+            html: html_output,
+            toc: TocItem::build_tree(&headings, config.toc_min_level, config.toc_max_level),
+        }
+    }
+}
+
+/// 文章正文的渲染结果
+///
+/// # 字段说明
+/// * `html` - 正文HTML
+/// * `toc` - 目录树，目录项ID与正文标题ID一致
+#[derive(Debug, Clone)]
+pub struct RenderedContent {
+    pub html: String,
+    pub toc: Vec<TocItem>,
+}
+
+/// 文章字数统计结果
+///
+/// # 字段说明
+/// * `cjk` - 汉字（以及日文假名、韩文音节）数量，按单字计数
+/// * `latin` - 拉丁文字单词数量，按单词计数
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordCount {
+    pub cjk: usize,
+    pub latin: usize,
+}
+
+impl WordCount {
+    /// 总字数：汉字数与单词数之和
+    pub fn total(&self) -> usize {
+        self.cjk + self.latin
     }
 
-    /// 生成文章的目录结构
+    /// 统计一段纯文本，累加到当前结果中
     ///
-    /// 解析文章内容中的标题标记，生成层级化的目录结构。
-    /// 每个目录项包含：
-    /// - 标题层级（如：1表示h1，2表示h2）
-    /// - 标题文本
-    /// - 由标题转换的拼音ID（用于锚点链接）
+    /// 连续的字母、数字（以及单词内部的 `'`、`’`、`-`、`_`）视为一个单词，
+    /// 汉字既计数又作为单词的分隔符。
+    fn add_text(&mut self, text: &str) {
+        let mut in_word = false;
+        for c in text.chars() {
+            if c.is_cjk() {
+                self.cjk += 1;
+                in_word = false;
+            } else if c.is_alphanumeric() {
+                if !in_word {
+                    self.latin += 1;
+                    in_word = true;
+                }
+            } else if !(in_word && matches!(c, '\'' | '’' | '-' | '_')) {
+                in_word = false;
+            }
+        }
+    }
+
+    /// 根据阅读速度计算预估阅读时间（分钟），最少为1分钟
     ///
-    /// # 返回值
+    /// # 参数
     ///
-    /// * `Vec<(usize, String, String)>` - 目录结构的向量
-    ///   - `usize`: 标题层级
-    ///   - `String`: 标题文本
-    ///   - `String`: 标题对应的拼音ID
-    pub fn generate_toc(&self) -> Vec<(usize, String, String)> {
-        let mut toc = Vec::new();
-        // 创建目录生成器实例
-        let result = TableOfContents::new(&self.content);
-        // 遍历所有标题并处理
-        result.headings().for_each(|h| {
-            let id = pinyin::to_pinyin(h.text().as_str()).join("-"); // 将标题文本转换为拼音作为ID
-            toc.push((h.level() as usize, h.text(), id)); // 将标题信息添加到目录中
-        });
-        // let mark = result.to_cmark();                        // 转换为Markdown格式
-        // println!("toc: {:?}", mark);                         // 输出调试信息
-        toc // 返回生成的目录
+    /// * `cjk_speed` - 每分钟可以阅读的汉字数量
+    /// * `latin_speed` - 每分钟可以阅读的英文单词数量
+    pub fn read_time(&self, cjk_speed: usize, latin_speed: usize) -> u32 {
+        let minutes = self.cjk as f64 / cjk_speed.max(1) as f64
+            + self.latin as f64 / latin_speed.max(1) as f64;
+        (minutes.ceil() as u32).max(1)
     }
 }
 
-/// 定义用于判断字符是否为汉字的特征
+/// 定义用于判断字符是否为 CJK 文字的特征
 ///
-/// 此特征提供了一个方法来判断字符是否属于汉字范围（Unicode: 4E00-9FFF）
-trait ChineseChar {
-    /// 判断字符是否为汉字
+/// 覆盖 CJK 统一汉字及其扩展区、兼容汉字、日文假名和韩文音节
+trait CjkChar {
+    /// 判断字符是否为 CJK 文字
     ///
     /// # 返回值
     ///
-    /// * `bool` - 如果字符是汉字则返回 true，否则返回 false
-    fn is_chinese(&self) -> bool;
+    /// * `bool` - 如果字符是 CJK 文字则返回 true，否则返回 false
+    fn is_cjk(&self) -> bool;
 }
 
-impl ChineseChar for char {
-    fn is_chinese(&self) -> bool {
-        // 使用Unicode范围判断是否为汉字（基本汉字范围：4E00-9FFF）
-        matches!(self, '\u{4e00}'..='\u{9fff}')
+impl CjkChar for char {
+    fn is_cjk(&self) -> bool {
+        matches!(self,
+            '\u{4e00}'..='\u{9fff}'       // CJK 统一汉字
+            | '\u{3400}'..='\u{4dbf}'     // 扩展A区
+            | '\u{20000}'..='\u{2ebef}'   // 扩展B-F区
+            | '\u{30000}'..='\u{323af}'   // 扩展G-H区
+            | '\u{f900}'..='\u{faff}'     // 兼容汉字
+            | '\u{2f800}'..='\u{2fa1f}'   // 兼容汉字增补
+            | '\u{3040}'..='\u{30ff}'     // 日文平假名、片假名
+            | '\u{ac00}'..='\u{d7af}'     // 韩文音节
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::markdown::MarkdownExtension;
+    use crate::models::test_util::post;
+
+    #[test]
+    fn test_word_count_mixed() {
+        let count = post("test", "", "学习 Rust 的 async/await 语法，don't panic").word_count(&MarkdownConfig::default());
+        assert_eq!(count, WordCount { cjk: 5, latin: 5 });
+        assert_eq!(count.total(), 10);
+    }
+
+    #[test]
+    fn test_word_count_extension_ranges() {
+        // 㐀 位于扩展A区，𠀀 位于扩展B区
+        let count = post("test", "", "㐀𠀀中").word_count(&MarkdownConfig::default());
+        assert_eq!(count.cjk, 3);
+    }
+
+    #[test]
+    fn test_word_count_skips_code_and_front_matter() {
+        let content = "---\ntitle: 标题\n---\n\n正文\n\n```rust\nfn main() {}\n```\n";
+        assert_eq!(
+            post("test", "", content).word_count(&MarkdownConfig::default()),
+            WordCount { cjk: 2, latin: 0 }
+        );
+    }
+
+    /// 测试用短代码：`note` 输出 Markdown，`figure` 输出HTML
+    struct TestShortcodes;
+
+    impl ShortcodeRenderer for TestShortcodes {
+        fn render_shortcode(&self, shortcode: &shortcode::Shortcode) -> anyhow::Result<String> {
+            match shortcode.name.as_str() {
+                "note" => Ok(format!(
+                    "<div class=\"note\">\n\n{}\n\n</div>",
+                    shortcode.inner.as_deref().unwrap_or_default()
+                )),
+                "figure" => Ok(match shortcode.args.get("link") {
+                    Some(link) => format!(
+                        "<figure><a href=\"{}\"><img src=\"{}\"></a></figure>",
+                        link, shortcode.args["src"]
+                    ),
+                    None => format!("<figure><img src=\"{}\"></figure>", shortcode.args["src"]),
+                }),
+                "quote" => Ok(format!(
+                    "<blockquote>{}</blockquote>",
+                    shortcode.inner.as_deref().unwrap_or_default()
+                )),
+                name => Err(anyhow::anyhow!("unknown shortcode {}", name)),
+            }
+        }
+    }
+
+    fn render(content: &str) -> String {
+        post("test", "", content)
+            .render(
+                &MarkdownConfig::default(),
+                &TestShortcodes,
+                &WikiIndex::default(),
+                &LinkPolicy::default(),
+            )
+            .html
+    }
+
+    #[test]
+    fn test_render_tables() {
+        let html = render("| 名称 | 值 |\n| --- | --- |\n| a | 1 |\n");
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<td>a</td>"));
+    }
+
+    #[test]
+    fn test_render_footnotes() {
+        let html = render("正文[^1]\n\n[^1]: 脚注内容\n");
+        assert!(html.contains(r##"<sup class="footnote-reference"><a href="#1">1</a></sup>"##));
+        assert!(html.contains(r#"<div class="footnote-definition" id="1">"#));
+    }
+
+    #[test]
+    fn test_render_strikethrough() {
+        assert!(render("~~删除~~").contains("<del>删除</del>"));
+    }
+
+    #[test]
+    fn test_render_tasklists() {
+        let html = render("- [x] 完成\n- [ ] 待办\n");
+        // 过滤后的HTML按HTML5序列化，空元素不带结尾斜杠
+        assert!(html.contains(r#"<input disabled="" type="checkbox" checked="">"#));
+        assert!(html.contains(r#"<input disabled="" type="checkbox">"#));
+    }
+
+    #[test]
+    fn test_render_smart_punctuation() {
+        assert!(render("\"quoted\" -- text...").contains("“quoted” – text…"));
+    }
+
+    #[test]
+    fn test_render_heading_attributes() {
+        let html = render("# 标题 {#custom .lead}");
+        assert!(html.contains(r#"id="custom""#));
+        assert!(html.contains(r#"class="lead""#));
+    }
+
+    #[test]
+    fn test_front_matter_overrides_extensions() {
+        let post = post("test", "markdown_extensions: [-strikethrough]", "~~删除~~\n\n| a |\n|---|\n| 1 |\n");
+        let html = post
+            .render(
+                &MarkdownConfig::default(),
+                &TestShortcodes,
+                &WikiIndex::default(),
+                &LinkPolicy::default(),
+            )
+            .html;
+        assert!(!html.contains("<del>"));
+        assert!(html.contains("<table>"));
+        assert!(serde_yaml::from_str::<FrontMatter>(
+            "title: a\ndate: 2025-01-01T00:00:00+08:00\nmarkdown_extensions: [nope]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_toc_matches_heading_ids() {
+        let rendered = post("test", "", "## 总结\n\n## 总结 {#end}\n\n### Hello World\n")
+            .render(
+                &MarkdownConfig::default(),
+                &TestShortcodes,
+                &WikiIndex::default(),
+                &LinkPolicy::default(),
+            );
+        let ids: Vec<_> = rendered.toc.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["zong-jie", "end"]);
+        assert_eq!(rendered.toc[1].children[0].id, "hello-world");
+        for id in ["zong-jie", "end", "hello-world"] {
+            assert!(rendered.html.contains(&format!("id=\"{}\"", id)));
+        }
+    }
+
+    #[test]
+    fn test_math_excluded_from_counts_and_summary() {
+        let post = post("test", "", "公式 $E=mc^2$ 很短\n\n$$\\sum_{i=1}^n i$$\n");
+        let mut config = MarkdownConfig::default();
+        config.extensions.push(MarkdownExtension::Math);
+        assert_eq!(post.word_count(&config), WordCount { cjk: 4, latin: 0 });
+        let description = post.generate_description(100, &config);
+        assert!(description.starts_with("公式  很短"));
+        assert!(!description.contains("mc"));
+        let rendered = post.render(
+            &config,
+            &TestShortcodes,
+            &WikiIndex::default(),
+            &LinkPolicy::default(),
+        );
+        assert!(rendered.html.contains("<math"));
+    }
+
+    #[test]
+    fn test_math_is_opt_in() {
+        let html = render("原价 $10，现价$8。\n");
+        assert!(!html.contains("<math"));
+        assert!(html.contains("原价 $10，现价$8。"));
+
+        // 文章可在 Front Matter 中单独启用，全站的其他扩展语法仍然生效
+        for extensions in ["[math]", "[+math]"] {
+            let post = post(
+                "test",
+                &format!("markdown_extensions: {}", extensions),
+                "原价 $10，现价$8。\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+            );
+            let html = post
+                .render(
+                    &MarkdownConfig::default(),
+                    &TestShortcodes,
+                    &WikiIndex::default(),
+                    &LinkPolicy::default(),
+                )
+                .html;
+            assert!(html.contains("<math"));
+            assert!(html.contains("<table>"));
+        }
+    }
+
+    #[test]
+    fn test_uploaded_posts_use_strict_policy() {
+        let content = "<iframe src=\"https://player.example.com/1\"></iframe>\n\n\
+                       正文<script>alert(1)</script>\n";
+        let mut post = post("test", "", content);
+        let html = render(content);
+        assert!(html.contains("<iframe"));
+        assert!(!html.contains("<script"));
+
+        post.trusted = false;
+        let html = post
+            .render(
+                &MarkdownConfig::default(),
+                &TestShortcodes,
+                &WikiIndex::default(),
+                &LinkPolicy::default(),
+            )
+            .html;
+        assert!(!html.contains("<iframe"));
+        assert!(!html.contains("<script"));
+        assert!(html.contains("正文"));
+
+        // 短代码的参数与内容由作者填写，输出同样要过滤
+        post.content = "{{< quote >}}<img src=x onerror=alert(1)>{{< /quote >}}\n\n\
+                        {{< figure src=\"/a.png\" link=\"javascript:alert(1)\" >}}\n"
+            .to_string();
+        let html = post
+            .render(
+                &MarkdownConfig::default(),
+                &TestShortcodes,
+                &WikiIndex::default(),
+                &LinkPolicy::default(),
+            )
+            .html;
+        assert!(html.contains("<blockquote><img src=\"x\"></blockquote>"), "{}", html);
+        assert!(html.contains("<img src=\"/a.png\">"), "{}", html);
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_render_shortcodes() {
+        let post = post(
+            "test",
+            "",
+            "{{< figure src=\"/a.png\" >}}\n\n{{% note %}}\n## 提示\n\n**重点** 内容\n{{% /note %}}\n",
+        );
+        let rendered = post.render(
+            &MarkdownConfig::default(),
+            &TestShortcodes,
+            &WikiIndex::default(),
+            &LinkPolicy::default(),
+        );
+        assert!(rendered
+            .html
+            .starts_with("<figure><img src=\"/a.png\"></figure>\n"));
+        assert!(rendered.html.contains("<strong>重点</strong>"));
+        assert_eq!(rendered.toc[0].id, "ti-shi");
+        assert_eq!(
+            post.word_count(&MarkdownConfig::default()),
+            WordCount { cjk: 6, latin: 0 }
+        );
+    }
+
+    #[test]
+    fn test_read_time() {
+        let count = WordCount { cjk: 450, latin: 250 };
+        assert_eq!(count.read_time(300, 200), 3);
+        assert_eq!(WordCount::default().read_time(300, 200), 1);
+        // 长文不会溢出
+        let long = WordCount { cjk: 10_000_000, latin: 0 };
+        assert_eq!(long.read_time(200, 200), 50_000);
     }
 }
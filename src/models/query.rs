@@ -0,0 +1,331 @@
+use super::{Post, TaxonomyConfig};
+use crate::utils::pinyin;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+/// 文章列表的排序方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    /// 按发布日期降序
+    #[default]
+    Date,
+    /// 按 Front Matter 中的 `updated` 降序，未填写时使用发布日期
+    Updated,
+    /// 按标题的拼音升序
+    Title,
+    /// 按 Front Matter 中的 `weight` 升序，未填写的排在最后，相同时按发布日期降序
+    Weight,
+}
+
+/// 同一分类法下多个词项的匹配方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TermMatch {
+    /// 包含任一词项
+    #[default]
+    Any,
+    /// 包含所有词项
+    All,
+}
+
+/// 某个分类法的词项筛选条件
+#[derive(Debug, Clone)]
+struct TermFilter {
+    taxonomy: String,
+    terms: Vec<String>,
+    mode: TermMatch,
+}
+
+/// 文章查询条件
+///
+/// 以构建器方式组合筛选、排序与分页条件，由 `PostService::query` 执行，
+/// 首页、词项文章列表与 `/posts/` 都基于它实现。草稿总是被排除。
+///
+/// ```ignore
+/// let query = PostQuery::new()
+///     .tag("Rust")
+///     .category("技术")
+///     .year(2024)
+///     .sort(PostSort::Date)
+///     .page(1, 20);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PostQuery {
+    terms: Vec<TermFilter>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    author: Option<String>,
+    series: Option<String>,
+    text: Option<String>,
+    sort: PostSort,
+    page: usize,
+    per_page: usize,
+}
+
+/// 查询结果
+///
+/// # 字段说明
+/// * `posts` - 当前页的文章
+/// * `total` - 符合条件的文章总数
+/// * `current` - 实际查询的页码，超出范围的页码按最后一页处理
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    pub posts: Vec<Post>,
+    pub total: usize,
+    pub current: usize,
+}
+
+impl PostQuery {
+    /// 创建不带条件的查询：所有已发布文章，按日期降序，不分页
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 筛选包含指定词项的文章；同一分类法多次调用时默认匹配任一词项
+    ///
+    /// 层级分类法中上级词项包含所有下级词项的文章。
+    pub fn term(mut self, taxonomy: &str, term: &str) -> Self {
+        match self.terms.iter_mut().find(|f| f.taxonomy == taxonomy) {
+            Some(filter) => filter.terms.push(term.to_string()),
+            None => self.terms.push(TermFilter {
+                taxonomy: taxonomy.to_string(),
+                terms: vec![term.to_string()],
+                mode: TermMatch::Any,
+            }),
+        }
+        self
+    }
+
+    /// 设置分类法下多个词项的匹配方式
+    pub fn term_match(mut self, taxonomy: &str, mode: TermMatch) -> Self {
+        if let Some(filter) = self.terms.iter_mut().find(|f| f.taxonomy == taxonomy) {
+            filter.mode = mode;
+        }
+        self
+    }
+
+    /// 筛选包含指定标签的文章
+    pub fn tag(self, tag: &str) -> Self {
+        self.term("tags", tag)
+    }
+
+    /// 筛选属于指定分类的文章
+    pub fn category(self, category: &str) -> Self {
+        self.term("categories", category)
+    }
+
+    /// 筛选发布日期在区间内的文章，两端均包含，`None` 表示不限
+    pub fn between(mut self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    /// 筛选某一年发布的文章，年份超出日期范围时不匹配任何文章
+    pub fn year(self, year: i32) -> Self {
+        match (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year, 12, 31),
+        ) {
+            (Some(from), Some(to)) => self.between(Some(from), Some(to)),
+            // 起点晚于终点的空区间
+            _ => self.between(Some(NaiveDate::MAX), Some(NaiveDate::MIN)),
+        }
+    }
+
+    /// 筛选 Front Matter 中 `author` 为指定作者的文章
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
+    /// 筛选 Front Matter 中 `series` 为指定系列的文章
+    pub fn series(mut self, series: &str) -> Self {
+        self.series = Some(series.to_string());
+        self
+    }
+
+    /// 筛选标题或正文包含指定文本的文章，不区分大小写
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_lowercase());
+        self
+    }
+
+    /// 设置排序方式
+    pub fn sort(mut self, sort: PostSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// 设置分页，页码从1开始，超出范围时查询最后一页；`per_page` 为0时不分页
+    pub fn page(mut self, page: usize, per_page: usize) -> Self {
+        self.page = page;
+        self.per_page = per_page;
+        self
+    }
+
+    /// 判断文章是否符合筛选条件
+    ///
+    /// # 参数
+    /// * `post` - 文章
+    /// * `taxonomies` - 分类法配置，决定词项是否按层级匹配
+    pub fn matches(&self, post: &Post, taxonomies: &[TaxonomyConfig]) -> bool {
+        let front_matter = &post.front_matter;
+        if front_matter.draft {
+            return false;
+        }
+        let date = front_matter.date.date_naive();
+        if self.from.is_some_and(|from| date < from) || self.to.is_some_and(|to| date > to) {
+            return false;
+        }
+        if self.author.as_deref().is_some_and(|a| extra_str(post, "author") != Some(a)) {
+            return false;
+        }
+        if self.series.as_deref().is_some_and(|s| extra_str(post, "series") != Some(s)) {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            if !front_matter.title.to_lowercase().contains(text)
+                && !post.content.to_lowercase().contains(text)
+            {
+                return false;
+            }
+        }
+        self.terms.iter().all(|filter| {
+            let fallback;
+            let taxonomy = match taxonomies.iter().find(|t| t.name == filter.taxonomy) {
+                Some(taxonomy) => taxonomy,
+                None => {
+                    fallback = TaxonomyConfig::new(&filter.taxonomy, &filter.taxonomy);
+                    &fallback
+                }
+            };
+            let terms = taxonomy.post_terms(post);
+            let has = |term: &String| terms.contains(term);
+            match filter.mode {
+                TermMatch::Any => filter.terms.iter().any(has),
+                TermMatch::All => filter.terms.iter().all(has),
+            }
+        })
+    }
+
+    /// 执行查询
+    ///
+    /// # 参数
+    /// * `posts` - 按发布日期降序排列的所有文章
+    /// * `taxonomies` - 分类法配置
+    pub fn run(&self, posts: Vec<Post>, taxonomies: &[TaxonomyConfig]) -> QueryResult {
+        let mut posts: Vec<Post> = posts
+            .into_iter()
+            .filter(|post| self.matches(post, taxonomies))
+            .collect();
+        // 文章已按日期降序排列，稳定排序保证其余条件相同时较新的文章在前
+        match self.sort {
+            PostSort::Date => posts.sort_by_key(|p| Reverse(p.front_matter.date)),
+            PostSort::Updated => posts.sort_by_key(|p| Reverse(updated(p))),
+            PostSort::Title => {
+                posts.sort_by_cached_key(|p| pinyin::slugify(&p.front_matter.title))
+            }
+            PostSort::Weight => posts.sort_by_key(|p| weight(p).unwrap_or(i64::MAX)),
+        }
+        let total = posts.len();
+        let mut current = 1;
+        if self.per_page > 0 {
+            // 先把页码限制在有效范围内再分页，列表内容与分页导航显示的页码一致
+            current = self.page.clamp(1, total.div_ceil(self.per_page).max(1));
+            posts = posts
+                .into_iter()
+                .skip((current - 1) * self.per_page)
+                .take(self.per_page)
+                .collect();
+        }
+        QueryResult { posts, total, current }
+    }
+}
+
+/// 读取 Front Matter 中的字符串字段
+fn extra_str<'a>(post: &'a Post, key: &str) -> Option<&'a str> {
+    post.front_matter.extra.get(key)?.as_str()
+}
+
+/// 文章的更新时间，Front Matter 中的 `updated` 无效或未填写时使用发布日期
+fn updated(post: &Post) -> DateTime<FixedOffset> {
+    extra_str(post, "updated")
+        .and_then(|updated| DateTime::parse_from_rfc3339(updated).ok())
+        .unwrap_or(post.front_matter.date)
+}
+
+/// 文章的排序权重
+fn weight(post: &Post) -> Option<i64> {
+    post.front_matter.extra.get("weight")?.as_i64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+
+    fn posts() -> Vec<Post> {
+        vec![
+            post("d", "date: 2025-02-01T00:00:00+08:00\ntags: [Rust, 异步]\ncategories: [技术/Rust]\nweight: 2", "d 的正文"),
+            post("c", "date: 2024-06-01T00:00:00+08:00\ntags: [Rust]\ncategories: [技术]\nauthor: 苏蘅\nupdated: 2025-03-01T00:00:00+08:00", "c 的正文"),
+            post("b", "date: 2024-01-01T00:00:00+08:00\ntags: [Go]\ncategories: [技术/Go]\nseries: 入门\nweight: 1", "b 的正文"),
+            post("a", "date: 2023-12-31T00:00:00+08:00\ntags: [Rust]\ncategories: [生活]\nseries: 入门", "a 的正文"),
+            post("draft", "date: 2025-03-01T00:00:00+08:00\ndraft: true\ntags: [Rust]", "draft 的正文"),
+        ]
+    }
+
+    fn urls(query: PostQuery) -> Vec<String> {
+        let taxonomies = TaxonomyConfig::defaults();
+        query
+            .run(posts(), &taxonomies)
+            .posts
+            .into_iter()
+            .map(|p| p.url)
+            .collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(urls(PostQuery::new()), ["d", "c", "b", "a"]);
+        assert_eq!(urls(PostQuery::new().tag("Rust").category("技术").year(2024)), ["c"]);
+        assert_eq!(urls(PostQuery::new().category("技术")), ["d", "c", "b"]);
+        assert_eq!(urls(PostQuery::new().tag("Go").tag("异步")), ["d", "b"]);
+        assert_eq!(
+            urls(PostQuery::new().tag("Rust").tag("异步").term_match("tags", TermMatch::All)),
+            ["d"]
+        );
+        assert_eq!(urls(PostQuery::new().author("苏蘅")), ["c"]);
+        assert_eq!(urls(PostQuery::new().series("入门")), ["b", "a"]);
+        assert_eq!(urls(PostQuery::new().text("B 的")), ["b"]);
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1);
+        assert_eq!(urls(PostQuery::new().between(from, None)), ["d", "c", "b"]);
+        assert_eq!(urls(PostQuery::new().term("topics", "并发")), Vec::<String>::new());
+        assert_eq!(urls(PostQuery::new().year(99_999_999)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_sort_and_page() {
+        assert_eq!(urls(PostQuery::new().sort(PostSort::Updated)), ["c", "d", "b", "a"]);
+        assert_eq!(urls(PostQuery::new().sort(PostSort::Title)), ["a", "b", "c", "d"]);
+        assert_eq!(urls(PostQuery::new().sort(PostSort::Weight)), ["b", "d", "c", "a"]);
+
+        let taxonomies = TaxonomyConfig::defaults();
+        let result = PostQuery::new().page(2, 3).run(posts(), &taxonomies);
+        assert_eq!(result.total, 4);
+        assert_eq!(result.posts.len(), 1);
+        assert_eq!(result.posts[0].url, "a");
+        assert_eq!(result.current, 2);
+        // 超出范围的页码查询最后一页
+        let result = PostQuery::new().page(3, 3).run(posts(), &taxonomies);
+        assert_eq!((result.current, result.posts[0].url.as_str()), (2, "a"));
+        let result = PostQuery::new().page(usize::MAX, 10).run(posts(), &taxonomies);
+        assert_eq!((result.total, result.current, result.posts.len()), (4, 1, 4));
+        let result = PostQuery::new().page(0, 3).run(posts(), &taxonomies);
+        assert_eq!((result.current, result.posts.len()), (1, 3));
+        let result = PostQuery::new().year(1900).page(5, 3).run(posts(), &taxonomies);
+        assert_eq!((result.total, result.current), (0, 1));
+    }
+}
@@ -0,0 +1,242 @@
+use super::search::{plain_text, tokenize};
+use super::{MarkdownConfig, Post};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 相关文章配置，对应 config.toml 的 `[related]` 节
+///
+/// # 字段说明
+/// * `count` - 每篇文章推荐的文章数
+/// * `tag_weight` - 共享标签相似度（Jaccard）的权重
+/// * `category_weight` - 共享分类相似度（Jaccard）的权重
+/// * `text_weight` - 标题与正文 TF-IDF 余弦相似度的权重
+/// * `min_score` - 相似度低于该值的文章不视为相关
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RelatedConfig {
+    #[serde(default = "default_count")]
+    pub count: usize,
+    #[serde(default = "default_tag_weight")]
+    pub tag_weight: f32,
+    #[serde(default = "default_category_weight")]
+    pub category_weight: f32,
+    #[serde(default = "default_text_weight")]
+    pub text_weight: f32,
+    #[serde(default = "default_min_score")]
+    pub min_score: f32,
+}
+
+impl Default for RelatedConfig {
+    fn default() -> Self {
+        Self {
+            count: default_count(),
+            tag_weight: default_tag_weight(),
+            category_weight: default_category_weight(),
+            text_weight: default_text_weight(),
+            min_score: default_min_score(),
+        }
+    }
+}
+
+fn default_count() -> usize {
+    5
+}
+
+fn default_tag_weight() -> f32 {
+    1.0
+}
+
+fn default_category_weight() -> f32 {
+    0.5
+}
+
+fn default_text_weight() -> f32 {
+    1.0
+}
+
+fn default_min_score() -> f32 {
+    0.05
+}
+
+/// 参与相似度计算的文章特征
+struct Features<'a> {
+    tags: HashSet<&'a str>,
+    categories: HashSet<&'a str>,
+    /// 归一化后的 TF-IDF 向量
+    vector: HashMap<String, f32>,
+}
+
+/// 将标签或分类列表转为集合
+fn to_set(values: &Option<Vec<String>>) -> HashSet<&str> {
+    values.iter().flatten().map(String::as_str).collect()
+}
+
+/// 两个集合的 Jaccard 相似度，任一为空时为0
+fn jaccard(a: &HashSet<&str>, b: &HashSet<&str>) -> f32 {
+    let union = a.union(b).count();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// 两个归一化向量的余弦相似度
+fn cosine(a: &HashMap<String, f32>, b: &HashMap<String, f32>) -> f32 {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short
+        .iter()
+        .filter_map(|(term, weight)| long.get(term).map(|other| weight * other))
+        .sum()
+}
+
+/// 文章的相关文章索引
+///
+/// 在加载文章时按共享标签、共享分类与分词后的 TF-IDF 文本相似度计算，
+/// 每篇已发布文章保留得分最高的 `count` 篇。
+#[derive(Debug, Default)]
+pub struct RelatedIndex {
+    related: HashMap<String, Vec<String>>,
+}
+
+impl RelatedIndex {
+    /// 构建相关文章索引
+    ///
+    /// # 参数
+    /// * `posts` - 所有文章，草稿不参与推荐
+    /// * `markdown` - Markdown 渲染配置，用于提取正文纯文本
+    /// * `config` - 相关文章配置
+    pub fn build(posts: &[Post], markdown: &MarkdownConfig, config: &RelatedConfig) -> Self {
+        let published: Vec<&Post> = posts.iter().filter(|p| !p.front_matter.draft).collect();
+
+        // 词频统计，单字词（多为虚词）不参与计算
+        let counts: Vec<HashMap<String, f32>> = published
+            .iter()
+            .map(|post| {
+                let text = format!("{} {}", post.front_matter.title, plain_text(post, markdown));
+                let mut counts = HashMap::new();
+                for term in tokenize(&text).into_iter().filter(|t| t.chars().count() > 1) {
+                    *counts.entry(term).or_insert(0.0) += 1.0;
+                }
+                counts
+            })
+            .collect();
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for term in counts.iter().flat_map(HashMap::keys) {
+            *document_frequency.entry(term.as_str()).or_default() += 1;
+        }
+
+        let total = published.len() as f32;
+        let features: Vec<Features> = published
+            .iter()
+            .zip(&counts)
+            .map(|(post, counts)| {
+                let mut vector: HashMap<String, f32> = counts
+                    .iter()
+                    .map(|(term, count)| {
+                        let idf = (total / document_frequency[term.as_str()] as f32).ln();
+                        (term.clone(), count * idf)
+                    })
+                    .filter(|(_, weight)| *weight > 0.0)
+                    .collect();
+                let norm = vector.values().map(|w| w * w).sum::<f32>().sqrt();
+                if norm > 0.0 {
+                    vector.values_mut().for_each(|w| *w /= norm);
+                }
+                Features {
+                    tags: to_set(&post.front_matter.tags),
+                    categories: to_set(&post.front_matter.categories),
+                    vector,
+                }
+            })
+            .collect();
+
+        let mut related = HashMap::new();
+        for (i, post) in published.iter().enumerate() {
+            let mut scored: Vec<(usize, f32)> = features
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(j, other)| {
+                    let current = &features[i];
+                    let score = config.tag_weight * jaccard(&current.tags, &other.tags)
+                        + config.category_weight
+                            * jaccard(&current.categories, &other.categories)
+                        + config.text_weight * cosine(&current.vector, &other.vector);
+                    (j, score)
+                })
+                .filter(|(_, score)| *score >= config.min_score && *score > 0.0)
+                .collect();
+            // 得分相同时较新的文章优先（文章已按日期降序排列）
+            scored.sort_by(|(a, score_a), (b, score_b)| score_b.total_cmp(score_a).then(a.cmp(b)));
+            related.insert(
+                post.url.clone(),
+                scored
+                    .into_iter()
+                    .take(config.count)
+                    .map(|(j, _)| published[j].url.clone())
+                    .collect(),
+            );
+        }
+
+        Self { related }
+    }
+
+    /// 获取文章的相关文章URL，按相似度降序排列
+    ///
+    /// # 参数
+    /// * `url` - 文章URL标识
+    pub fn related(&self, url: &str) -> &[String] {
+        self.related.get(url).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+
+    fn build(posts: &[Post], count: usize) -> RelatedIndex {
+        let config = RelatedConfig {
+            count,
+            ..RelatedConfig::default()
+        };
+        RelatedIndex::build(posts, &MarkdownConfig::default(), &config)
+    }
+
+    #[test]
+    fn test_tags_and_categories() {
+        let posts = vec![
+            post("rust-1", "tags: [Rust, 异步]\ncategories: [技术]", ""),
+            post("rust-2", "tags: [Rust]\ncategories: [技术]", ""),
+            post("cook", "categories: [生活]", ""),
+            post("tech", "categories: [技术]", ""),
+        ];
+        let index = build(&posts, 5);
+        assert_eq!(index.related("rust-1"), ["rust-2", "tech"]);
+        assert_eq!(index.related("tech"), ["rust-1", "rust-2"]);
+        assert!(index.related("cook").is_empty());
+        assert!(index.related("missing").is_empty());
+    }
+
+    #[test]
+    fn test_text_similarity() {
+        let posts = vec![
+            post("a", "", "蓝桉树在雨季开花，蓝桉树的叶子散发清香。"),
+            post("b", "", "今天的晚饭是红烧肉和米饭。"),
+            post("c", "", "院子里种了一棵蓝桉树，花开时满院清香。"),
+            post("d", "", "周末去爬山，山顶风很大。"),
+        ];
+        let index = build(&posts, 1);
+        assert_eq!(index.related("a"), ["c"]);
+        assert_eq!(index.related("c"), ["a"]);
+    }
+
+    #[test]
+    fn test_drafts_excluded() {
+        let mut draft = post("draft", "tags: [Rust]", "");
+        draft.front_matter.draft = true;
+        let posts = vec![post("a", "tags: [Rust]", ""), draft];
+        let index = build(&posts, 5);
+        assert!(index.related("a").is_empty());
+        assert!(index.related("draft").is_empty());
+    }
+}
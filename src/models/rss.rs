@@ -13,4 +13,5 @@ pub struct RssFeed {
     pub last_build_date: String,
     pub site_url: String,
     pub site_title: String,
+    pub description: String,
 }
@@ -0,0 +1,422 @@
+use super::search_export::ExportConfig;
+use super::{FrontMatter, MarkdownConfig, Post};
+use crate::utils::html::escape_html;
+use crate::utils::obsidian::{self, Piece};
+use crate::utils::shortcode;
+use jieba_rs::Jieba;
+use once_cell::sync::Lazy;
+use pulldown_cmark::{Event, Parser, TagEnd, TextMergeStream};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 中文分词器，首次使用时加载词典
+static JIEBA: Lazy<Jieba> = Lazy::new(Jieba::new);
+
+/// 摘要中命中词之前保留的字符数
+const SNIPPET_LEADING: usize = 20;
+
+/// 站内搜索配置，对应 config.toml 的 `[search]` 节
+///
+/// # 字段说明
+/// * `per_page` - 搜索结果每页条数
+/// * `title_boost` - 标题命中的权重倍数
+/// * `tag_boost` - 标签与分类命中的权重倍数
+/// * `snippet_length` - 结果摘要的字符数
+/// * `suggest_limit` - `/search/suggest` 最多返回的建议条数
+/// * `export` - `/search-index.json` 静态搜索索引配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchConfig {
+    #[serde(default = "default_per_page")]
+    pub per_page: usize,
+    #[serde(default = "default_title_boost")]
+    pub title_boost: f32,
+    #[serde(default = "default_tag_boost")]
+    pub tag_boost: f32,
+    #[serde(default = "default_snippet_length")]
+    pub snippet_length: usize,
+    #[serde(default = "default_suggest_limit")]
+    pub suggest_limit: usize,
+    #[serde(default)]
+    pub export: ExportConfig,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            per_page: default_per_page(),
+            title_boost: default_title_boost(),
+            tag_boost: default_tag_boost(),
+            snippet_length: default_snippet_length(),
+            suggest_limit: default_suggest_limit(),
+            export: ExportConfig::default(),
+        }
+    }
+}
+
+fn default_per_page() -> usize {
+    10
+}
+
+fn default_title_boost() -> f32 {
+    5.0
+}
+
+fn default_tag_boost() -> f32 {
+    3.0
+}
+
+fn default_snippet_length() -> usize {
+    120
+}
+
+fn default_suggest_limit() -> usize {
+    8
+}
+
+/// 一条搜索结果
+///
+/// # 字段说明
+/// * `url` - 文章URL标识
+/// * `front_matter` - 文章头部信息
+/// * `title` - 命中词以 `<mark>` 标记的标题HTML
+/// * `snippet` - 命中词以 `<mark>` 标记的正文摘要HTML
+/// * `score` - 相关度得分
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchHit {
+    pub url: String,
+    pub front_matter: FrontMatter,
+    pub title: String,
+    pub snippet: String,
+    pub score: f32,
+}
+
+/// 累加词项在某个字段中的出现次数
+type FieldCounter = fn(&mut Posting);
+
+/// 已索引的文章
+#[derive(Debug)]
+struct SearchDoc {
+    url: String,
+    front_matter: FrontMatter,
+    /// 正文纯文本，用于生成摘要
+    text: String,
+}
+
+/// 词项在一篇文章各字段中的出现次数
+#[derive(Debug, Default, Clone, Copy)]
+struct Posting {
+    doc: usize,
+    title: u32,
+    tags: u32,
+    body: u32,
+}
+
+/// 站内搜索倒排索引
+///
+/// 在加载文章时由所有已发布文章的标题、标签、分类与正文构建，
+/// 中文使用 jieba 搜索引擎模式分词，英文按词并转为小写。
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    terms: HashMap<String, Vec<Posting>>,
+    config: SearchConfig,
+}
+
+impl SearchIndex {
+    /// 构建搜索索引
+    ///
+    /// # 参数
+    /// * `posts` - 所有文章，草稿不会被索引
+    /// * `markdown` - Markdown 渲染配置，用于确定文章启用的扩展语法
+    /// * `config` - 搜索配置
+    pub fn build(posts: &[Post], markdown: &MarkdownConfig, config: &SearchConfig) -> Self {
+        let mut docs = Vec::new();
+        let mut terms: HashMap<String, Vec<Posting>> = HashMap::new();
+
+        for post in posts.iter().filter(|p| !p.front_matter.draft) {
+            let doc = docs.len();
+            let text = plain_text(post, markdown);
+            let labels = post
+                .front_matter
+                .tags
+                .iter()
+                .chain(post.front_matter.categories.iter())
+                .flatten()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let mut postings: HashMap<String, Posting> = HashMap::new();
+            let fields: [(&str, FieldCounter); 3] = [
+                (&post.front_matter.title, |p| p.title += 1),
+                (&labels, |p| p.tags += 1),
+                (&text, |p| p.body += 1),
+            ];
+            for (field, count) in fields {
+                for term in tokenize(field) {
+                    count(postings.entry(term).or_insert(Posting {
+                        doc,
+                        ..Posting::default()
+                    }));
+                }
+            }
+            for (term, posting) in postings {
+                terms.entry(term).or_default().push(posting);
+            }
+
+            docs.push(SearchDoc {
+                url: post.url.clone(),
+                front_matter: post.front_matter.clone(),
+                text,
+            });
+        }
+
+        Self {
+            docs,
+            terms,
+            config: config.clone(),
+        }
+    }
+
+    /// 搜索文章
+    ///
+    /// 排序规则：命中的查询词越多越靠前；命中数相同时按相关度得分降序，
+    /// 得分相同时按发布日期降序。标题中完整出现查询语句的文章得分加倍。
+    ///
+    /// # 参数
+    /// * `query` - 查询语句
+    ///
+    /// # 返回
+    /// * `Vec<SearchHit>` - 按相关度排序的全部结果
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let query = query.trim();
+        let mut query_terms = tokenize(query);
+        let mut seen = HashSet::new();
+        query_terms.retain(|term| seen.insert(term.clone()));
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        // 文章下标 -> (命中词数, 得分)
+        let mut scores: HashMap<usize, (usize, f32)> = HashMap::new();
+        let total = self.docs.len() as f32;
+        for term in &query_terms {
+            let Some(postings) = self.terms.get(term) else {
+                continue;
+            };
+            let idf = (1.0 + total / postings.len() as f32).ln();
+            for posting in postings {
+                let weight = self.config.title_boost * posting.title as f32
+                    + self.config.tag_boost * posting.tags as f32
+                    + (1.0 + posting.body as f32).ln();
+                let entry = scores.entry(posting.doc).or_default();
+                entry.0 += 1;
+                entry.1 += idf * weight;
+            }
+        }
+
+        let phrase = query.to_lowercase();
+        let mut ranked: Vec<(usize, usize, f32)> = scores
+            .into_iter()
+            .map(|(doc, (matched, score))| {
+                let title = self.docs[doc].front_matter.title.to_lowercase();
+                let score = if title.contains(&phrase) { score * 2.0 } else { score };
+                (doc, matched, score)
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then(b.2.total_cmp(&a.2))
+                .then_with(|| {
+                    let date = |doc: usize| self.docs[doc].front_matter.date;
+                    date(b.0).cmp(&date(a.0))
+                })
+        });
+
+        // 高亮时优先匹配完整查询语句，再匹配较长的词
+        let mut marks: Vec<String> = query_terms.clone();
+        if !marks.contains(&phrase) {
+            marks.push(phrase);
+        }
+        marks.sort_by_key(|term| std::cmp::Reverse(term.chars().count()));
+
+        ranked
+            .into_iter()
+            .map(|(doc, _, score)| {
+                let doc = &self.docs[doc];
+                SearchHit {
+                    url: doc.url.clone(),
+                    front_matter: doc.front_matter.clone(),
+                    title: highlight(&doc.front_matter.title, &marks),
+                    snippet: snippet(&doc.text, &marks, self.config.snippet_length),
+                    score,
+                }
+            })
+            .collect()
+    }
+}
+
+/// 将文本切分为索引词：中文按词切分，英文与数字转为小写，忽略空白与标点
+pub fn tokenize(text: &str) -> Vec<String> {
+    JIEBA
+        .cut_for_search(text, true)
+        .into_iter()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| word.trim().to_lowercase())
+        .collect()
+}
+
+/// 提取文章正文的纯文本，双链以显示文本代替，数学公式不计入
+pub(crate) fn plain_text(post: &Post, config: &MarkdownConfig) -> String {
+    let content = shortcode::strip(&post.content);
+    let parser = Parser::new_ext(&content, post.markdown_options(config));
+    let mut text = String::new();
+    for event in TextMergeStream::new(parser) {
+        match event {
+            Event::Text(content) => {
+                for piece in obsidian::split_wikilinks(&content) {
+                    match piece {
+                        Piece::Text(content) => text.push_str(content),
+                        Piece::Link(link) if !link.embed => {
+                            text.push_str(link.alias.unwrap_or(link.target))
+                        }
+                        Piece::Link(_) => {}
+                    }
+                }
+            }
+            Event::Code(code) => text.push_str(&code),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableCell,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 查找文本中第一个命中词的字符位置与长度，`marks` 中靠前的词优先
+fn find_mark(lower: &[char], from: usize, marks: &[Vec<char>]) -> Option<(usize, usize)> {
+    (from..lower.len()).find_map(|start| {
+        marks
+            .iter()
+            .find(|mark| !mark.is_empty() && lower[start..].starts_with(mark))
+            .map(|mark| (start, mark.len()))
+    })
+}
+
+/// 转为逐字符的小写形式，保持与原文字符一一对应
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// 转义文本并以 `<mark>` 标记所有命中词
+fn highlight(text: &str, marks: &[String]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let lower = lowercase_chars(text);
+    let marks: Vec<Vec<char>> = marks.iter().map(|m| lowercase_chars(m)).collect();
+    let mut html = String::new();
+    let mut pos = 0;
+    while let Some((start, len)) = find_mark(&lower, pos, &marks) {
+        html.push_str(&escape_html(&chars[pos..start].iter().collect::<String>()));
+        html.push_str("<mark>");
+        html.push_str(&escape_html(&chars[start..start + len].iter().collect::<String>()));
+        html.push_str("</mark>");
+        pos = start + len;
+    }
+    html.push_str(&escape_html(&chars[pos..].iter().collect::<String>()));
+    html
+}
+
+/// 截取第一个命中词附近的正文作为摘要，没有命中时取正文开头
+fn snippet(text: &str, marks: &[String], length: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let lower = lowercase_chars(text);
+    let mark_chars: Vec<Vec<char>> = marks.iter().map(|m| lowercase_chars(m)).collect();
+    let start = find_mark(&lower, 0, &mark_chars)
+        .map_or(0, |(start, _)| start.saturating_sub(SNIPPET_LEADING));
+    let end = (start + length).min(chars.len());
+    let excerpt: String = chars[start..end].iter().collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        highlight(&excerpt, marks),
+        if end < chars.len() { "…" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+
+    fn index() -> SearchIndex {
+        let mut draft = post("draft", "title: 蓝桉树草稿\ndate: 2025-03-01T00:00:00+08:00", "蓝桉树");
+        draft.front_matter.draft = true;
+        let posts = vec![
+            post(
+                "garden",
+                "title: 院子里的植物\ndate: 2025-01-01T00:00:00+08:00\ntags: [生活]",
+                "我在院子里种了一棵**蓝桉树**，长得很快。\n\n```\ncode\n```\n",
+            ),
+            post(
+                "eucalyptus",
+                "title: 蓝桉树\ndate: 2024-01-01T00:00:00+08:00\ntags: [植物]",
+                "关于桉树的笔记。",
+            ),
+            post(
+                "rust",
+                "title: Rust 异步编程\ndate: 2025-02-01T00:00:00+08:00\ntags: [Rust, 异步]",
+                "使用 Tokio 运行时，见 [[院子里的植物|花园日记]]。",
+            ),
+            draft,
+        ];
+        SearchIndex::build(&posts, &MarkdownConfig::default(), &SearchConfig::default())
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("Rust 异步编程！"), vec!["rust", "异步", "编程"]);
+        assert!(tokenize("，。 ").is_empty());
+    }
+
+    #[test]
+    fn test_search_chinese() {
+        let index = index();
+        assert_eq!(index.docs.len(), 3);
+        let hits = index.search("蓝桉树");
+        let urls: Vec<_> = hits.iter().map(|hit| hit.url.as_str()).collect();
+        // 标题命中的文章排在正文命中之前，草稿不出现
+        assert_eq!(urls, vec!["eucalyptus", "garden"]);
+        assert_eq!(hits[0].title, "<mark>蓝桉树</mark>");
+        assert!(hits[1].snippet.contains("一棵<mark>蓝桉树</mark>，长得很快。"));
+    }
+
+    #[test]
+    fn test_search_latin_and_tags() {
+        let index = index();
+        let hits = index.search("tokio");
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].snippet.contains("<mark>Tokio</mark>"));
+        // 标签命中
+        assert_eq!(index.search("异步")[0].url, "rust");
+        // 双链以显示文本参与索引
+        assert_eq!(index.search("花园日记")[0].url, "rust");
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn test_snippet_is_truncated_and_escaped() {
+        let text = format!("{}<b>目标</b>{}", "前".repeat(50), "后".repeat(200));
+        let snippet = snippet(&text, &["目标".to_string()], 40);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("&lt;b&gt;<mark>目标</mark>&lt;&#x2F;b&gt;"));
+        assert_eq!(snippet.matches('前').count(), SNIPPET_LEADING - 3);
+    }
+}
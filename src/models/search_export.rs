@@ -0,0 +1,309 @@
+use super::search::{plain_text, tokenize};
+use super::{MarkdownConfig, Post};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// 静态搜索索引可输出的字段
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportField {
+    Title,
+    Url,
+    Date,
+    Tags,
+    Categories,
+    Summary,
+    Body,
+}
+
+/// 静态搜索索引配置，对应 config.toml 的 `[search.export]` 节
+///
+/// # 字段说明
+/// * `fields` - 输出的字段
+/// * `summary_length` - 摘要的字符数
+/// * `max_body_tokens` - 每篇文章正文最多输出的词数
+/// * `max_bytes` - 索引文件的大小上限，超出时从最早的文章开始舍弃，0 表示不限制
+/// * `output` - 非空时每次加载文章后将索引写入该文件，供静态镜像使用
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportConfig {
+    #[serde(default = "default_fields")]
+    pub fields: Vec<ExportField>,
+    #[serde(default = "default_summary_length")]
+    pub summary_length: usize,
+    #[serde(default = "default_max_body_tokens")]
+    pub max_body_tokens: usize,
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+    #[serde(default)]
+    pub output: String,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            fields: default_fields(),
+            summary_length: default_summary_length(),
+            max_body_tokens: default_max_body_tokens(),
+            max_bytes: default_max_bytes(),
+            output: String::new(),
+        }
+    }
+}
+
+fn default_fields() -> Vec<ExportField> {
+    vec![
+        ExportField::Title,
+        ExportField::Url,
+        ExportField::Date,
+        ExportField::Tags,
+        ExportField::Categories,
+        ExportField::Summary,
+        ExportField::Body,
+    ]
+}
+
+fn default_summary_length() -> usize {
+    120
+}
+
+fn default_max_body_tokens() -> usize {
+    400
+}
+
+fn default_max_bytes() -> usize {
+    1024 * 1024
+}
+
+/// 静态索引中的一篇文章，未启用的字段不输出
+#[derive(Debug, Serialize, Default)]
+struct ExportEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    categories: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    /// 去重后以空格分隔的正文分词结果，客户端直接按空格切分即可匹配
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+impl ExportEntry {
+    fn new(post: &Post, markdown: &MarkdownConfig, config: &ExportConfig) -> Self {
+        let has = |field| config.fields.contains(&field);
+        let front_matter = &post.front_matter;
+        let text = if has(ExportField::Summary) || has(ExportField::Body) {
+            plain_text(post, markdown)
+        } else {
+            String::new()
+        };
+        Self {
+            title: has(ExportField::Title).then(|| front_matter.title.clone()),
+            url: has(ExportField::Url).then(|| format!("/post/{}/", post.url)),
+            date: has(ExportField::Date).then(|| front_matter.date.to_rfc3339()),
+            tags: has(ExportField::Tags).then(|| front_matter.tags.clone().unwrap_or_default()),
+            categories: has(ExportField::Categories)
+                .then(|| front_matter.categories.clone().unwrap_or_default()),
+            summary: has(ExportField::Summary).then(|| summary(&text, config.summary_length)),
+            body: has(ExportField::Body).then(|| compact_tokens(&text, config.max_body_tokens)),
+        }
+    }
+}
+
+/// 截取纯文本开头作为摘要，被截断时追加省略号
+fn summary(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+    let mut summary: String = text.chars().take(length).collect();
+    summary.push('…');
+    summary
+}
+
+/// 分词并按首次出现的顺序去重，最多保留 `limit` 个词
+fn compact_tokens(text: &str, limit: usize) -> String {
+    let mut seen = HashSet::new();
+    tokenize(text)
+        .into_iter()
+        .filter(|token| seen.insert(token.clone()))
+        .take(limit)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 供静态镜像在浏览器端检索的搜索索引
+///
+/// 由 `/search-index.json` 输出，也可按配置写入文件。
+/// 内容在加载文章时一次性序列化，`etag` 由内容计算，内容不变则保持不变。
+#[derive(Debug, Clone)]
+pub struct SearchExport {
+    pub json: String,
+    pub etag: String,
+}
+
+impl Default for SearchExport {
+    fn default() -> Self {
+        Self::from_json("[]".to_string())
+    }
+}
+
+impl SearchExport {
+    /// 由已发布文章生成静态搜索索引
+    ///
+    /// # 参数
+    /// * `posts` - 按日期降序排列的文章，草稿不输出
+    /// * `markdown` - Markdown 渲染配置，用于提取正文纯文本
+    /// * `config` - 静态索引配置
+    pub fn build(posts: &[Post], markdown: &MarkdownConfig, config: &ExportConfig) -> Self {
+        let published: Vec<&Post> = posts.iter().filter(|p| !p.front_matter.draft).collect();
+        let mut json = String::from("[");
+        let mut exported = 0;
+        for post in &published {
+            let entry = ExportEntry::new(post, markdown, config);
+            let entry = match serde_json::to_string(&entry) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    tracing::warn!("Failed to serialize search entry {}: {}", post.url, e);
+                    continue;
+                }
+            };
+            // 预留分隔符与结尾的 `]`
+            if config.max_bytes > 0 && json.len() + entry.len() + 2 > config.max_bytes {
+                tracing::warn!(
+                    "Search index exceeds {} bytes, {} older posts omitted",
+                    config.max_bytes,
+                    published.len() - exported
+                );
+                break;
+            }
+            if exported > 0 {
+                json.push(',');
+            }
+            json.push_str(&entry);
+            exported += 1;
+        }
+        json.push(']');
+        Self::from_json(json)
+    }
+
+    fn from_json(json: String) -> Self {
+        let mut hasher = DefaultHasher::new();
+        json.hash(&mut hasher);
+        Self {
+            etag: format!("\"{:016x}\"", hasher.finish()),
+            json,
+        }
+    }
+
+    /// 判断 `If-None-Match` 请求头是否与当前内容一致
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == "*" || tag == self.etag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+    use serde_json::Value;
+
+    fn posts() -> Vec<Post> {
+        vec![
+            post(
+                "b",
+                "title: 蓝桉树下\ndate: 2025-02-01T00:00:00+08:00\ncategories: [随笔]\ntags: [植物]",
+                "# 标题\n\n蓝桉树下的**故事**，故事很长。",
+            ),
+            post(
+                "a",
+                "title: 旧文\ndate: 2025-01-01T00:00:00+08:00\ncategories: [随笔]\ntags: [植物]",
+                "Hello [[蓝桉树下|world]] hello.",
+            ),
+            post(
+                "c",
+                "title: 草稿\ndate: 2025-03-01T00:00:00+08:00\ndraft: true\ncategories: [随笔]\ntags: [植物]",
+                "未发布",
+            ),
+        ]
+    }
+
+    fn parse(export: &SearchExport) -> Vec<Value> {
+        serde_json::from_str(&export.json).unwrap()
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let export = SearchExport::build(&posts(), &MarkdownConfig::default(), &ExportConfig::default());
+        let entries = parse(&export);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["title"], "蓝桉树下");
+        assert_eq!(entries[0]["url"], "/post/b/");
+        assert_eq!(entries[0]["date"], "2025-02-01T00:00:00+08:00");
+        assert_eq!(entries[0]["tags"], serde_json::json!(["植物"]));
+        assert_eq!(entries[0]["categories"], serde_json::json!(["随笔"]));
+        assert_eq!(entries[0]["summary"], "标题 蓝桉树下的故事，故事很长。");
+        let body = entries[0]["body"].as_str().unwrap();
+        assert!(body.starts_with("标题 "));
+        assert_eq!(body.split(' ').filter(|t| *t == "故事").count(), 1);
+        assert_eq!(entries[1]["body"], "hello world");
+    }
+
+    #[test]
+    fn test_field_set_and_limits() {
+        let config = ExportConfig {
+            fields: vec![ExportField::Title, ExportField::Summary, ExportField::Body],
+            summary_length: 4,
+            max_body_tokens: 1,
+            ..ExportConfig::default()
+        };
+        let entries = parse(&SearchExport::build(&posts(), &MarkdownConfig::default(), &config));
+        let keys: Vec<&String> = entries[0].as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(), 3);
+        assert!(entries[0].get("url").is_none() && entries[0].get("tags").is_none());
+        assert_eq!(entries[0]["summary"], "标题 蓝…");
+        assert_eq!(entries[0]["body"], "标题");
+    }
+
+    #[test]
+    fn test_size_cap_drops_oldest() {
+        let full = SearchExport::build(&posts(), &MarkdownConfig::default(), &ExportConfig::default());
+        let config = ExportConfig {
+            max_bytes: full.json.len() - 1,
+            ..ExportConfig::default()
+        };
+        let capped = SearchExport::build(&posts(), &MarkdownConfig::default(), &config);
+        assert!(capped.json.len() <= config.max_bytes);
+        let entries = parse(&capped);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["url"], "/post/b/");
+        assert_ne!(capped.etag, full.etag);
+
+        let tiny = ExportConfig {
+            max_bytes: 1,
+            ..ExportConfig::default()
+        };
+        assert_eq!(SearchExport::build(&posts(), &MarkdownConfig::default(), &tiny).json, "[]");
+    }
+
+    #[test]
+    fn test_etag() {
+        let a = SearchExport::build(&posts(), &MarkdownConfig::default(), &ExportConfig::default());
+        let b = SearchExport::build(&posts(), &MarkdownConfig::default(), &ExportConfig::default());
+        assert_eq!(a.etag, b.etag);
+        assert!(a.etag.starts_with('"') && a.etag.ends_with('"'));
+        assert!(a.matches(&a.etag));
+        assert!(a.matches(&format!("\"x\", W/{}", a.etag)));
+        assert!(a.matches("*"));
+        assert!(!a.matches("\"x\""));
+    }
+}
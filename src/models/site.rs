@@ -1,8 +1,13 @@
-use chrono::Datelike;
+use super::{MarkdownConfig, RelatedConfig, SearchConfig, TaxonomyConfig};
+use chrono::{Datelike, FixedOffset, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use toml;
 
+/// 未设置 `TIMEZONE` 时使用的时区，与文章日期常用的偏移一致
+const DEFAULT_TIMEZONE: &str = "+08:00";
+
 /// 站点配置结构体
 /// 用于存储网站的基本配置信息
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -29,6 +34,20 @@ pub struct Site {
     pub menus: Vec<Menu>,
     /// 当前年
     pub year: u32,
+    /// 汉字阅读速度（字/分钟）
+    pub read_speed_cjk: usize,
+    /// 英文阅读速度（词/分钟）
+    pub read_speed_latin: usize,
+    /// 站点时区相对UTC的偏移秒数，用于计算往年今日等按日期展示的内容
+    pub utc_offset: i32,
+    /// Markdown渲染配置
+    pub markdown: MarkdownConfig,
+    /// 站内搜索配置
+    pub search: SearchConfig,
+    /// 相关文章配置
+    pub related: RelatedConfig,
+    /// 分类法配置，包括标签、分类与自定义分类法
+    pub taxonomies: Vec<TaxonomyConfig>,
 }
 
 /// 站点导航
@@ -41,13 +60,15 @@ pub struct Menu {
 }
 
 impl Site {
-    /// 从config.toml中读取配置内容
-    fn load_menu_config() -> Vec<Menu> {
+    /// 读取并解析config.toml，文件不存在或格式错误时返回空表
+    fn load_config() -> toml::Value {
         let config_str = fs::read_to_string("config.toml").unwrap_or_else(|_| String::from(""));
 
-        let config: toml::Value =
-            toml::from_str(&config_str).unwrap_or_else(|_| toml::Value::Table(toml::Table::new()));
+        toml::from_str(&config_str).unwrap_or_else(|_| toml::Value::Table(toml::Table::new()))
+    }
 
+    /// 从config.toml中读取配置内容
+    fn load_menu_config(config: &toml::Value) -> Vec<Menu> {
         let binding = Vec::new();
         let menu = config
             .get("menu")
@@ -76,13 +97,63 @@ impl Site {
             .collect()
     }
 
+    /// 读取config.toml中的一节并反序列化
+    /// 未配置时使用默认值，配置无效时记录警告并使用默认值
+    fn load_section<T: DeserializeOwned + Default>(config: &toml::Value, key: &str) -> T {
+        match config.get(key) {
+            Some(value) => value.clone().try_into().unwrap_or_else(|e| {
+                tracing::warn!("Invalid [{}] config, using defaults: {}", key, e);
+                T::default()
+            }),
+            None => T::default(),
+        }
+    }
+
+    /// 从config.toml的`[[taxonomies]]`读取分类法配置，未配置时使用标签与分类
+    fn load_taxonomies_config(config: &toml::Value) -> Vec<TaxonomyConfig> {
+        let mut taxonomies = Self::load_section::<Option<Vec<TaxonomyConfig>>>(config, "taxonomies")
+            .unwrap_or_else(TaxonomyConfig::defaults);
+        for taxonomy in &mut taxonomies {
+            if taxonomy.title.is_empty() {
+                taxonomy.title = taxonomy.name.clone();
+            }
+        }
+        taxonomies
+    }
+
+    /// 解析 `+08:00` 形式的时区偏移，无效时记录警告并使用默认时区
+    fn parse_timezone(timezone: &str) -> i32 {
+        timezone
+            .trim()
+            .parse::<FixedOffset>()
+            .or_else(|e| {
+                tracing::warn!("Invalid TIMEZONE `{}`, using {}: {}", timezone, DEFAULT_TIMEZONE, e);
+                DEFAULT_TIMEZONE.parse()
+            })
+            .map(|offset| offset.local_minus_utc())
+            .unwrap_or(0)
+    }
+
+    /// 站点时区的今天
+    pub fn today(&self) -> NaiveDate {
+        let now = Utc::now();
+        match FixedOffset::east_opt(self.utc_offset) {
+            Some(offset) => now.with_timezone(&offset).date_naive(),
+            None => now.date_naive(),
+        }
+    }
+
     /// 从环境变量中读取站点配置
     /// 如果环境变量不存在，则使用默认值
     ///
     /// 环境变量：
     /// - SITE_TITLE: 站点标题
     /// - SITE_URL: 站点URL
+    /// - READ_SPEED_CJK: 汉字阅读速度（字/分钟）
+    /// - READ_SPEED_LATIN: 英文阅读速度（词/分钟）
+    /// - TIMEZONE: 站点时区，如 `+08:00`，默认为东八区
     pub fn from_env() -> Self {
+        let config = Self::load_config();
         Self {
             title: std::env::var("SITE_TITLE").unwrap_or_else(|_| "Default Title".to_string()),
             url: std::env::var("SITE_URL").unwrap_or_else(|_| "http://localhost:3000".to_string()),
@@ -98,8 +169,35 @@ impl Site {
             keywords: std::env::var("KEYWORDS").unwrap_or_else(|_| "".to_string()),
             description: std::env::var("DESCRIPTION").unwrap_or_else(|_| "".to_string()),
             author: std::env::var("AUTHOR").unwrap_or_else(|_| "".to_string()),
-            menus: Self::load_menu_config(),
+            menus: Self::load_menu_config(&config),
             year: chrono::Local::now().year_ce().1,
+            read_speed_cjk: std::env::var("READ_SPEED_CJK")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300),
+            read_speed_latin: std::env::var("READ_SPEED_LATIN")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .unwrap_or(200),
+            utc_offset: Self::parse_timezone(
+                &std::env::var("TIMEZONE").unwrap_or_else(|_| DEFAULT_TIMEZONE.to_string()),
+            ),
+            markdown: Self::load_section(&config, "markdown"),
+            search: Self::load_section(&config, "search"),
+            related: Self::load_section(&config, "related"),
+            taxonomies: Self::load_taxonomies_config(&config),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(Site::parse_timezone("+08:00"), 8 * 3600);
+        assert_eq!(Site::parse_timezone(" -05:30 "), -(5 * 3600 + 30 * 60));
+        assert_eq!(Site::parse_timezone("Asia/Shanghai"), 8 * 3600);
+    }
+}
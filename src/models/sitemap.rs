@@ -1,3 +1,18 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+/// `<loc>` 中需要百分号编码的字符，非ASCII字符总是编码
+const LOC: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'<')
+    .add(b'>')
+    .add(b'\\')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
 /// 站点地图URL结构体
 /// 表示站点地图中的单个URL条目
 #[derive(Debug)]
@@ -10,6 +25,16 @@ pub struct SitemapUrl {
     pub priority: String,
 }
 
+impl SitemapUrl {
+    /// 写入 `<loc>` 的地址：非ASCII与不安全字符百分号编码，`&` 与 `'` 转义为XML实体
+    pub fn escaped_loc(&self) -> String {
+        utf8_percent_encode(&self.loc, LOC)
+            .to_string()
+            .replace('&', "&amp;")
+            .replace('\'', "&apos;")
+    }
+}
+
 /// 站点地图结构体
 /// 包含整个站点的URL列表
 #[derive(Debug)]
@@ -17,3 +42,29 @@ pub struct Sitemap {
     /// 所有URL条目的集合
     pub urls: Vec<SitemapUrl>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaped_loc() {
+        let url = |loc: &str| SitemapUrl {
+            loc: loc.to_string(),
+            lastmod: String::new(),
+            priority: String::new(),
+        };
+        assert_eq!(
+            url("https://example.com/post/蓝桉/").escaped_loc(),
+            "https://example.com/post/%E8%93%9D%E6%A1%89/"
+        );
+        assert_eq!(
+            url("https://example.com/tags/a<b>&c'd/").escaped_loc(),
+            "https://example.com/tags/a%3Cb%3E&amp;c&apos;d/"
+        );
+        assert_eq!(
+            url("https://example.com/tags/%E6%9C%AA/").escaped_loc(),
+            "https://example.com/tags/%E6%9C%AA/"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use super::taxonomy::{count_terms, TermCount};
+use super::{MarkdownConfig, Post, TaxonomyConfig, TermSlugs};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// 各分类法列出的最常用词项数
+const TOP_TERMS: usize = 10;
+
+/// 列出的最长文章数
+const LONGEST_POSTS: usize = 10;
+
+/// 某一年或某一月的写作量
+///
+/// # 字段说明
+/// * `period` - 年份（`2025`）或年月（`2025-01`）
+/// * `posts` - 文章数
+/// * `words` - 字数（汉字数 + 单词数）
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct PeriodStats {
+    pub period: String,
+    pub posts: usize,
+    pub words: usize,
+}
+
+/// 文章及其字数
+#[derive(Debug, Serialize, Clone)]
+pub struct PostLength {
+    pub title: String,
+    pub url: String,
+    pub date: String,
+    pub words: usize,
+}
+
+/// 热力图中的一天
+///
+/// # 字段说明
+/// * `date` - 日期，`YYYY-MM-DD`
+/// * `posts` - 当天发布的文章数
+/// * `words` - 当天发布的字数
+/// * `level` - 颜色深浅，0 表示没有文章，1–4 按字数相对最多的一天划分
+#[derive(Debug, Serialize, Clone)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub posts: usize,
+    pub words: usize,
+    pub level: u8,
+}
+
+/// 类似 GitHub 贡献图的写作热力图
+///
+/// `weeks` 的每个元素为一周（周一到周日），区间以外的格子为空。
+#[derive(Debug, Serialize, Clone)]
+pub struct Heatmap {
+    pub weeks: Vec<Vec<Option<HeatmapDay>>>,
+}
+
+/// 参与统计的已发布文章
+struct Entry<'a> {
+    post: &'a Post,
+    date: NaiveDate,
+    words: usize,
+}
+
+impl Entry<'_> {
+    fn length(&self) -> PostLength {
+        PostLength {
+            title: self.post.front_matter.title.clone(),
+            url: self.post.url.clone(),
+            date: self.date.format("%Y-%m-%d").to_string(),
+            words: self.words,
+        }
+    }
+}
+
+/// 计算已发布文章的日期与字数
+fn entries<'a>(posts: &'a [Post], markdown: &MarkdownConfig) -> Vec<Entry<'a>> {
+    posts
+        .iter()
+        .filter(|p| !p.front_matter.draft)
+        .map(|post| Entry {
+            post,
+            date: post.front_matter.date.date_naive(),
+            words: post.word_count(markdown).total(),
+        })
+        .collect()
+}
+
+/// 按 `key` 汇总文章数与字数，结果按周期降序排列
+fn group_by(entries: &[Entry], key: impl Fn(&Entry) -> String) -> Vec<PeriodStats> {
+    let mut periods: BTreeMap<String, PeriodStats> = BTreeMap::new();
+    for entry in entries {
+        let period = key(entry);
+        let stats = periods.entry(period.clone()).or_insert(PeriodStats {
+            period,
+            posts: 0,
+            words: 0,
+        });
+        stats.posts += 1;
+        stats.words += entry.words;
+    }
+    periods.into_values().rev().collect()
+}
+
+/// 各分类法最常用的词项
+fn top_terms(
+    entries: &[Entry],
+    taxonomies: &[TaxonomyConfig],
+    slugs: &TermSlugs,
+) -> BTreeMap<String, Vec<TermCount>> {
+    let posts: Vec<Post> = entries.iter().map(|e| e.post.clone()).collect();
+    taxonomies
+        .iter()
+        .map(|taxonomy| {
+            let mut terms = count_terms(&posts, taxonomy, slugs);
+            terms.truncate(TOP_TERMS);
+            (taxonomy.name.clone(), terms)
+        })
+        .collect()
+}
+
+/// 字数最多的文章
+fn longest(entries: &[Entry]) -> Vec<PostLength> {
+    let mut sorted: Vec<&Entry> = entries.iter().collect();
+    sorted.sort_by_key(|e| std::cmp::Reverse(e.words));
+    sorted.into_iter().take(LONGEST_POSTS).map(Entry::length).collect()
+}
+
+impl Heatmap {
+    /// 生成区间内（两端均包含）的热力图
+    fn new(from: NaiveDate, to: NaiveDate, entries: &[Entry]) -> Self {
+        let mut days: HashMap<NaiveDate, (usize, usize)> = HashMap::new();
+        for entry in entries.iter().filter(|e| e.date >= from && e.date <= to) {
+            let day = days.entry(entry.date).or_default();
+            day.0 += 1;
+            day.1 += entry.words;
+        }
+        let max = days.values().map(|(_, words)| *words).max().unwrap_or(0).max(1);
+
+        let start = from - Duration::days(from.weekday().num_days_from_monday() as i64);
+        let mut weeks = Vec::new();
+        let mut date = start;
+        while date <= to {
+            let week = (0..7)
+                .map(|offset| {
+                    let day = date + Duration::days(offset);
+                    if day < from || day > to {
+                        return None;
+                    }
+                    let (posts, words) = days.get(&day).copied().unwrap_or_default();
+                    let level = if posts == 0 { 0 } else { (1 + words * 3 / max).min(4) as u8 };
+                    Some(HeatmapDay {
+                        date: day.format("%Y-%m-%d").to_string(),
+                        posts,
+                        words,
+                        level,
+                    })
+                })
+                .collect();
+            weeks.push(week);
+            date += Duration::days(7);
+        }
+        Self { weeks }
+    }
+}
+
+/// 全站写作统计，由 `/stats/` 与 `/stats.json` 输出
+///
+/// # 字段说明
+/// * `posts` - 已发布文章数
+/// * `words` - 总字数
+/// * `years` - 每年的写作量，按年份降序
+/// * `months` - 每月的写作量，按月份降序，只含有文章的月份
+/// * `terms` - 分类法名称 → 最常用的词项
+/// * `longest` - 字数最多的文章
+/// * `heatmap` - 截至今天最近一年的写作热力图
+#[derive(Debug, Serialize, Clone)]
+pub struct SiteStats {
+    pub posts: usize,
+    pub words: usize,
+    pub years: Vec<PeriodStats>,
+    pub months: Vec<PeriodStats>,
+    pub terms: BTreeMap<String, Vec<TermCount>>,
+    pub longest: Vec<PostLength>,
+    pub heatmap: Heatmap,
+}
+
+impl SiteStats {
+    /// 统计全站文章
+    ///
+    /// # 参数
+    /// * `posts` - 所有文章，草稿不参与统计
+    /// * `markdown` - Markdown 渲染配置，用于统计字数
+    /// * `taxonomies` - 分类法配置
+    /// * `slugs` - 词项的URL别名
+    /// * `today` - 热力图的结束日期
+    pub fn build(
+        posts: &[Post],
+        markdown: &MarkdownConfig,
+        taxonomies: &[TaxonomyConfig],
+        slugs: &TermSlugs,
+        today: NaiveDate,
+    ) -> Self {
+        let entries = entries(posts, markdown);
+        Self {
+            posts: entries.len(),
+            words: entries.iter().map(|e| e.words).sum(),
+            years: group_by(&entries, |e| e.date.format("%Y").to_string()),
+            months: group_by(&entries, |e| e.date.format("%Y-%m").to_string()),
+            terms: top_terms(&entries, taxonomies, slugs),
+            longest: longest(&entries),
+            heatmap: Heatmap::new(today - Duration::days(364), today, &entries),
+        }
+    }
+}
+
+/// 年度回顾，由 `/stats/{year}/` 输出
+///
+/// # 字段说明
+/// * `year` - 年份
+/// * `posts` - 当年发布的文章数
+/// * `words` - 当年的总字数
+/// * `days` - 有文章发布的天数
+/// * `longest_streak` - 最长的连续发布天数
+/// * `months` - 1 到 12 月的写作量，没有文章的月份为0
+/// * `terms` - 分类法名称 → 当年最常用的词项
+/// * `longest` - 当年字数最多的文章
+/// * `list` - 当年的所有文章，按发布日期降序
+/// * `heatmap` - 全年的写作热力图
+#[derive(Debug, Serialize, Clone)]
+pub struct YearReview {
+    pub year: u32,
+    pub posts: usize,
+    pub words: usize,
+    pub days: usize,
+    pub longest_streak: usize,
+    pub months: Vec<PeriodStats>,
+    pub terms: BTreeMap<String, Vec<TermCount>>,
+    pub longest: Vec<PostLength>,
+    pub list: Vec<PostLength>,
+    pub heatmap: Heatmap,
+}
+
+impl YearReview {
+    /// 生成年度回顾
+    ///
+    /// # 参数
+    /// * `year` - 年份
+    /// * `posts` - 按发布日期降序排列的所有文章，草稿不参与统计
+    /// * `markdown` - Markdown 渲染配置，用于统计字数
+    /// * `taxonomies` - 分类法配置
+    /// * `slugs` - 词项的URL别名
+    ///
+    /// # 返回
+    /// * `Option<YearReview>` - 当年没有文章时返回 `None`
+    pub fn build(
+        year: u32,
+        posts: &[Post],
+        markdown: &MarkdownConfig,
+        taxonomies: &[TaxonomyConfig],
+        slugs: &TermSlugs,
+    ) -> Option<Self> {
+        let in_year: Vec<Post> = posts
+            .iter()
+            .filter(|p| p.front_matter.date.year() as u32 == year)
+            .cloned()
+            .collect();
+        let entries = entries(&in_year, markdown);
+        if entries.is_empty() {
+            return None;
+        }
+        let from = NaiveDate::from_ymd_opt(year as i32, 1, 1)?;
+        let to = NaiveDate::from_ymd_opt(year as i32, 12, 31)?;
+
+        let mut dates: Vec<NaiveDate> = entries.iter().map(|e| e.date).collect();
+        dates.sort();
+        dates.dedup();
+        let mut longest_streak = 0;
+        let mut streak = 0;
+        for (i, date) in dates.iter().enumerate() {
+            streak = if i > 0 && *date - dates[i - 1] == Duration::days(1) { streak + 1 } else { 1 };
+            longest_streak = longest_streak.max(streak);
+        }
+
+        let by_month = group_by(&entries, |e| e.date.format("%Y-%m").to_string());
+        let months = (1..=12)
+            .map(|month| {
+                let period = format!("{}-{:02}", year, month);
+                by_month
+                    .iter()
+                    .find(|m| m.period == period)
+                    .cloned()
+                    .unwrap_or(PeriodStats {
+                        period,
+                        posts: 0,
+                        words: 0,
+                    })
+            })
+            .collect();
+
+        Some(Self {
+            year,
+            posts: entries.len(),
+            words: entries.iter().map(|e| e.words).sum(),
+            days: dates.len(),
+            longest_streak,
+            months,
+            terms: top_terms(&entries, taxonomies, slugs),
+            longest: longest(&entries),
+            list: entries.iter().map(Entry::length).collect(),
+            heatmap: Heatmap::new(from, to, &entries),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+
+    fn posts() -> Vec<Post> {
+        let mut draft = post("draft", "date: 2025-01-05T08:00:00+08:00\ntags: [Rust]", "草稿草稿草稿");
+        draft.front_matter.draft = true;
+        vec![
+            draft,
+            post("d", "date: 2025-01-03T08:00:00+08:00\ntags: [Rust]", "one two three four"),
+            post("c", "date: 2025-01-02T08:00:00+08:00\ntags: [Rust, Go]", "蓝桉树"),
+            post("b", "date: 2025-01-01T08:00:00+08:00\ntags: [Go]", "一二"),
+            post("a", "date: 2024-12-31T08:00:00+08:00\ntags: [Rust]", "一"),
+        ]
+    }
+
+    fn build(today: NaiveDate) -> SiteStats {
+        let posts = posts();
+        let taxonomies = TaxonomyConfig::defaults();
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        SiteStats::build(&posts, &MarkdownConfig::default(), &taxonomies, &slugs, today)
+    }
+
+    #[test]
+    fn test_site_stats() {
+        let stats = build(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap());
+        assert_eq!((stats.posts, stats.words), (4, 10));
+        let years: Vec<(&str, usize, usize)> = stats
+            .years
+            .iter()
+            .map(|y| (y.period.as_str(), y.posts, y.words))
+            .collect();
+        assert_eq!(years, [("2025", 3, 9), ("2024", 1, 1)]);
+        assert_eq!(stats.months[0].period, "2025-01");
+        let tags: Vec<(&str, usize)> =
+            stats.terms["tags"].iter().map(|t| (t.name.as_str(), t.count)).collect();
+        assert_eq!(tags, [("Rust", 3), ("Go", 2)]);
+        assert!(stats.terms["categories"].is_empty());
+        assert_eq!(stats.longest[0].url, "d");
+        assert_eq!(stats.longest[0].words, 4);
+
+        // 最近一年共365天，最后一天是周五
+        let days: Vec<&HeatmapDay> = stats.heatmap.weeks.iter().flatten().flatten().collect();
+        assert_eq!(days.len(), 365);
+        assert!(stats.heatmap.weeks.iter().all(|week| week.len() == 7));
+        let last_week = stats.heatmap.weeks.last().unwrap();
+        assert_eq!(last_week[4].as_ref().unwrap().date, "2025-01-03");
+        assert!(last_week[5].is_none());
+        let levels: Vec<u8> = days[days.len() - 4..].iter().map(|d| d.level).collect();
+        assert_eq!(levels, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_year_review() {
+        let posts = posts();
+        let taxonomies = TaxonomyConfig::defaults();
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        let markdown = MarkdownConfig::default();
+        let review = YearReview::build(2025, &posts, &markdown, &taxonomies, &slugs).unwrap();
+        assert_eq!((review.posts, review.words, review.days), (3, 9, 3));
+        assert_eq!(review.longest_streak, 3);
+        assert_eq!(review.months.len(), 12);
+        assert_eq!(review.months[0].posts, 3);
+        assert_eq!(review.months[11], PeriodStats { period: "2025-12".to_string(), posts: 0, words: 0 });
+        let urls: Vec<&str> = review.list.iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(urls, ["d", "c", "b"]);
+        assert_eq!(review.heatmap.weeks.iter().flatten().flatten().count(), 365);
+
+        let review = YearReview::build(2024, &posts, &markdown, &taxonomies, &slugs).unwrap();
+        assert_eq!(review.longest_streak, 1);
+        assert!(YearReview::build(2023, &posts, &markdown, &taxonomies, &slugs).is_none());
+    }
+}
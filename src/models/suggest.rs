@@ -0,0 +1,220 @@
+use super::{LinkGraph, Post, TermSlugs};
+use crate::utils::pinyin;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// 搜索建议的类型
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    Post,
+    Tag,
+}
+
+/// 一条搜索建议，供 `/search/suggest` 输出
+///
+/// # 字段说明
+/// * `kind` - 建议类型：文章标题或标签
+/// * `name` - 文章标题或标签名
+/// * `url` - 访问地址
+/// * `count` - 热度：标签为文章数，文章为被其他文章引用的次数
+#[derive(Debug, Serialize, Clone)]
+pub struct Suggestion {
+    #[serde(rename = "type")]
+    pub kind: SuggestionKind,
+    pub name: String,
+    pub url: String,
+    pub count: usize,
+}
+
+/// 可被匹配的候选项，预先计算小写名称、全拼与首字母
+#[derive(Debug)]
+struct Candidate {
+    suggestion: Suggestion,
+    name: String,
+    full: String,
+    initials: String,
+}
+
+impl Candidate {
+    fn new(suggestion: Suggestion) -> Self {
+        let syllables: Vec<String> = pinyin::to_pinyin(&suggestion.name)
+            .into_iter()
+            .map(|syllable| {
+                syllable
+                    .chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            })
+            .filter(|syllable| !syllable.is_empty())
+            .collect();
+        Self {
+            name: normalize(&suggestion.name),
+            full: syllables.concat(),
+            initials: syllables
+                .iter()
+                .filter_map(|syllable| syllable.chars().next())
+                .collect(),
+            suggestion,
+        }
+    }
+
+    /// 匹配查询，返回匹配方式的优先级：0 汉字前缀，1 全拼前缀，2 首字母前缀
+    fn matches(&self, query: &str) -> Option<u8> {
+        if self.name.starts_with(query) {
+            Some(0)
+        } else if self.full.starts_with(query) {
+            Some(1)
+        } else if self.initials.starts_with(query) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// 去除空白并转为小写，用于不区分大小写的前缀匹配
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 文章标题与标签的输入建议索引
+///
+/// 支持汉字前缀（`蓝桉`）、全拼前缀（`lananshu`）与拼音首字母（`las`）匹配。
+#[derive(Debug, Default)]
+pub struct SuggestIndex {
+    candidates: Vec<Candidate>,
+}
+
+impl SuggestIndex {
+    /// 由已发布文章构建建议索引
+    ///
+    /// # 参数
+    /// * `posts` - 所有文章，草稿不参与建议
+    /// * `graph` - 文章链接关系，用于统计文章被引用的次数
+    /// * `slugs` - 词项URL别名，用于生成标签地址
+    pub fn build(posts: &[Post], graph: &LinkGraph, slugs: &TermSlugs) -> Self {
+        let backlinks: HashMap<&str, usize> = graph
+            .data()
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node.backlinks))
+            .collect();
+        let published = posts.iter().filter(|p| !p.front_matter.draft);
+
+        let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+        let mut candidates = Vec::new();
+        for post in published {
+            for tag in post.front_matter.tags.iter().flatten() {
+                *tag_counts.entry(tag.as_str()).or_default() += 1;
+            }
+            candidates.push(Candidate::new(Suggestion {
+                kind: SuggestionKind::Post,
+                name: post.front_matter.title.clone(),
+                url: format!("/post/{}/", post.url),
+                count: backlinks.get(post.url.as_str()).copied().unwrap_or_default(),
+            }));
+        }
+        candidates.extend(tag_counts.into_iter().map(|(tag, count)| {
+            Candidate::new(Suggestion {
+                kind: SuggestionKind::Tag,
+                name: tag.to_string(),
+                url: slugs.url("tags", tag),
+                count,
+            })
+        }));
+
+        Self { candidates }
+    }
+
+    /// 查找输入建议
+    ///
+    /// 排序规则：汉字前缀匹配优先于全拼，全拼优先于首字母；
+    /// 同一匹配方式下按热度降序，热度相同时名称较短的优先。
+    ///
+    /// # 参数
+    /// * `query` - 用户输入
+    /// * `limit` - 最多返回的条数
+    pub fn suggest(&self, query: &str, limit: usize) -> Vec<Suggestion> {
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matched: Vec<(u8, &Candidate)> = self
+            .candidates
+            .iter()
+            .filter_map(|candidate| candidate.matches(&query).map(|rank| (rank, candidate)))
+            .collect();
+        matched.sort_by(|(rank_a, a), (rank_b, b)| {
+            rank_a
+                .cmp(rank_b)
+                .then(b.suggestion.count.cmp(&a.suggestion.count))
+                .then(a.name.chars().count().cmp(&b.name.chars().count()))
+                .then(a.name.cmp(&b.name))
+        });
+        matched
+            .into_iter()
+            .take(limit)
+            .map(|(_, candidate)| candidate.suggestion.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+    use crate::models::{TaxonomyConfig, WikiIndex};
+    use std::path::Path;
+
+    fn index() -> SuggestIndex {
+        let posts = vec![
+            post("a", "title: 蓝桉树下\ntags: [蓝桉树, 故事]", ""),
+            post("b", "title: 蓝色的海\ntags: [故事]", "见 [[蓝桉树下]]"),
+            post("c", "title: Rust 异步编程\ntags: [Rust]", "见 [[蓝桉树下]]"),
+        ];
+        let wiki = WikiIndex::new(posts.clone(), Path::new("static"));
+        let slugs = TermSlugs::build(&posts, &TaxonomyConfig::defaults());
+        SuggestIndex::build(&posts, &LinkGraph::build(&wiki, ""), &slugs)
+    }
+
+    fn names(suggestions: Vec<Suggestion>) -> Vec<String> {
+        suggestions.into_iter().map(|s| s.name).collect()
+    }
+
+    #[test]
+    fn test_hanzi_prefix() {
+        let suggestions = index().suggest("蓝", 10);
+        // 标题「蓝桉树下」被引用2次，排在最前
+        assert_eq!(suggestions[0].name, "蓝桉树下");
+        assert_eq!(suggestions[0].count, 2);
+        assert_eq!(suggestions[0].url, "/post/a/");
+        assert_eq!(names(suggestions), vec!["蓝桉树下", "蓝桉树", "蓝色的海"]);
+    }
+
+    #[test]
+    fn test_full_pinyin_and_initials() {
+        let index = index();
+        assert_eq!(names(index.suggest("lananshu", 10)), vec!["蓝桉树下", "蓝桉树"]);
+        assert_eq!(names(index.suggest("Lan An", 10)), vec!["蓝桉树下", "蓝桉树"]);
+        assert_eq!(names(index.suggest("las", 10)), vec!["蓝桉树下", "蓝桉树"]);
+        let tags = index.suggest("gs", 10);
+        assert_eq!(tags[0].kind, SuggestionKind::Tag);
+        assert_eq!((tags[0].name.as_str(), tags[0].count), ("故事", 2));
+        assert_eq!(tags[0].url, "/tags/gu-shi/");
+    }
+
+    #[test]
+    fn test_latin_and_limit() {
+        let index = index();
+        // 名称前缀优先于首字母匹配
+        assert_eq!(names(index.suggest("rust", 10)), vec!["Rust", "Rust 异步编程"]);
+        assert_eq!(names(index.suggest("rustyb", 10)), vec!["Rust 异步编程"]);
+        assert_eq!(index.suggest("l", 2).len(), 2);
+        assert!(index.suggest(" ", 10).is_empty());
+    }
+}
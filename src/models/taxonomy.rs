@@ -0,0 +1,848 @@
+use super::Post;
+use crate::utils::pinyin;
+use chrono::{DateTime, FixedOffset};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// 分类法配置，对应 config.toml 中的 `[[taxonomies]]`
+///
+/// 每个分类法以 `name` 作为 Front Matter 字段名与URL前缀，
+/// 例如 `topics` 对应文章中的 `topics: [...]` 与 `/topics/{term}/`。
+/// 名称不能与 `post`、`page`、`search` 等已有路由的前缀相同。
+///
+/// # 字段说明
+/// * `name` - 分类法名称
+/// * `title` - 显示名称，如「标签」
+/// * `per_page` - 词项文章列表每页条数
+/// * `feed` - 是否为每个词项提供 `/{name}/{term}/index.xml` 订阅源
+/// * `sitemap` - 词项页面是否加入站点地图
+/// * `template` - 词项列表页模板，默认 `taxonomy.html.tera`
+/// * `term_template` - 词项文章列表页模板，默认 `taxonomy_term.html.tera`
+/// * `slugs` - 词项URL别名的手动指定，词项名 → slug，优先于自动生成的拼音
+/// * `hierarchical` - 是否为层级分类法，词项以 `/` 分隔层级，如 `技术/Rust/异步`
+/// * `normalize` - 是否合并仅大小写或全角半角不同的词项，如 `Rust`、`rust`、`ＲＵＳＴ`
+/// * `aliases` - 词项别名，规范词项名 → 别名列表，如 `Rust = ["锈"]`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaxonomyConfig {
+    pub name: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default = "default_per_page")]
+    pub per_page: usize,
+    #[serde(default = "default_true")]
+    pub feed: bool,
+    #[serde(default = "default_true")]
+    pub sitemap: bool,
+    #[serde(default = "default_template")]
+    pub template: String,
+    #[serde(default = "default_term_template")]
+    pub term_template: String,
+    #[serde(default)]
+    pub slugs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub hierarchical: bool,
+    #[serde(default)]
+    pub normalize: bool,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl TaxonomyConfig {
+    /// 使用默认设置创建分类法配置
+    pub fn new(name: &str, title: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            per_page: default_per_page(),
+            feed: true,
+            sitemap: true,
+            template: default_template(),
+            term_template: default_term_template(),
+            slugs: BTreeMap::new(),
+            hierarchical: false,
+            normalize: false,
+            aliases: BTreeMap::new(),
+        }
+    }
+
+    /// 获取文章在该分类法下的所有词项
+    ///
+    /// 层级分类法会展开所有上级词项：`技术/Rust/异步` 展开为
+    /// `技术`、`技术/Rust`、`技术/Rust/异步`，因此上级词项包含所有下级的文章。
+    pub fn post_terms(&self, post: &Post) -> Vec<String> {
+        let mut terms: Vec<String> = Vec::new();
+        for term in post.front_matter.terms(&self.name) {
+            if !self.hierarchical {
+                if !terms.iter().any(|t| t == term) {
+                    terms.push(term.to_string());
+                }
+                continue;
+            }
+            let mut path = String::new();
+            for segment in term.split(SEPARATOR).filter(|s| !s.is_empty()) {
+                if !path.is_empty() {
+                    path.push(SEPARATOR);
+                }
+                path.push_str(segment);
+                if !terms.contains(&path) {
+                    terms.push(path.clone());
+                }
+            }
+        }
+        terms
+    }
+
+    /// 未配置 `[[taxonomies]]` 时使用的分类法：标签与分类
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                template: "tags.html.tera".to_string(),
+                term_template: "tag_posts.html.tera".to_string(),
+                ..Self::new("tags", "标签")
+            },
+            Self {
+                template: "categories.html.tera".to_string(),
+                term_template: "category_posts.html.tera".to_string(),
+                hierarchical: true,
+                ..Self::new("categories", "分类")
+            },
+        ]
+    }
+}
+
+fn default_per_page() -> usize {
+    20
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_template() -> String {
+    "taxonomy.html.tera".to_string()
+}
+
+fn default_term_template() -> String {
+    "taxonomy_term.html.tera".to_string()
+}
+
+/// 层级分类法中词项各层级的分隔符
+pub const SEPARATOR: char = '/';
+
+/// 词项的显示名称：层级词项为最后一级，如 `技术/Rust` → `Rust`
+fn label(term: &str) -> &str {
+    term.rsplit(SEPARATOR).next().unwrap_or(term)
+}
+
+/// 层级词项的上级，如 `技术/Rust` → `技术`
+fn parent(term: &str) -> Option<&str> {
+    term.rsplit_once(SEPARATOR).map(|(parent, _)| parent)
+}
+
+/// 词项统计信息
+///
+/// # 字段说明
+/// * `name` - 词项名称，如标签名；层级词项为完整路径
+/// * `label` - 显示名称，层级词项为最后一级
+/// * `slug` - 词项在URL中使用的ASCII别名
+/// * `count` - 使用该词项的文章数量，层级词项包含所有下级的文章
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TermCount {
+    pub name: String,
+    pub label: String,
+    pub slug: String,
+    pub count: usize,
+}
+
+/// 词项列表的排序方式，由 `?sort=` 查询参数选择
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TermOrder {
+    /// 按文章数降序、名称升序
+    #[default]
+    Count,
+    /// 按拼音（非汉字按原字符）字母顺序，如通讯录
+    Pinyin,
+}
+
+/// 按拼音首字母分组的词项
+///
+/// # 字段说明
+/// * `letter` - 大写首字母，非字母开头的词项归入 `#`
+/// * `terms` - 组内按拼音排序的词项
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct LetterGroup {
+    pub letter: String,
+    pub terms: Vec<TermCount>,
+}
+
+/// 词项显示名称的拼音排序键，如 `蓝桉树` → `lan an shu`、`Go语言` → `go yu yan`
+///
+/// 拉丁字母按整词参与排序，不会被拆成单个字母
+fn pinyin_key(term: &TermCount) -> String {
+    pinyin::slugify(&term.label).replace('-', " ")
+}
+
+/// 排序键的首字母，非拉丁字母时为 `#`
+fn initial(key: &str) -> char {
+    match key.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => c.to_ascii_uppercase(),
+        _ => '#',
+    }
+}
+
+/// 列表与分组索引共用的排序键：首字母为 `#` 的词项在最后，其余按拼音、名称排序
+fn sort_key(term: &TermCount) -> (bool, String, String) {
+    let key = pinyin_key(term);
+    (initial(&key) == '#', key, term.name.clone())
+}
+
+/// 按拼音排序词项，拼音相同时按名称排序，非拉丁字母开头的词项排在最后，
+/// 与 [`group_by_letter`] 的顺序一致
+pub fn sort_by_pinyin(terms: &mut [TermCount]) {
+    terms.sort_by_cached_key(sort_key);
+}
+
+/// 按拼音首字母将词项分组，字母按 A–Z 排列，`#` 组在最后
+pub fn group_by_letter(terms: &[TermCount]) -> Vec<LetterGroup> {
+    let mut sorted = terms.to_vec();
+    sort_by_pinyin(&mut sorted);
+    let mut groups: Vec<LetterGroup> = Vec::new();
+    for term in sorted {
+        let letter = initial(&pinyin_key(&term)).to_string();
+        match groups.last_mut() {
+            Some(group) if group.letter == letter => group.terms.push(term),
+            _ => groups.push(LetterGroup {
+                letter,
+                terms: vec![term],
+            }),
+        }
+    }
+    groups
+}
+
+/// 层级分类法的词项树节点
+#[derive(Debug, Serialize, Clone)]
+pub struct TermNode {
+    #[serde(flatten)]
+    pub term: TermCount,
+    pub children: Vec<TermNode>,
+}
+
+/// 面包屑导航中的一级
+///
+/// # 字段说明
+/// * `name` - 词项完整路径
+/// * `label` - 显示名称
+/// * `url` - 词项页面地址
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    pub name: String,
+    pub label: String,
+    pub url: String,
+}
+
+/// 统计分类法下所有词项的文章数
+///
+/// # 参数
+/// * `posts` - 所有文章，草稿不计入
+/// * `taxonomy` - 分类法配置
+/// * `slugs` - 词项的URL别名
+///
+/// # 返回
+/// * `Vec<TermCount>` - 按文章数降序、名称升序排列的词项
+pub fn count_terms(posts: &[Post], taxonomy: &TaxonomyConfig, slugs: &TermSlugs) -> Vec<TermCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for post in posts.iter().filter(|p| !p.front_matter.draft) {
+        for term in taxonomy.post_terms(post) {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+    let mut terms: Vec<TermCount> = counts
+        .into_iter()
+        .map(|(name, count)| TermCount {
+            label: if taxonomy.hierarchical { label(&name) } else { &name }.to_string(),
+            slug: slugs.path(&taxonomy.name, &name),
+            name,
+            count,
+        })
+        .collect();
+    terms.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+    terms
+}
+
+/// 将层级分类法的词项组织为树，各层保持输入的顺序
+///
+/// # 参数
+/// * `terms` - [`count_terms`] 的结果，包含所有上级词项
+pub fn term_tree(terms: &[TermCount]) -> Vec<TermNode> {
+    fn children(terms: &[TermCount], of: Option<&str>) -> Vec<TermNode> {
+        terms
+            .iter()
+            .filter(|term| parent(&term.name) == of)
+            .map(|term| TermNode {
+                term: term.clone(),
+                children: children(terms, Some(&term.name)),
+            })
+            .collect()
+    }
+    children(terms, None)
+}
+
+/// 生成词项的面包屑导航，层级词项从最上级开始，非层级词项只有一级
+///
+/// # 参数
+/// * `taxonomy` - 分类法配置
+/// * `term` - 词项名称
+/// * `slugs` - 词项的URL别名
+pub fn breadcrumbs(taxonomy: &TaxonomyConfig, term: &str, slugs: &TermSlugs) -> Vec<Breadcrumb> {
+    let mut crumbs = Vec::new();
+    let mut path = String::new();
+    let segments: Vec<&str> = if taxonomy.hierarchical {
+        term.split(SEPARATOR).filter(|s| !s.is_empty()).collect()
+    } else {
+        vec![term]
+    };
+    for segment in segments {
+        if !path.is_empty() {
+            path.push(SEPARATOR);
+        }
+        path.push_str(segment);
+        crumbs.push(Breadcrumb {
+            name: path.clone(),
+            label: segment.to_string(),
+            url: slugs.url(&taxonomy.name, &path),
+        });
+    }
+    crumbs
+}
+
+/// 无法生成拼音或字母数字时使用的别名前缀
+const FALLBACK_SLUG: &str = "term";
+
+/// 词项名作为地址路径时需要转义的字符，保留字母数字与 `-_.~`
+const TERM_PATH: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+/// 词项名无法转写为ASCII时的别名，如 `term-1a2b3c4d`
+///
+/// 后缀为词项名的 FNV-1a 哈希，只取决于词项名本身，与文章的加载顺序无关
+fn fallback_slug(term: &str) -> String {
+    let hash = term
+        .bytes()
+        .fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+    format!("{}-{:08x}", FALLBACK_SLUG, hash)
+}
+
+/// 配置中手动指定的别名只能包含ASCII字母、数字、`-` 与 `_`
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty() && slug.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// 规范化词项的写法：全角字符转为半角，去除首尾空白并合并连续空白
+fn normalize(term: &str) -> String {
+    let half_width: String = term
+        .chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    half_width.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 词项的比较键，规范化后忽略大小写
+fn term_key(term: &str) -> String {
+    normalize(term).to_lowercase()
+}
+
+/// 所有分类法的词项别名与写法合并
+///
+/// 配置的 `aliases` 总是生效；开启 `normalize` 的分类法中，
+/// 仅大小写或全角半角不同的词项合并为同一词项，使用次数最多的写法（次数相同时取最早出现的）。
+/// 加载文章时用 [`Self::apply`] 将文章中的词项统一替换为规范词项，
+/// 因此词项统计、文章列表、订阅源与站点地图都只看到规范词项。
+#[derive(Debug, Default)]
+pub struct TermAliases {
+    /// 分类法名称 → 词项比较键 → 规范词项名
+    canonical: HashMap<String, HashMap<String, String>>,
+}
+
+impl TermAliases {
+    /// 由配置与文章中的词项生成别名表
+    ///
+    /// # 参数
+    /// * `posts` - 按日期降序排列的文章
+    /// * `taxonomies` - 分类法配置
+    pub fn build(posts: &[Post], taxonomies: &[TaxonomyConfig]) -> Self {
+        let mut result = Self::default();
+        for taxonomy in taxonomies {
+            let mut canonical: HashMap<String, String> = HashMap::new();
+            for (term, aliases) in &taxonomy.aliases {
+                let term = normalize(term);
+                for alias in aliases.iter().map(String::as_str).chain([term.as_str()]) {
+                    if let Some(existing) = canonical.insert(term_key(alias), term.clone()) {
+                        if existing != term {
+                            tracing::warn!(
+                                "Alias `{}` of {} is claimed by both `{}` and `{}`",
+                                alias,
+                                taxonomy.name,
+                                existing,
+                                term
+                            );
+                        }
+                    }
+                }
+            }
+            if taxonomy.normalize {
+                // 比较键 → 写法 → (使用次数, 首次出现的顺序)
+                let mut spellings: HashMap<String, HashMap<String, (usize, usize)>> =
+                    HashMap::new();
+                let terms = posts
+                    .iter()
+                    .rev()
+                    .flat_map(|p| p.front_matter.terms(&taxonomy.name))
+                    .map(normalize);
+                for (order, term) in terms.enumerate() {
+                    let key = term_key(&term);
+                    if canonical.contains_key(&key) {
+                        continue;
+                    }
+                    let entry = spellings.entry(key).or_default().entry(term).or_insert((0, order));
+                    entry.0 += 1;
+                }
+                for (key, spellings) in spellings {
+                    if let Some((term, _)) = spellings
+                        .into_iter()
+                        .max_by(|(_, (a, first_a)), (_, (b, first_b))| {
+                            a.cmp(b).then(first_b.cmp(first_a))
+                        })
+                    {
+                        canonical.insert(key, term);
+                    }
+                }
+            }
+            result.canonical.insert(taxonomy.name.clone(), canonical);
+        }
+        result
+    }
+
+    /// 获取词项对应的规范词项，没有别名且未合并时返回 `None`
+    pub fn canonical(&self, taxonomy: &str, term: &str) -> Option<&str> {
+        self.canonical
+            .get(taxonomy)?
+            .get(&term_key(term))
+            .map(String::as_str)
+    }
+
+    /// 将文章中的词项替换为规范词项，并去除替换后重复的词项
+    pub fn apply(&self, posts: &mut [Post], taxonomies: &[TaxonomyConfig]) {
+        for taxonomy in taxonomies {
+            if self.canonical.get(&taxonomy.name).is_none_or(HashMap::is_empty) {
+                continue;
+            }
+            for post in posts.iter_mut() {
+                let original = post.front_matter.terms(&taxonomy.name);
+                if original.is_empty() {
+                    continue;
+                }
+                let mut terms: Vec<String> = Vec::new();
+                for term in &original {
+                    let term = self.canonical(&taxonomy.name, term).unwrap_or(term).to_string();
+                    if !terms.contains(&term) {
+                        terms.push(term);
+                    }
+                }
+                if terms.iter().ne(original.iter()) {
+                    post.front_matter.set_terms(&taxonomy.name, terms);
+                }
+            }
+        }
+    }
+}
+
+/// 所有分类法词项的URL别名
+///
+/// 别名由 [`pinyin::slugify`] 生成（`蓝桉树下` → `lan-an-shu-xia`），只包含ASCII字符，
+/// 无法转写的词项使用由词项名哈希得到的 `term-xxxxxxxx`。
+/// 配置中的 `slugs` 优先，不合法的别名会被忽略。多个词项生成相同别名时，按词项首次出现的发布日期
+/// （相同时按名称）排序，最早的词项使用原别名，其余依次追加 `-2`、`-3`。
+///
+/// 发布日期更晚的新文章不会改变已有词项的地址；但补发日期更早的文章、
+/// 删除文章或修改日期可能改变后缀的归属，需要固定地址的词项应写入配置的 `slugs`。
+#[derive(Debug, Default)]
+pub struct TermSlugs {
+    /// 分类法名称 → 词项名 → 别名
+    slugs: HashMap<String, HashMap<String, String>>,
+    /// 分类法名称 → 别名 → 词项名
+    terms: HashMap<String, HashMap<String, String>>,
+}
+
+impl TermSlugs {
+    /// 为已发布文章中的所有词项分配别名
+    ///
+    /// # 参数
+    /// * `posts` - 按日期降序排列的文章，草稿不参与
+    /// * `taxonomies` - 分类法配置
+    pub fn build(posts: &[Post], taxonomies: &[TaxonomyConfig]) -> Self {
+        let mut result = Self::default();
+        for taxonomy in taxonomies {
+            // 按首次出现的日期与名称排列词项，较早的词项优先获得无后缀的别名，
+            // 结果与文章的加载顺序无关
+            let mut first_seen: HashMap<String, DateTime<FixedOffset>> = HashMap::new();
+            for post in posts.iter().filter(|p| !p.front_matter.draft) {
+                for term in taxonomy.post_terms(post) {
+                    let date = first_seen.entry(term).or_insert(post.front_matter.date);
+                    *date = (*date).min(post.front_matter.date);
+                }
+            }
+            let mut terms: Vec<String> = first_seen.keys().cloned().collect();
+            terms.sort_by(|a, b| first_seen[a].cmp(&first_seen[b]).then_with(|| a.cmp(b)));
+
+            let mut taken: HashSet<String> = HashSet::new();
+            let mut slugs: HashMap<String, String> = HashMap::new();
+            for term in &terms {
+                if let Some(slug) = taxonomy.slugs.get(term) {
+                    if !is_valid_slug(slug) {
+                        tracing::warn!(
+                            "Invalid slug `{}` for {} `{}`, generating one instead",
+                            slug,
+                            taxonomy.name,
+                            term
+                        );
+                    } else if taken.insert(slug.clone()) {
+                        slugs.insert(term.to_string(), slug.clone());
+                    } else {
+                        tracing::warn!(
+                            "Duplicate slug `{}` for {} `{}`, generating one instead",
+                            slug,
+                            taxonomy.name,
+                            term
+                        );
+                    }
+                }
+            }
+            for term in &terms {
+                if slugs.contains_key(term) {
+                    continue;
+                }
+                let mut base = pinyin::slugify(term);
+                if base.is_empty() {
+                    base = fallback_slug(term);
+                }
+                let mut slug = base.clone();
+                let mut suffix = 2;
+                while !taken.insert(slug.clone()) {
+                    slug = format!("{}-{}", base, suffix);
+                    suffix += 1;
+                }
+                slugs.insert(term.to_string(), slug);
+            }
+
+            let terms = slugs
+                .iter()
+                .map(|(term, slug)| (slug.clone(), term.clone()))
+                .collect();
+            result.terms.insert(taxonomy.name.clone(), terms);
+            result.slugs.insert(taxonomy.name.clone(), slugs);
+        }
+        result
+    }
+
+    /// 获取词项的别名
+    pub fn slug(&self, taxonomy: &str, term: &str) -> Option<&str> {
+        self.slugs.get(taxonomy)?.get(term).map(String::as_str)
+    }
+
+    /// 由别名查找词项名
+    pub fn term(&self, taxonomy: &str, slug: &str) -> Option<&str> {
+        self.terms.get(taxonomy)?.get(slug).map(String::as_str)
+    }
+
+    /// 词项在地址中的路径段：有别名时使用别名，否则使用转义后的词项名
+    pub fn path(&self, taxonomy: &str, term: &str) -> String {
+        match self.slug(taxonomy, term) {
+            Some(slug) => slug.to_string(),
+            None => utf8_percent_encode(term, TERM_PATH).to_string(),
+        }
+    }
+
+    /// 词项页面的地址，如 `/tags/lan-an-shu-xia/`；词项不存在时使用转义后的词项名
+    pub fn url(&self, taxonomy: &str, term: &str) -> String {
+        format!("/{}/{}/", taxonomy, self.path(taxonomy, term))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::query::PostQuery;
+    use crate::models::test_util::post;
+    use crate::models::FrontMatter;
+
+    fn posts() -> Vec<Post> {
+        vec![
+            post("a", "title: a\ndate: 2025-01-03T00:00:00+08:00\ntags: [Rust, 异步]\ntopics: [并发]", ""),
+            post("b", "title: b\ndate: 2025-01-02T00:00:00+08:00\ntags: [Rust]\ntopics: 并发\nauthor: 某人", ""),
+            post("c", "title: c\ndate: 2025-01-01T00:00:00+08:00\ndraft: true\ntags: [Rust]", ""),
+        ]
+    }
+
+    fn taxonomies() -> Vec<TaxonomyConfig> {
+        let mut taxonomies = TaxonomyConfig::defaults();
+        taxonomies.push(TaxonomyConfig::new("topics", "主题"));
+        taxonomies
+    }
+
+    #[test]
+    fn test_front_matter_terms() {
+        let front_matter: FrontMatter = serde_yaml::from_str(
+            "title: t\ndate: 2025-01-01T00:00:00+08:00\ncategories: [技术]\ncharacters: [苏蘅, 沈铎]\nrating: 5",
+        )
+        .unwrap();
+        assert_eq!(front_matter.terms("categories"), ["技术"]);
+        assert_eq!(front_matter.terms("characters"), ["苏蘅", "沈铎"]);
+        assert!(front_matter.terms("tags").is_empty());
+        assert!(front_matter.terms("rating").is_empty());
+        assert!(front_matter.terms("missing").is_empty());
+    }
+
+    #[test]
+    fn test_count_terms() {
+        let posts = posts();
+        let taxonomies = taxonomies();
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        assert_eq!(
+            count_terms(&posts, &taxonomies[0], &slugs),
+            [count("Rust", "rust", 2), count("异步", "yi-bu", 1)]
+        );
+        assert_eq!(count_terms(&posts, &taxonomies[2], &slugs), [count("并发", "bing-fa", 2)]);
+        assert!(count_terms(&posts, &taxonomies[1], &slugs).is_empty());
+    }
+
+    /// 包含指定词项的已发布文章的URL
+    fn posts_with_term(posts: Vec<Post>, taxonomy: &str, term: &str) -> Vec<String> {
+        let query = PostQuery::new().term(taxonomy, term);
+        query.run(posts, &taxonomies()).posts.into_iter().map(|p| p.url).collect()
+    }
+
+    #[test]
+    fn test_posts_with_term() {
+        assert_eq!(posts_with_term(posts(), "tags", "Rust"), ["a", "b"]);
+        assert_eq!(posts_with_term(posts(), "topics", "并发"), ["a", "b"]);
+        assert!(posts_with_term(posts(), "tags", "rust").is_empty());
+    }
+
+    fn count(name: &str, slug: &str, count: usize) -> TermCount {
+        TermCount {
+            name: name.to_string(),
+            label: label(name).to_string(),
+            slug: slug.to_string(),
+            count,
+        }
+    }
+
+    fn nested_posts() -> Vec<Post> {
+        vec![
+            post("a", "title: a\ndate: 2025-01-03T00:00:00+08:00\ncategories: [技术/Rust/异步, 技术/Rust]", ""),
+            post("b", "title: b\ndate: 2025-01-02T00:00:00+08:00\ncategories: [技术/Go]", ""),
+            post("c", "title: c\ndate: 2025-01-01T00:00:00+08:00\ncategories: [生活, /技术//Rust/]", ""),
+        ]
+    }
+
+    #[test]
+    fn test_hierarchical_terms() {
+        let posts = nested_posts();
+        let taxonomies = taxonomies();
+        let categories = &taxonomies[1];
+        assert!(categories.hierarchical);
+        assert_eq!(categories.post_terms(&posts[0]), ["技术", "技术/Rust", "技术/Rust/异步"]);
+        assert_eq!(categories.post_terms(&posts[2]), ["生活", "技术", "技术/Rust"]);
+
+        assert_eq!(posts_with_term(posts.clone(), "categories", "技术"), ["a", "b", "c"]);
+        assert_eq!(posts_with_term(posts.clone(), "categories", "技术/Rust"), ["a", "c"]);
+        assert_eq!(posts_with_term(posts.clone(), "categories", "技术/Rust/异步"), ["a"]);
+
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        assert_eq!(slugs.slug("categories", "技术/Rust/异步"), Some("ji-shu-rust-yi-bu"));
+        let terms = count_terms(&posts, categories, &slugs);
+        assert_eq!(terms[0], count("技术", "ji-shu", 3));
+        let tree = term_tree(&terms);
+        let names = |nodes: &[TermNode]| nodes.iter().map(|n| n.term.label.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&tree), ["技术", "生活"]);
+        assert_eq!(names(&tree[0].children), ["Rust", "Go"]);
+        assert_eq!(tree[0].children[0].term.count, 2);
+        assert_eq!(names(&tree[0].children[0].children), ["异步"]);
+
+        // 非层级分类法中 `/` 是词项名的一部分
+        let flat = TaxonomyConfig::new("categories", "分类");
+        assert_eq!(flat.post_terms(&posts[0]), ["技术/Rust/异步", "技术/Rust"]);
+    }
+
+    #[test]
+    fn test_breadcrumbs() {
+        let posts = nested_posts();
+        let taxonomies = taxonomies();
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        let crumbs = breadcrumbs(&taxonomies[1], "技术/Rust/异步", &slugs);
+        let labels: Vec<&str> = crumbs.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["技术", "Rust", "异步"]);
+        assert_eq!(crumbs[1].name, "技术/Rust");
+        assert_eq!(crumbs[1].url, "/categories/ji-shu-rust/");
+        let flat = breadcrumbs(&taxonomies[0], "a/b", &slugs);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].label, "a/b");
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: TaxonomyConfig = toml::from_str("name = \"topics\"").unwrap();
+        assert_eq!(config.per_page, 20);
+        assert!(config.feed && config.sitemap);
+        assert_eq!(config.template, "taxonomy.html.tera");
+        assert_eq!(config.term_template, "taxonomy_term.html.tera");
+        let defaults = TaxonomyConfig::defaults();
+        assert_eq!(defaults[0].name, "tags");
+        assert_eq!(defaults[1].term_template, "category_posts.html.tera");
+    }
+
+    #[test]
+    fn test_slug_collisions_and_overrides() {
+        let posts = vec![
+            post("new", "title: c\ndate: 2025-01-03T00:00:00+08:00\ntags: [rust, 诗, 蓝桉]", ""),
+            post("mid", "title: b\ndate: 2025-01-02T00:00:00+08:00\ntags: [Rust, 师, 🦀]", ""),
+            post("old", "title: a\ndate: 2025-01-01T00:00:00+08:00\ntags: [RUST, 湿]", ""),
+        ];
+        let mut taxonomies = taxonomies();
+        taxonomies[0].slugs.insert("蓝桉".to_string(), "eucalyptus".to_string());
+        taxonomies[0].slugs.insert("诗".to_string(), "<script>".to_string());
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        // 最早出现的词项获得无后缀的别名
+        assert_eq!(slugs.slug("tags", "RUST"), Some("rust"));
+        assert_eq!(slugs.slug("tags", "Rust"), Some("rust-2"));
+        assert_eq!(slugs.slug("tags", "rust"), Some("rust-3"));
+        assert_eq!(slugs.slug("tags", "湿"), Some("shi"));
+        assert_eq!(slugs.slug("tags", "师"), Some("shi-2"));
+        assert_eq!(slugs.slug("tags", "诗"), Some("shi-3"));
+        assert_eq!(slugs.slug("tags", "🦀"), Some(fallback_slug("🦀").as_str()));
+        assert!(fallback_slug("🦀").starts_with("term-"));
+        assert_ne!(fallback_slug("🦀"), fallback_slug("🐍"));
+        assert_eq!(slugs.slug("tags", "蓝桉"), Some("eucalyptus"));
+        assert_eq!(slugs.term("tags", "shi-2"), Some("师"));
+        assert_eq!(slugs.term("tags", "lan-an"), None);
+        assert_eq!(slugs.url("tags", "蓝桉"), "/tags/eucalyptus/");
+        assert_eq!(slugs.url("tags", "未知"), "/tags/%E6%9C%AA%E7%9F%A5/");
+        assert_eq!(slugs.url("tags", "a<b>\"c"), "/tags/a%3Cb%3E%22c/");
+        assert_eq!(slugs.slug("categories", "RUST"), None);
+    }
+
+    #[test]
+    fn test_slugs_stable_when_posts_added() {
+        let old = vec![post("a", "title: a\ndate: 2025-01-01T00:00:00+08:00\ntags: [诗]", "")];
+        let mut posts = vec![post("b", "title: b\ndate: 2025-02-01T00:00:00+08:00\ntags: [师]", "")];
+        posts.extend(old.clone());
+        let before = TermSlugs::build(&old, &taxonomies());
+        let after = TermSlugs::build(&posts, &taxonomies());
+        assert_eq!(before.slug("tags", "诗"), after.slug("tags", "诗"));
+        assert_eq!(after.slug("tags", "师"), Some("shi-2"));
+
+        // 与文章的加载顺序无关，同一天出现的词项按名称排序
+        posts.push(post("c", "title: c\ndate: 2025-02-01T00:00:00+08:00\ntags: [湿]", ""));
+        let forward = TermSlugs::build(&posts, &taxonomies());
+        posts.reverse();
+        let backward = TermSlugs::build(&posts, &taxonomies());
+        for term in ["诗", "师", "湿"] {
+            assert_eq!(forward.slug("tags", term), backward.slug("tags", term));
+        }
+        assert_eq!(forward.slug("tags", "师"), Some("shi-2"));
+        assert_eq!(forward.slug("tags", "湿"), Some("shi-3"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("ＲＵＳＴ"), "RUST");
+        assert_eq!(normalize("  Rust\u{3000}异步  编程 "), "Rust 异步 编程");
+        assert_eq!(normalize("蓝桉树（下）"), "蓝桉树(下)");
+        assert_eq!(term_key("Ｒｕｓｔ"), "rust");
+    }
+
+    #[test]
+    fn test_term_aliases() {
+        let mut posts = vec![
+            post("a", "title: a\ndate: 2025-01-04T00:00:00+08:00\ntags: [rust, 锈]", ""),
+            post("b", "title: b\ndate: 2025-01-03T00:00:00+08:00\ntags: [Rust, Go]\ntopics: [ＧＯ]", ""),
+            post("c", "title: c\ndate: 2025-01-02T00:00:00+08:00\ntags: [RUST, go]\ntopics: [Go]", ""),
+            post("d", "title: d\ndate: 2025-01-01T00:00:00+08:00\ntags: [Rust, ｇｏ]", ""),
+        ];
+        let mut taxonomies = taxonomies();
+        taxonomies[0].normalize = true;
+        taxonomies[0]
+            .aliases
+            .insert("Rust".to_string(), vec!["锈".to_string(), "rust-lang".to_string()]);
+        let aliases = TermAliases::build(&posts, &taxonomies);
+        assert_eq!(aliases.canonical("tags", "锈"), Some("Rust"));
+        assert_eq!(aliases.canonical("tags", "RUST"), Some("Rust"));
+        assert_eq!(aliases.canonical("tags", "Rust-Lang"), Some("Rust"));
+        // 取使用次数最多的写法
+        assert_eq!(aliases.canonical("tags", "GO"), Some("go"));
+        // 未开启 normalize 的分类法不合并
+        assert_eq!(aliases.canonical("topics", "ＧＯ"), None);
+
+        aliases.apply(&mut posts, &taxonomies);
+        assert_eq!(posts[0].front_matter.terms("tags"), ["Rust"]);
+        assert_eq!(posts[1].front_matter.terms("tags"), ["Rust", "go"]);
+        assert_eq!(posts[1].front_matter.terms("topics"), ["ＧＯ"]);
+
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        assert_eq!(
+            count_terms(&posts, &taxonomies[0], &slugs),
+            [count("Rust", "rust", 4), count("go", "go", 3)]
+        );
+    }
+
+    #[test]
+    fn test_pinyin_order_and_groups() {
+        let terms = vec![
+            count("蓝桉树", "lan-an-shu", 3),
+            count("Rust", "rust", 2),
+            count("阿里", "a-li", 1),
+            count("2025", "2025", 1),
+            count("沈铎", "shen-duo", 1),
+            count("老街", "lao-jie", 1),
+        ];
+        let mut sorted = terms.clone();
+        sort_by_pinyin(&mut sorted);
+        let names: Vec<&str> = sorted.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["阿里", "蓝桉树", "老街", "Rust", "沈铎", "2025"]);
+
+        let groups = group_by_letter(&terms);
+        let letters: Vec<&str> = groups.iter().map(|g| g.letter.as_str()).collect();
+        assert_eq!(letters, ["A", "L", "R", "S", "#"]);
+        let l: Vec<&str> = groups[1].terms.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(l, ["蓝桉树", "老街"]);
+        // 分组索引与列表的顺序一致
+        let grouped: Vec<&str> = groups
+            .iter()
+            .flat_map(|g| g.terms.iter().map(|t| t.name.as_str()))
+            .collect();
+        assert_eq!(grouped, names);
+        assert!(group_by_letter(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_pinyin_order_mixed_script() {
+        let mut terms = vec![
+            count("Go语言", "go-yu-yan", 1),
+            count("Rust", "rust", 1),
+            count("高效", "gao-xiao", 1),
+            count("认证", "ren-zheng", 1),
+            count("Git", "git", 1),
+        ];
+        sort_by_pinyin(&mut terms);
+        let names: Vec<&str> = terms.iter().map(|t| t.name.as_str()).collect();
+        // 拉丁单词整体与拼音比较：gao < git < go，ren < rust
+        assert_eq!(names, ["高效", "Git", "Go语言", "认证", "Rust"]);
+    }
+
+}
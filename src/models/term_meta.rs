@@ -0,0 +1,233 @@
+use super::{TaxonomyConfig, TermAliases, TermSlugs};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 词项说明文件的文件名，如 `content/tags/蓝桉树/_index.md`
+pub const TERM_INDEX_FILE: &str = "_index.md";
+
+/// 词项的说明页面，由 `_index.md` 的 Front Matter 与正文组成
+///
+/// # 字段说明
+/// * `title` - 自定义标题，替代词项名显示在页面与订阅源中
+/// * `description` - 词项简介，用于页面 meta description 与订阅源描述
+/// * `keywords` - 页面 meta keywords
+/// * `cover` - 封面图片地址
+/// * `noindex` - 为true时页面不加入站点地图，并要求搜索引擎不收录
+/// * `priority` - 站点地图中的优先级，默认使用站点配置
+/// * `content` - 正文渲染后的HTML，显示在文章列表上方
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TermMeta {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub keywords: Option<String>,
+    #[serde(default)]
+    pub cover: Option<String>,
+    #[serde(default)]
+    pub noindex: bool,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(skip_deserializing)]
+    pub content: String,
+}
+
+impl TermMeta {
+    /// 解析 `_index.md` 的内容，Front Matter 可省略
+    ///
+    /// # 参数
+    /// * `text` - 文件内容
+    /// * `render` - 正文的渲染函数，与文章正文使用同一渲染管线，见 [`super::Post::render_trusted`]
+    pub fn parse(text: &str, render: impl Fn(&str) -> String) -> Result<Self> {
+        let (mut meta, body) = match text.trim_start().strip_prefix("---") {
+            Some(rest) => {
+                let (yaml, body) = rest.split_once("---").unwrap_or((rest, ""));
+                let meta: Self = if yaml.trim().is_empty() {
+                    Self::default()
+                } else {
+                    serde_yaml::from_str(yaml)?
+                };
+                (meta, body)
+            }
+            None => (Self::default(), text),
+        };
+        meta.content = render(body);
+        Ok(meta)
+    }
+}
+
+/// 所有分类法词项的说明页面
+///
+/// 说明文件放在 `{dir}/{分类法}/{词项}/_index.md`，目录名可以是词项名、
+/// 词项的URL别名或配置的词项别名；层级分类法的下级词项使用嵌套目录，
+/// 如 `content/categories/技术/Rust/_index.md`。
+#[derive(Debug, Default)]
+pub struct TermMetaIndex {
+    /// 分类法名称 → 词项名 → 说明页面
+    metas: HashMap<String, HashMap<String, TermMeta>>,
+}
+
+impl TermMetaIndex {
+    /// 读取目录下所有分类法的词项说明文件，无法解析的文件记录警告后跳过
+    ///
+    /// # 参数
+    /// * `dir` - 说明文件的根目录，不存在时结果为空
+    /// * `taxonomies` - 分类法配置
+    /// * `slugs` - 词项的URL别名，用于识别以别名命名的目录
+    /// * `aliases` - 词项别名表，用于识别以别名命名的目录
+    /// * `render` - 正文的渲染函数
+    pub fn load(
+        dir: &Path,
+        taxonomies: &[TaxonomyConfig],
+        slugs: &TermSlugs,
+        aliases: &TermAliases,
+        render: impl Fn(&str) -> String,
+    ) -> Self {
+        let mut result = Self::default();
+        for taxonomy in taxonomies {
+            let mut files = Vec::new();
+            collect_index_files(&dir.join(&taxonomy.name), "", &mut files);
+            let mut metas = HashMap::new();
+            for (name, path) in files {
+                let meta = match std::fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|text| TermMeta::parse(&text, &render))
+                {
+                    Ok(meta) => meta,
+                    Err(e) => {
+                        tracing::warn!("Failed to load {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+                let term = match slugs.term(&taxonomy.name, &name) {
+                    Some(term) => term,
+                    None => aliases.canonical(&taxonomy.name, &name).unwrap_or(&name),
+                };
+                metas.insert(term.to_string(), meta);
+            }
+            result.metas.insert(taxonomy.name.clone(), metas);
+        }
+        result
+    }
+
+    /// 获取词项的说明页面
+    pub fn get(&self, taxonomy: &str, term: &str) -> Option<&TermMeta> {
+        self.metas.get(taxonomy)?.get(term)
+    }
+}
+
+/// 递归查找目录下的 `_index.md`，`name` 为相对分类法目录的路径，以 `/` 分隔
+fn collect_index_files(dir: &Path, name: &str, files: &mut Vec<(String, std::path::PathBuf)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(segment) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let name = if name.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}/{}", name, segment)
+        };
+        let index = path.join(TERM_INDEX_FILE);
+        if index.is_file() {
+            files.push((name.clone(), index));
+        }
+        collect_index_files(&path, &name, files);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+    use crate::models::{MarkdownConfig, Post, WikiIndex};
+    use crate::utils::links::LinkPolicy;
+    use crate::utils::shortcode::{Shortcode, ShortcodeRenderer};
+
+    /// 测试用短代码：`note` 输出带 `<script>` 的HTML
+    struct TestShortcodes;
+
+    impl ShortcodeRenderer for TestShortcodes {
+        fn render_shortcode(&self, shortcode: &Shortcode) -> Result<String> {
+            let inner = shortcode.inner.as_deref().unwrap_or("");
+            Ok(format!("<aside>{}<script>alert(1)</script></aside>", inner))
+        }
+    }
+
+    fn render(body: &str) -> String {
+        let config = MarkdownConfig::default();
+        let (wiki, links) = (WikiIndex::default(), LinkPolicy::default());
+        Post::render_trusted(body, &config, &TestShortcodes, &wiki, &links).html
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "---\ntitle: 蓝桉树的故事\ndescription: 关于蓝桉树\ncover: /static/cover.jpg\nnoindex: true\n---\n\n蓝桉树是**桃金娘科**植物。\n";
+        let meta = TermMeta::parse(text, render).unwrap();
+        assert_eq!(meta.title.as_deref(), Some("蓝桉树的故事"));
+        assert_eq!(meta.description.as_deref(), Some("关于蓝桉树"));
+        assert_eq!(meta.cover.as_deref(), Some("/static/cover.jpg"));
+        assert!(meta.noindex);
+        assert_eq!(meta.priority, None);
+        assert_eq!(meta.content.trim(), "<p>蓝桉树是<strong>桃金娘科</strong>植物。</p>");
+
+        let plain = TermMeta::parse("只有正文", render).unwrap();
+        assert_eq!(plain.title, None);
+        assert_eq!(plain.content.trim(), "<p>只有正文</p>");
+        assert!(TermMeta::parse("---\ntitle: [\n---\n", render).is_err());
+    }
+
+    #[test]
+    fn test_body_uses_post_pipeline() {
+        let body = "## 简介\n\n{{< note >}}提示{{< /note >}}\n\n<script>alert(2)</script>\n\n```rust\nfn main() {}\n```\n";
+        let meta = TermMeta::parse(body, render).unwrap();
+        assert!(meta.content.contains(r#"<h2 id="jian-jie">"#));
+        assert!(meta.content.contains("<aside>提示</aside>"));
+        assert!(meta.content.contains(r#"data-lang="rust""#));
+        assert!(!meta.content.contains("<script"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join("au92-blog-test-term-meta");
+        let _ = std::fs::remove_dir_all(&dir);
+        let write = |path: &str, text: &str| {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        write("tags/蓝桉树/_index.md", "---\ntitle: 蓝桉\n---\n");
+        write("tags/rust/_index.md", "---\ntitle: 锈\n---\n");
+        write("tags/锈/_index.md", "---\ntitle: 别名\n---\n");
+        write("categories/技术/Rust/_index.md", "---\ndescription: Rust 相关\n---\n");
+        write("categories/broken/_index.md", "---\ntitle: [\n---\n");
+
+        let post = post("a", "tags: [Rust, 蓝桉树]\ncategories: [技术/Rust]", "");
+        let mut taxonomies = TaxonomyConfig::defaults();
+        taxonomies[0].aliases.insert("Go".to_string(), vec!["锈".to_string()]);
+        let posts = vec![post];
+        let slugs = TermSlugs::build(&posts, &taxonomies);
+        let aliases = TermAliases::build(&posts, &taxonomies);
+        let index = TermMetaIndex::load(&dir, &taxonomies, &slugs, &aliases, render);
+        let title = |taxonomy, term| index.get(taxonomy, term).and_then(|m| m.title.as_deref());
+        assert_eq!(title("tags", "蓝桉树"), Some("蓝桉"));
+        assert_eq!(title("tags", "Rust"), Some("锈"));
+        assert_eq!(title("tags", "Go"), Some("别名"));
+        assert_eq!(
+            index.get("categories", "技术/Rust").and_then(|m| m.description.as_deref()),
+            Some("Rust 相关")
+        );
+        assert!(index.get("categories", "broken").is_none());
+        assert!(index.get("tags", "missing").is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+//! 测试用的文章构造工具
+
+use super::Post;
+use serde_yaml::{Mapping, Value};
+
+/// 未指定 `date` 时测试文章使用的发布日期
+const DEFAULT_DATE: &str = "2025-01-01T00:00:00+08:00";
+
+/// 构造一篇测试文章
+///
+/// Front Matter 按 YAML 解析，未给出 `title` 时使用 `url`，
+/// 未给出 `date` 时使用 `2025-01-01T00:00:00+08:00`。
+///
+/// # 参数
+/// * `url` - 文章URL
+/// * `front_matter` - YAML 格式的 Front Matter，可以为空
+/// * `content` - 文章正文
+pub fn post(url: &str, front_matter: &str, content: &str) -> Post {
+    let mut yaml: Mapping = serde_yaml::from_str::<Option<Mapping>>(front_matter)
+        .unwrap()
+        .unwrap_or_default();
+    yaml.entry("title".into()).or_insert_with(|| url.into());
+    yaml.entry("date".into()).or_insert_with(|| DEFAULT_DATE.into());
+    Post {
+        front_matter: serde_yaml::from_value(Value::Mapping(yaml)).unwrap(),
+        content: content.to_string(),
+        url: url.to_string(),
+        trusted: true,
+    }
+}
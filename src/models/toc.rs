@@ -0,0 +1,99 @@
+use crate::utils::heading::Heading;
+use serde::{Deserialize, Serialize};
+
+/// 文章目录项
+///
+/// # 功能说明
+/// - 以树形结构表示文章目录，子标题挂在上一级标题的 `children` 下
+///
+/// # 字段说明
+/// * `level` - 标题层级（2表示h2）
+/// * `title` - 标题纯文本
+/// * `id` - 标题锚点ID，与正文中标题的 `id` 属性一致
+/// * `children` - 下级目录项
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TocItem {
+    pub level: u8,
+    pub title: String,
+    pub id: String,
+    pub children: Vec<TocItem>,
+}
+
+impl TocItem {
+    /// 根据文章标题列表构建目录树
+    ///
+    /// # 参数
+    /// * `headings` - 正文渲染时收集的标题
+    /// * `min_level` - 目录包含的最高标题层级
+    /// * `max_level` - 目录包含的最低标题层级
+    ///
+    /// # 返回
+    /// * `Vec<TocItem>` - 顶层目录项列表
+    pub fn build_tree(headings: &[Heading], min_level: u8, max_level: u8) -> Vec<TocItem> {
+        let mut roots = Vec::new();
+        for heading in headings
+            .iter()
+            .filter(|h| (min_level..=max_level).contains(&h.level))
+        {
+            Self::insert(
+                &mut roots,
+                TocItem {
+                    level: heading.level,
+                    title: heading.title.clone(),
+                    id: heading.id.clone(),
+                    children: Vec::new(),
+                },
+            );
+        }
+        roots
+    }
+
+    /// 将目录项插入到最近的上级目录项之下
+    fn insert(items: &mut Vec<TocItem>, item: TocItem) {
+        match items.last_mut() {
+            Some(last) if last.level < item.level => Self::insert(&mut last.children, item),
+            _ => items.push(item),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(level: u8, id: &str) -> Heading {
+        Heading {
+            level,
+            id: id.to_string(),
+            title: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_tree() {
+        let headings = vec![
+            heading(1, "title"),
+            heading(2, "a"),
+            heading(3, "a1"),
+            heading(4, "a1x"),
+            heading(3, "a2"),
+            heading(2, "b"),
+        ];
+        let toc = TocItem::build_tree(&headings, 2, 3);
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0].id, "a");
+        let children: Vec<_> = toc[0].children.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(children, vec!["a1", "a2"]);
+        assert!(toc[0].children[0].children.is_empty());
+        assert!(toc[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_build_tree_skipped_level() {
+        // h3 出现在第一个 h2 之前时作为顶层目录项
+        let headings = vec![heading(3, "x"), heading(2, "a"), heading(3, "a1")];
+        let toc = TocItem::build_tree(&headings, 2, 3);
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[1].children[0].id, "a1");
+    }
+}
@@ -0,0 +1,119 @@
+use super::Post;
+use crate::utils::pinyin;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 可作为附件嵌入的文件扩展名
+const ATTACHMENT_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "avif"];
+
+/// Obsidian 双链索引
+///
+/// # 功能说明
+/// - 按标题、slug（标题的拼音形式）或文件名查找文章，不区分大小写
+/// - 按文件名查找 `static/` 下的图片附件
+///
+/// # 字段说明
+/// * `posts` - 已发布的文章
+/// * `keys` - 查找键到文章下标的映射
+/// * `attachments` - 小写文件名到访问地址的映射
+#[derive(Debug, Default)]
+pub struct WikiIndex {
+    posts: Vec<Post>,
+    keys: HashMap<String, usize>,
+    attachments: HashMap<String, String>,
+}
+
+impl WikiIndex {
+    /// 根据文章列表与静态文件目录构建索引
+    ///
+    /// 文件名优先于标题，标题优先于slug；同名时先出现的文章优先。
+    ///
+    /// # 参数
+    /// * `posts` - 已发布的文章
+    /// * `static_dir` - 静态文件目录，其中的图片可通过 `![[文件名]]` 嵌入
+    pub fn new(posts: Vec<Post>, static_dir: &Path) -> Self {
+        let mut keys = HashMap::new();
+        let key_sets: [fn(&Post) -> String; 3] = [
+            |post| post.url.clone(),
+            |post| post.front_matter.title.trim().to_string(),
+            |post| pinyin::slugify(&post.front_matter.title),
+        ];
+        for key_of in key_sets {
+            for (index, post) in posts.iter().enumerate() {
+                keys.entry(key_of(post).to_lowercase()).or_insert(index);
+            }
+        }
+
+        let mut attachments = HashMap::new();
+        collect_attachments(static_dir, "/static", &mut attachments);
+
+        Self {
+            posts,
+            keys,
+            attachments,
+        }
+    }
+
+    /// 查找双链指向的文章
+    ///
+    /// # 参数
+    /// * `target` - 双链目标，可以是标题、slug或文件名（可带 `.md` 后缀）
+    pub fn resolve(&self, target: &str) -> Option<&Post> {
+        let target = target.trim();
+        let target = target.strip_suffix(".md").unwrap_or(target).to_lowercase();
+        self.keys.get(&target).map(|&index| &self.posts[index])
+    }
+
+    /// 按文件名（即文章URL）查找文章
+    pub fn by_url(&self, url: &str) -> Option<&Post> {
+        self.keys
+            .get(&url.to_lowercase())
+            .map(|&index| &self.posts[index])
+            .filter(|post| post.url.eq_ignore_ascii_case(url))
+    }
+
+    /// 已发布的文章
+    pub fn posts(&self) -> &[Post] {
+        &self.posts
+    }
+
+    /// 查找附件的访问地址
+    ///
+    /// 目标包含路径时按 `static/` 下的相对路径查找，否则按文件名查找。
+    pub fn attachment(&self, target: &str) -> Option<&str> {
+        let target = target.trim();
+        let name = target.rsplit('/').next().unwrap_or_default();
+        let path = format!("/{}", target.trim_start_matches('/').replace(' ', "%20"));
+        self.attachments
+            .get(&name.to_lowercase())
+            .map(String::as_str)
+            .filter(|url| !target.contains('/') || url.ends_with(&path))
+    }
+
+    /// 判断双链目标是否为附件
+    pub fn is_attachment(target: &str) -> bool {
+        Path::new(target.trim())
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ATTACHMENT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+    }
+}
+
+/// 递归收集目录中的图片附件，同名文件以先找到的为准
+fn collect_attachments(dir: &Path, url: &str, attachments: &mut HashMap<String, String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let child_url = format!("{}/{}", url, name.replace(' ', "%20"));
+        let path = entry.path();
+        if path.is_dir() {
+            collect_attachments(&path, &child_url, attachments);
+        } else if WikiIndex::is_attachment(&name) {
+            attachments.entry(name.to_lowercase()).or_insert(child_url);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 /// 路由模块 - 负责处理所有HTTP路由配置和请求分发
-use crate::services::{CategoryService, PostService, RssService, SitemapService, UploadService,FriendLinkService};
-use crate::{handlers, services::TagService};
+use crate::services::{PostService, RssService, SitemapService, UploadService,FriendLinkService, SearchService, StatsService};
+use crate::{handlers, services::TaxonomyService};
 use axum::routing::get_service;
 use axum::{
     routing::{get, post},
@@ -15,10 +15,8 @@ use tower_http::services::ServeDir;
 /// 在请求处理过程中可以访问这些服务
 #[derive(Clone)]
 pub struct AppState {
-    /// 分类服务实例：处理文章分类相关功能
-    pub category_service: Arc<CategoryService>,
-    /// 标签服务实例：处理文章标签相关功能
-    pub tag_service: Arc<TagService>,
+    /// 分类法服务实例：处理标签、分类与自定义分类法相关功能
+    pub taxonomy_service: Arc<TaxonomyService>,
     /// RSS服务实例：生成站点RSS订阅源
     pub rss_service: Arc<RssService>,
     /// Sitemap服务实例：生成站点地图
@@ -29,44 +27,42 @@ pub struct AppState {
     pub upload_service: Arc<UploadService>,
     /// 友链服务实例：处理友链的加载和渲染
     pub friend_service: Arc<FriendLinkService>,
+    /// 搜索服务实例：处理站内全文搜索
+    pub search_service: Arc<SearchService>,
+    /// 统计服务实例：处理写作统计与年度回顾
+    pub stats_service: Arc<StatsService>,
 }
 
 /// 创建并配置应用路由系统
 ///
 /// # 功能说明
-/// - 初始化应用状态，包含所有核心服务实例
+/// - 接收包含所有核心服务实例的应用状态
 /// - 配置所有HTTP路由规则和对应的处理函数
 /// - 支持文章、分类、标签、RSS和站点地图等功能的访问
 ///
 /// # 路由说明
 /// - `/post/page/:page/` - 分页显示文章列表
 /// - `/post/` - 显示文章首页
-/// - `/categories/` - 显示分类列表
-/// - `/categories/:category/` - 显示特定分类下的文章
-/// - `/tags/` - 显示标签云
-/// - `/tags/:tag/` - 显示特定标签下的文章
+/// - `/random/` - 302跳转到随机一篇已发布文章
+/// - `/posts/?tag=&category=&year=&sort=` - 按标签、分类、年份等组合筛选的文章列表
+/// - `/archives/` - 按年、按月统计文章数的归档索引
+/// - `/archives/:year/`、`/archives/:year/:month/` - 年度、月度归档，月度归档带月历
+/// - `/:taxonomy/` - 显示分类法的词项列表，如 `/tags/`、`/categories/`
+/// - `/:taxonomy/:term/` - 显示特定词项下的文章，如 `/tags/Rust/`
+/// - `/:taxonomy/:term/index.xml` - 特定词项的RSS订阅源
+/// - `/search/?q=` - 站内全文搜索
+/// - `/search/suggest?q=` - 标题与标签的输入建议（JSON），支持拼音
+/// - `/search-index.json` - 供静态镜像在浏览器端检索的搜索索引（JSON，带ETag）
 /// - `/index.xml` - RSS订阅源
 /// - `/sitemap.xml` - 网站地图
+/// - `/highlight.css` - 代码高亮样式表
+/// - `/graph.json` - 文章链接关系图数据
+/// - `/stats/`、`/stats.json` - 每年每月的文章数与字数、常用词项、最长文章与写作热力图
+/// - `/stats/:year/` - 年度回顾
+/// - `/images/` - 正文图片的缩略图与WebP版本
 /// - `/refresh/posts/` - 刷新文章缓存
-pub fn create_router(
-    category_service: Arc<CategoryService>,
-    tag_service: Arc<TagService>,
-    rss_service: Arc<RssService>,
-    sitemap_service: Arc<SitemapService>,
-    post_service: Arc<PostService>,
-    upload_service: Arc<UploadService>,
-    friend_service: Arc<FriendLinkService>,
-) -> Router {
-    // 创建应用状态实例
-    let state = AppState {
-        category_service,
-        tag_service,
-        rss_service,
-        sitemap_service,
-        post_service,
-        upload_service,
-        friend_service,
-    };
+pub fn create_router(state: AppState) -> Router {
+    let image_cache_dir = state.post_service.image_cache_dir().to_string();
 
     // 构建路由表
     // 使用 axum 的 Router 来定义所有路由规则
@@ -80,24 +76,36 @@ pub fn create_router(
         .route("/post/{url}/", get(handlers::post_detail))
         .route("/post/{url}", get(handlers::post_detail))
         .route("/post/{url}/index.html", get(handlers::post_detail))
-        // 分类相关路由
-        .route("/categories/", get(handlers::categories_index))
+        .route("/random/", get(handlers::random_post))
+        // 组合筛选的文章列表
+        .route("/posts/", get(handlers::filter_posts))
+        // 按年、按月归档
+        .route("/archives/", get(handlers::archive_index))
+        .route("/archives/{year}/", get(handlers::year_archive))
+        .route("/archives/{year}/{month}/", get(handlers::month_archive))
+        // 写作统计与年度回顾
+        .route("/stats/", get(handlers::stats_page))
+        .route("/stats.json", get(handlers::stats_json))
+        .route("/stats/{year}/", get(handlers::year_review))
+        // 分类法相关路由（标签、分类与配置中声明的其他分类法），
+        // 固定路径优先匹配，未配置的分类法返回404
+        .route("/{taxonomy}/", get(handlers::taxonomy_terms))
         .route(
-            "/categories/{category}/page/{page}/",
-            get(handlers::category_posts_with_page),
+            "/{taxonomy}/{term}/page/{page}/",
+            get(handlers::term_posts_with_page),
         )
-        .route("/categories/{category}/", get(handlers::category_posts))
-        // 标签相关路由
-        .route("/tags/", get(handlers::tags_index))
-        .route(
-            "/tags/{tag}/page/{page}/",
-            get(handlers::tag_posts_with_page),
-        )
-        .route("/tags/{tag}/", get(handlers::tag_posts))
-        .route("/tags/{tag}", get(handlers::tag_posts))
+        .route("/{taxonomy}/{term}/index.xml", get(handlers::term_feed))
+        .route("/{taxonomy}/{term}/", get(handlers::term_posts))
+        .route("/{taxonomy}/{term}", get(handlers::term_posts))
+        // 搜索路由
+        .route("/search/", get(handlers::search))
+        .route("/search/suggest", get(handlers::search_suggest))
+        .route("/search-index.json", get(handlers::search_index_json))
         // 站点功能路由
         .route("/index.xml", get(handlers::rss_feed))
         .route("/sitemap.xml", get(handlers::sitemap_xml))
+        .route("/highlight.css", get(handlers::highlight_css))
+        .route("/graph.json", get(handlers::graph_json))
         // 首页路由
         .route("/index.html", get(handlers::render_index))
         .route("/", get(handlers::render_index))
@@ -106,6 +114,7 @@ pub fn create_router(
         .route("/refresh/posts/", get(handlers::refresh_posts))
         // Static files
         .nest_service("/static", get_service(ServeDir::new("static")))
+        .nest_service("/images", get_service(ServeDir::new(image_cache_dir)))
         // 注入应用状态
         .with_state(state)
 }
@@ -35,13 +35,27 @@ impl FriendLinkService {
     /// # 返回
     /// * `Vec<FriendLink>` - 友情连接列表
     async fn get_friend_links(&self) -> Vec<FriendLink> {
+        Self::load_friend_links()
+    }
+
+    /// 读取/static/friends.yaml中的友情连接，文件不存在或格式错误时返回空列表
+    ///
+    /// 渲染文章时也会用到友链地址，用于判断外部链接是否受信任
+    pub fn load_friend_links() -> Vec<FriendLink> {
         let file_path = Path::new("static/friends.yaml");
         if !file_path.exists() {
             return vec![];
         }
-        let file = File::open(file_path).unwrap();
-        let reader = BufReader::new(file);
-        let friend_links: Vec<FriendLink> = serde_yaml::from_reader(reader).unwrap();
-        friend_links
+        let reader = match File::open(file_path) {
+            Ok(file) => BufReader::new(file),
+            Err(e) => {
+                tracing::warn!("Failed to open {}: {}", file_path.display(), e);
+                return vec![];
+            }
+        };
+        serde_yaml::from_reader(reader).unwrap_or_else(|e| {
+            tracing::warn!("Invalid {}: {}", file_path.display(), e);
+            vec![]
+        })
     }
 }
@@ -1,19 +1,18 @@
-/// 博客核心服务模块
-/// 
-/// # 模块说明
-/// * `post_service` - 文章管理服务，提供文章的CRUD操作
-/// * `tag_service` - 标签管理服务，处理文章标签相关功能
-/// * `category_service` - 分类管理服务，处理文章分类相关功能
-/// * `template_service` - 模板渲染服务，负责HTML页面生成
-/// * `rss_service` - RSS订阅服务，生成订阅源
-/// * `sitemap_service` - 站点地图服务，生成搜索引擎所需的站点地图
+//! 博客核心服务模块
+//! 
+//! # 模块说明
+//! * `post_service` - 文章管理服务，提供文章的CRUD操作
+//! * `taxonomy_service` - 分类法服务，统一处理标签、分类与自定义分类法
+//! * `template_service` - 模板渲染服务，负责HTML页面生成
+//! * `rss_service` - RSS订阅服务，生成订阅源
+//! * `sitemap_service` - 站点地图服务，生成搜索引擎所需的站点地图
+//! * `search_service` - 站内搜索服务，检索文章并渲染搜索结果
+//! * `stats_service` - 写作统计服务，生成统计页面与年度回顾
 
 /// 文章服务模块，提供文章的加载、解析和管理功能
 pub mod post_service;
-/// 标签服务模块，提供标签的统计和文章分类功能
-pub mod tag_service;
-/// 分类服务模块，提供文章分类管理功能
-pub mod category_service;
+/// 分类法服务模块，提供标签、分类等分类法的统计与文章列表功能
+pub mod taxonomy_service;
 /// 模板服务模块，提供模板渲染和管理功能
 pub mod template_service;
 /// RSS服务模块，提供RSS订阅功能
@@ -24,13 +23,18 @@ pub mod sitemap_service;
 pub mod upload_service;
 /// 友链服务模块，提供友链的加载和渲染功能
 pub mod friend_service;
+/// 搜索服务模块，提供站内全文搜索功能
+pub mod search_service;
+/// 统计服务模块，提供写作统计与年度回顾功能
+pub mod stats_service;
 
 // 导出服务结构体，方便其他模块使用
 pub use post_service::PostService;
-pub use tag_service::TagService;
-pub use category_service::CategoryService;
+pub use taxonomy_service::TaxonomyService;
 pub use template_service::TemplateService;
 pub use rss_service::RssService;
 pub use sitemap_service::SitemapService;
 pub use upload_service::UploadService;
 pub use friend_service::FriendLinkService;
+pub use search_service::SearchService;
+pub use stats_service::StatsService;
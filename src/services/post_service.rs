@@ -1,13 +1,22 @@
-use super::TemplateService;
+use super::upload_service::UPLOAD_DIR;
+use super::{FriendLinkService, TemplateService};
 use crate::error::AppError;
+use crate::models::archive::{count_by_month, group_posts, on_this_day};
 use crate::models::page::Page;
-use crate::models::{Archive, ArchivePost, FrontMatter, Post};
+use crate::models::query::{PostQuery, QueryResult};
+use crate::models::taxonomy::{breadcrumbs, Breadcrumb};
+use crate::models::{
+    Archive, Backlink, Calendar, FrontMatter, OnThisDay, GraphData, LinkGraph, Post, RelatedIndex,
+    SearchExport, SearchIndex, Site, SuggestIndex, TermAliases, TermMetaIndex, TermSlugs, TocItem, WikiIndex, WordCount,
+};
+use crate::utils::links::LinkPolicy;
+use crate::utils::{highlight, images, obsidian, shortcode};
 use anyhow::Result;
 use chrono::Datelike;
 use once_cell::sync::Lazy;
-use rand::seq::SliceRandom;
+use rand::seq::{IndexedRandom, SliceRandom};
 use serde::{Deserialize, Serialize};
-use std::{path::Path, sync::Arc};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
 use tera::Context;
 use tokio::fs;
 use tokio::sync::RwLock;
@@ -15,8 +24,72 @@ use tokio::sync::RwLock;
 /// 全局文章缓存
 /// 使用 Lazy 和 RwLock 实现线程安全的延迟初始化缓存
 static POSTS_CACHE: Lazy<RwLock<Option<Vec<Post>>>> = Lazy::new(|| RwLock::new(None));
-/// 定义每分钟阅读汉字的速度，用于计算文章阅读时间
-static READ_SPEED: u16 = 200; // 阅读速度（汉字/分钟）
+
+/// 全局双链索引，随文章缓存一同在加载时重建
+static WIKI_INDEX: Lazy<RwLock<Arc<WikiIndex>>> =
+    Lazy::new(|| RwLock::new(Arc::new(WikiIndex::default())));
+
+/// 全局文章链接关系，随文章缓存一同在加载时重建
+static LINK_GRAPH: Lazy<RwLock<Arc<LinkGraph>>> =
+    Lazy::new(|| RwLock::new(Arc::new(LinkGraph::default())));
+
+/// 全局外部链接处理策略，随文章缓存一同在加载时重建以读取最新的友链
+static LINK_POLICY: Lazy<RwLock<Arc<LinkPolicy>>> =
+    Lazy::new(|| RwLock::new(Arc::new(LinkPolicy::default())));
+
+/// 全局站内搜索索引，随文章缓存一同在加载时重建
+static SEARCH_INDEX: Lazy<RwLock<Arc<SearchIndex>>> =
+    Lazy::new(|| RwLock::new(Arc::new(SearchIndex::default())));
+
+/// 全局静态搜索索引，随文章缓存一同在加载时重建
+static SEARCH_EXPORT: Lazy<RwLock<Arc<SearchExport>>> =
+    Lazy::new(|| RwLock::new(Arc::new(SearchExport::default())));
+
+/// 全局输入建议索引，随文章缓存一同在加载时重建
+static SUGGEST_INDEX: Lazy<RwLock<Arc<SuggestIndex>>> =
+    Lazy::new(|| RwLock::new(Arc::new(SuggestIndex::default())));
+
+/// 全局相关文章索引，随文章缓存一同在加载时重建
+static RELATED_INDEX: Lazy<RwLock<Arc<RelatedIndex>>> =
+    Lazy::new(|| RwLock::new(Arc::new(RelatedIndex::default())));
+
+/// 全局词项URL别名，随文章缓存一同在加载时重建
+///
+/// 模板过滤器 `term_url` 以同步方式读取，因此使用标准库的读写锁。
+static TERM_SLUGS: Lazy<std::sync::RwLock<Arc<TermSlugs>>> =
+    Lazy::new(|| std::sync::RwLock::new(Arc::new(TermSlugs::default())));
+
+/// 全局词项别名表，随文章缓存一同在加载时重建
+static TERM_ALIASES: Lazy<std::sync::RwLock<Arc<TermAliases>>> =
+    Lazy::new(|| std::sync::RwLock::new(Arc::new(TermAliases::default())));
+
+/// 获取当前的词项别名表
+pub fn term_aliases() -> Arc<TermAliases> {
+    match TERM_ALIASES.read() {
+        Ok(aliases) => Arc::clone(&aliases),
+        Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+    }
+}
+
+/// 词项说明文件的根目录，如 `content/tags/蓝桉树/_index.md`
+pub const TERM_CONTENT_DIR: &str = "content";
+
+/// 全局词项说明页面，随文章缓存一同在加载时重建
+static TERM_META: Lazy<RwLock<Arc<TermMetaIndex>>> =
+    Lazy::new(|| RwLock::new(Arc::new(TermMetaIndex::default())));
+
+/// 获取当前的词项说明页面
+pub async fn term_meta() -> Arc<TermMetaIndex> {
+    Arc::clone(&*TERM_META.read().await)
+}
+
+/// 获取当前的词项URL别名
+pub fn term_slugs() -> Arc<TermSlugs> {
+    match TERM_SLUGS.read() {
+        Ok(slugs) => Arc::clone(&slugs),
+        Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+    }
+}
 
 /// 文章服务结构体
 /// 负责博客文章的加载、缓存管理、解析和查询等核心功能
@@ -31,6 +104,8 @@ static READ_SPEED: u16 = 200; // 阅读速度（汉字/分钟）
 pub struct PostService {
     /// 模板服务实例，用于处理页面渲染
     template_service: Arc<TemplateService>,
+    /// 站点配置信息，提供阅读速度等设置
+    site: Site,
 }
 
 /// 文章摘要结构体，用于首页文章列表展示
@@ -40,8 +115,9 @@ pub struct PostSummary {
     pub content: String,           // 文章完整内容
     pub url: String,               // 文章访问地址
     pub summary: String,           // 文章摘要内容
-    pub count: usize,              // 文章字数统计
-    pub read_time: u16,            // 预估阅读时间（分钟）
+    pub count: usize,              // 文章字数统计（汉字数 + 单词数）
+    pub word_count: WordCount,     // 汉字数与单词数的分项统计
+    pub read_time: u32,            // 预估阅读时间（分钟）
 }
 
 /// 单篇文章详情结构体，用于文章详情页展示
@@ -51,12 +127,16 @@ pub struct SinglePost {
     pub content: String,                   // 文章HTML内容
     pub summary: String,                   // 文章摘要
     pub url: String,                       // 文章URL
-    pub count: usize,                      // 文章字数
-    pub read_time: u16,                    // 预估阅读时间
-    pub toc: Vec<(usize, String, String)>, // 文章目录结构：(层级, 标题, ID)
+    pub count: usize,                      // 文章字数（汉字数 + 单词数）
+    pub word_count: WordCount,             // 汉字数与单词数的分项统计
+    pub read_time: u32,                    // 预估阅读时间
+    pub toc: Vec<TocItem>,                 // 文章目录树
+    pub backlinks: Vec<Backlink>,          // 引用本文的文章
     pub prev: Option<Post>,                // 上一篇文章
     pub next: Option<Post>,                // 下一篇文章
-    pub random_posts: Vec<Post>,           // 随机推荐文章列表
+    pub related_posts: Vec<Post>,          // 相关文章列表，不足时随机补足
+    pub breadcrumbs: BTreeMap<String, Vec<Vec<Breadcrumb>>>, // 层级分类法名 → 各词项的面包屑
+    pub on_this_day: Vec<OnThisDay>,       // 往年同月同日发布的文章
 }
 
 impl PostService {
@@ -64,8 +144,12 @@ impl PostService {
     ///
     /// # 参数
     /// * `template_service` - 模板服务实例
-    pub fn new(template_service: Arc<TemplateService>) -> Self {
-        Self { template_service }
+    /// * `site` - 站点配置信息
+    pub fn new(template_service: Arc<TemplateService>, site: Site) -> Self {
+        Self {
+            template_service,
+            site,
+        }
     }
 
     /// 渲染首页
@@ -75,6 +159,7 @@ impl PostService {
     /// - 加载并过滤非草稿状态的文章
     /// - 处理分页逻辑
     /// - 生成文章摘要和阅读时间
+    /// - 第一页附带往年今日发布的文章
     /// - 准备模板渲染所需的上下文数据
     ///
     /// # 参数
@@ -86,43 +171,81 @@ impl PostService {
         let mut context = Context::new();
         // 设置每页显示的记录数
         let per_page = 10;
-        // 加载所有文章
-        let all_posts = self
-            .load_all_posts()
-            .await?
-            .into_iter()
-            .filter(|p| !p.front_matter.draft);
-        // 使用count()获取过滤后的数量
-        let len = all_posts.clone().count();
-        let posts: Vec<PostSummary> = all_posts
-            .skip((page - 1) * per_page)
-            .take(per_page)
-            .map(|post| {
-                let summary = post.generate_description(200);
-                PostSummary {
-                    front_matter: post.front_matter.clone(),
-                    content: post.content.clone(),
-                    url: post.url.clone(),
-                    summary,
-                    count: post.count_chinese_chars(),
-                    read_time: post.read_time(READ_SPEED),
-                }
-            })
-            .collect();
+        let result = self.query(&PostQuery::new().page(page, per_page)).await?;
+        let posts: Vec<PostSummary> = result.posts.iter().map(|p| self.summarize(p)).collect();
         context.insert("posts", &posts);
 
-        let total_pages = (len + per_page - 1) / per_page;
-        let page = Page::from_count(total_pages as u16, page as u16);
+        let page = Page::clamped(result.total, result.current, per_page);
         context.insert("page", &page);
 
         if page.current > 1 {
             context.insert("site_title", &format!("第{}页 - ", page.current));
+        } else {
+            // 首页第一页显示往年今日发布的文章
+            let posts = self.load_all_posts().await?;
+            context.insert("on_this_day", &on_this_day(&posts, self.site.today()));
         }
 
         // 渲染首页模板
         self.template_service.render("index.html.tera", &context)
     }
 
+    /// 按条件查询已发布文章
+    ///
+    /// # 参数
+    /// * `query` - 筛选、排序与分页条件
+    ///
+    /// # 返回
+    /// * `Result<QueryResult>` - 当前页的文章与符合条件的文章总数
+    pub async fn query(&self, query: &PostQuery) -> Result<QueryResult> {
+        let posts = self.load_all_posts().await?;
+        Ok(query.run(posts, &self.site.taxonomies))
+    }
+
+    /// 渲染组合筛选的文章列表页面 `/posts/`
+    ///
+    /// # 参数
+    /// * `query` - 由请求参数生成的查询条件
+    /// * `filter` - 请求参数，用于页面中回显筛选条件与生成分页链接
+    /// * `page` - 当前页码
+    /// * `per_page` - 每页文章数
+    pub async fn render_posts<T: Serialize>(
+        &self,
+        query: PostQuery,
+        filter: &T,
+        page: usize,
+        per_page: usize,
+    ) -> Result<String> {
+        let result = self.query(&query.page(page, per_page)).await?;
+        let posts: Vec<PostSummary> = result.posts.iter().map(|p| self.summarize(p)).collect();
+        let page = Page::clamped(result.total, result.current, per_page);
+
+        let mut context = Context::new();
+        context.insert("posts", &posts);
+        context.insert("count", &result.total);
+        context.insert("filter", filter);
+        context.insert("page", &page);
+        if page.current > 1 {
+            context.insert("site_title", &format!("第{}页 - ", page.current));
+        }
+        self.template_service.render("posts.html.tera", &context)
+    }
+
+    /// 生成文章列表中使用的摘要信息
+    fn summarize(&self, post: &Post) -> PostSummary {
+        let summary = post.generate_description(200, &self.site.markdown);
+        let word_count = post.word_count(&self.site.markdown);
+        PostSummary {
+            front_matter: post.front_matter.clone(),
+            content: post.content.clone(),
+            url: post.url.clone(),
+            summary,
+            count: word_count.total(),
+            word_count,
+            read_time: word_count.read_time(self.site.read_speed_cjk, self.site.read_speed_latin),
+        }
+    }
+
     /// 渲染博客文章详情页面
     ///
     /// # 功能说明
@@ -229,10 +352,8 @@ impl PostService {
     /// * `Result<String>` - 返回渲染后的HTML字符串或错误
     pub async fn render_archives(&self, page: usize, per_page: usize) -> Result<String> {
         let mut context = Context::new();
-        let (archives, len) = self.get_paginated_archives(page, per_page).await?;
+        let (archives, page) = self.get_paginated_archives(page, per_page).await?;
         context.insert("archives", &archives);
-
-        let page = Page::new(len as u32, page as u16, per_page as u16);
         context.insert("page", &page);
 
         if page.current > 1 {
@@ -241,6 +362,161 @@ impl PostService {
         self.template_service.render("archives.html.tera", &context)
     }
 
+    /// 渲染完整的归档索引页面 `/archives/`，列出每年、每月的文章数
+    pub async fn render_archive_index(&self) -> Result<String, AppError> {
+        let posts = self.published_posts().await?;
+        let mut context = Context::new();
+        context.insert("years", &count_by_month(&posts));
+        context.insert("count", &posts.len());
+        Ok(self.template_service.render("archive_index.html.tera", &context)?)
+    }
+
+    /// 渲染某年的归档页面 `/archives/{year}/`，文章按月份分组
+    ///
+    /// # 参数
+    /// * `year` - 年份
+    ///
+    /// # 返回
+    /// * `Result<String, AppError>` - 渲染后的HTML，该年没有文章时返回 NotFound
+    pub async fn render_year_archive(&self, year: u32) -> Result<String, AppError> {
+        let posts = self.published_posts().await?;
+        let years = count_by_month(&posts);
+        let Some(current) = years.iter().find(|y| y.year == year) else {
+            return Err(AppError::NotFound(format!("/archives/{}/", year)));
+        };
+        let in_year = posts.iter().filter(|p| p.front_matter.date.year() as u32 == year);
+
+        let mut context = Context::new();
+        context.insert("year", &year);
+        context.insert("current", current);
+        context.insert("years", &years);
+        context.insert("archives", &group_posts(in_year, true));
+        context.insert("site_title", &format!("{}年 - ", year));
+        Ok(self.template_service.render("archive_period.html.tera", &context)?)
+    }
+
+    /// 渲染某月的归档页面 `/archives/{year}/{month}/`，包含文章列表与月历
+    ///
+    /// # 参数
+    /// * `year` - 年份
+    /// * `month` - 月份，1–12
+    ///
+    /// # 返回
+    /// * `Result<String, AppError>` - 渲染后的HTML，该月没有文章时返回 NotFound
+    pub async fn render_month_archive(&self, year: u32, month: u32) -> Result<String, AppError> {
+        let not_found = || AppError::NotFound(format!("/archives/{}/{:02}/", year, month));
+        let posts = self.published_posts().await?;
+        let years = count_by_month(&posts);
+        // 所有有文章的月份，按时间降序排列，用于前后月份的导航
+        let months: Vec<(u32, u32)> = years
+            .iter()
+            .flat_map(|y| y.months.iter().map(|m| (y.year, m.month)))
+            .collect();
+        let index = months
+            .iter()
+            .position(|&m| m == (year, month))
+            .ok_or_else(not_found)?;
+        let calendar = Calendar::new(year, month, &posts).ok_or_else(not_found)?;
+        let in_month = posts.iter().filter(|p| {
+            let date = p.front_matter.date;
+            date.year() as u32 == year && date.month() == month
+        });
+
+        let mut context = Context::new();
+        context.insert("year", &year);
+        context.insert("month", &month);
+        context.insert("years", &years);
+        context.insert("archives", &group_posts(in_month, true));
+        context.insert("calendar", &calendar);
+        let link = |(year, month): (u32, u32)| format!("/archives/{}/{:02}/", year, month);
+        if index > 0 {
+            context.insert("newer", &link(months[index - 1]));
+        }
+        if let Some(&older) = months.get(index + 1) {
+            context.insert("older", &link(older));
+        }
+        context.insert("site_title", &format!("{}年{}月 - ", year, month));
+        Ok(self.template_service.render("archive_period.html.tera", &context)?)
+    }
+
+    /// 随机选取一篇已发布文章
+    ///
+    /// # 返回
+    /// * `Result<Option<String>>` - 文章的URL，没有已发布文章时为 `None`
+    pub async fn random_post(&self) -> Result<Option<String>> {
+        let posts = self.published_posts().await?;
+        Ok(posts.choose(&mut rand::rng()).map(|p| p.url.clone()))
+    }
+
+    /// 所有已发布文章，按日期降序排列
+    async fn published_posts(&self) -> Result<Vec<Post>> {
+        Ok(self
+            .load_all_posts()
+            .await?
+            .into_iter()
+            .filter(|p| !p.front_matter.draft)
+            .collect())
+    }
+
+    /// 生成代码高亮样式表
+    ///
+    /// # 功能说明
+    /// - 根据站点配置的高亮主题生成CSS，供 `classes` 输出方式使用
+    ///
+    /// # 返回
+    /// * `Result<String>` - CSS样式表内容
+    pub fn highlight_css(&self) -> Result<String> {
+        highlight::theme_css(&self.site.markdown.highlight)
+    }
+
+    /// 获取文章关系图数据
+    ///
+    /// # 功能说明
+    /// - 包含所有已发布文章，以及文章间的链接与共享标签关系
+    ///
+    /// # 返回
+    /// * `Result<GraphData>` - 关系图数据
+    pub async fn graph(&self) -> Result<GraphData> {
+        // 确保文章已加载，关系图随文章一同构建
+        self.load_all_posts().await?;
+        Ok(LINK_GRAPH.read().await.data().clone())
+    }
+
+    /// 获取站内搜索索引
+    ///
+    /// # 返回
+    /// * `Result<Arc<SearchIndex>>` - 与当前文章缓存对应的搜索索引
+    pub async fn search_index(&self) -> Result<Arc<SearchIndex>> {
+        // 确保文章已加载，搜索索引随文章一同构建
+        self.load_all_posts().await?;
+        Ok(Arc::clone(&*SEARCH_INDEX.read().await))
+    }
+
+    /// 获取供静态镜像使用的搜索索引
+    ///
+    /// # 返回
+    /// * `Result<Arc<SearchExport>>` - 序列化后的索引JSON与对应的ETag
+    pub async fn search_export(&self) -> Result<Arc<SearchExport>> {
+        // 确保文章已加载，静态索引随文章一同构建
+        self.load_all_posts().await?;
+        Ok(Arc::clone(&*SEARCH_EXPORT.read().await))
+    }
+
+    /// 获取标题与标签的输入建议索引
+    ///
+    /// # 返回
+    /// * `Result<Arc<SuggestIndex>>` - 与当前文章缓存对应的建议索引
+    pub async fn suggest_index(&self) -> Result<Arc<SuggestIndex>> {
+        // 确保文章已加载，建议索引随文章一同构建
+        self.load_all_posts().await?;
+        Ok(Arc::clone(&*SUGGEST_INDEX.read().await))
+    }
+
+    /// 缩略图缓存目录，由 `/images/` 路由提供访问
+    pub fn image_cache_dir(&self) -> &str {
+        &self.site.markdown.images.cache_dir
+    }
+
     /// 获取单篇文章
     ///
     /// # 功能说明
@@ -279,53 +555,97 @@ impl PostService {
                 None
             };
 
-            let random_posts = self.get_random_post(url).await?;
+            let related_posts = self.get_related_posts(url, &published_posts).await;
+            let word_count = current_post.word_count(&self.site.markdown);
+            let wiki = Arc::clone(&*WIKI_INDEX.read().await);
+            let links = Arc::clone(&*LINK_POLICY.read().await);
+            let rendered = current_post.render(
+                &self.site.markdown,
+                self.template_service.as_ref(),
+                &wiki,
+                &links,
+            );
+
+            let slugs = term_slugs();
+            let breadcrumbs = self
+                .site
+                .taxonomies
+                .iter()
+                .filter(|taxonomy| taxonomy.hierarchical)
+                .map(|taxonomy| {
+                    let trails = current_post
+                        .front_matter
+                        .terms(&taxonomy.name)
+                        .into_iter()
+                        .map(|term| breadcrumbs(taxonomy, term, &slugs))
+                        .filter(|trail| !trail.is_empty())
+                        .collect();
+                    (taxonomy.name.clone(), trails)
+                })
+                .collect();
 
             // 构造SinglePost对象
             Ok(Some(SinglePost {
                 front_matter: current_post.front_matter.clone(),
                 url: current_post.url.clone(),
-                content: current_post.generate_html(),
-                summary: current_post.generate_description(100),
-                count: current_post.count_chinese_chars(),
-                read_time: current_post.read_time(READ_SPEED),
-                toc: current_post.generate_toc(),
+                content: rendered.html,
+                summary: current_post.generate_description(100, &self.site.markdown),
+                count: word_count.total(),
+                word_count,
+                read_time: word_count
+                    .read_time(self.site.read_speed_cjk, self.site.read_speed_latin),
+                toc: rendered.toc,
+                backlinks: LINK_GRAPH.read().await.backlinks(url),
                 prev,
                 next,
-                random_posts,
+                related_posts,
+                breadcrumbs,
+                on_this_day: on_this_day(
+                    &published_posts,
+                    current_post.front_matter.date.date_naive(),
+                ),
             }))
         } else {
             Ok(None)
         }
     }
 
-    /// 获取随机文章列表
+    /// 获取相关文章列表
     ///
     /// # 功能说明
-    /// - 从已加载的文章列表中随机选择5篇非草稿状态的文章
+    /// - 按加载时计算的相似度取前 `count` 篇相关文章
+    /// - 相关文章不足时，从其余已发布文章中随机补足
     /// # 参数
-    /// * `url` - 当前文章的URL，用于过滤
+    /// * `url` - 当前文章的URL
+    /// * `published_posts` - 所有已发布文章
     /// # 返回值
-    /// * `Result<Vec<Post>>` - 随机选择的5篇文章列表
-    async fn get_random_post(&self, url: &str) -> Result<Vec<Post>> {
-        // 获取所有文章，过滤当前url文章，返回随机5篇文章
-        let posts = self.load_all_posts().await?;
-        let mut rng = rand::rng();
-        let mut filtered_posts: Vec<Post> = posts
-            .into_iter()
-            .filter(|p| !p.front_matter.draft && p.url != url)
+    /// * `Vec<Post>` - 相关文章在前、随机文章在后的列表
+    async fn get_related_posts(&self, url: &str, published_posts: &[Post]) -> Vec<Post> {
+        let count = self.site.related.count;
+        let index = Arc::clone(&*RELATED_INDEX.read().await);
+        let related = index.related(url);
+        let mut posts: Vec<Post> = related
+            .iter()
+            .filter_map(|related| published_posts.iter().find(|p| &p.url == related))
+            .take(count)
+            .cloned()
             .collect();
-        // 随机打乱文章列表
-        filtered_posts.shuffle(&mut rng);
-        // 取前5篇文章
-        let random_posts: Vec<Post> = filtered_posts.into_iter().take(5).collect();
-        Ok(random_posts)
+        if posts.len() < count {
+            let mut rest: Vec<&Post> = published_posts
+                .iter()
+                .filter(|p| p.url != url && !related.contains(&p.url))
+                .collect();
+            rest.shuffle(&mut rand::rng());
+            posts.extend(rest.into_iter().take(count - posts.len()).cloned());
+        }
+        posts
     }
 
     /// 加载并解析单个Markdown文章文件
     ///
     /// # 参数
     /// * `path` - 文章文件路径
+    /// * `trusted` - 是否为站长撰写的文章，上传的文章为false
     ///
     /// # 返回
     /// * `Result<Post>` - 解析后的文章对象或错误
@@ -333,7 +653,7 @@ impl PostService {
     /// # 错误
     /// * 文件读取失败时返回错误
     /// * Front Matter解析失败时返回错误
-    async fn load_post<P: AsRef<Path>>(&self, path: P) -> Result<Post> {
+    async fn load_post<P: AsRef<Path>>(&self, path: P, trusted: bool) -> Result<Post> {
         // 读取文件内容
         let content = fs::read_to_string(&path).await?;
 
@@ -360,27 +680,233 @@ impl PostService {
             front_matter,
             content: body.to_string(),
             url,
+            trusted,
         })
     }
 
     /// 从文件系统加载所有文章
-    /// 扫描 post 目录下的所有 .md 文件，并按日期降序排序
+    /// 扫描 post 目录与上传目录下的所有 .md 文件，并按日期降序排序
     async fn load_posts_from_fs(&self) -> Result<Vec<Post>> {
         let mut posts = Vec::new();
-        // 异步读取 post 目录
-        let mut entries = fs::read_dir("post").await?;
+        self.load_posts_from_dir("post", true, &mut posts).await?;
+        // 上传目录不存在时视为没有上传的文章
+        if Path::new(UPLOAD_DIR).is_dir() {
+            self.load_posts_from_dir(UPLOAD_DIR, false, &mut posts).await?;
+        }
+        // 按发布日期降序排序
+        posts.sort_by_key(|p| std::cmp::Reverse(p.front_matter.date));
+        self.apply_term_aliases(&mut posts);
+        self.build_term_slugs(&posts);
+        let wiki = self.build_wiki_index(&posts).await;
+        self.build_link_policy().await;
+        self.build_term_meta(&wiki).await;
+        self.build_search_index(&posts).await;
+        self.build_related_index(&posts).await;
+        self.prepare_images(&posts, &wiki).await;
+        Ok(posts)
+    }
+
+    /// 加载目录中的 .md 文件（不含子目录）
+    ///
+    /// # 参数
+    /// * `dir` - 文章目录
+    /// * `trusted` - 目录中的文章是否为站长撰写
+    /// * `posts` - 加载结果追加到此列表
+    async fn load_posts_from_dir(
+        &self,
+        dir: &str,
+        trusted: bool,
+        posts: &mut Vec<Post>,
+    ) -> Result<()> {
+        // 异步读取文章目录
+        let mut entries = fs::read_dir(dir).await?;
 
         // 遍历目录中的所有条目
         while let Some(entry) = entries.next_entry().await? {
             // 只处理 .md 后缀的文件
             if entry.path().extension().and_then(|s| s.to_str()) == Some("md") {
-                let post = self.load_post(entry.path()).await?;
+                let post = self.load_post(entry.path(), trusted).await?;
+                // 检查短代码语法与模板是否存在，问题仅记录警告，不影响加载
+                let diagnostics = shortcode::diagnose(&post.content, |name| {
+                    self.template_service.has_shortcode(name)
+                });
+                for diagnostic in diagnostics {
+                    tracing::warn!("Post `{}`: {}", post.url, diagnostic);
+                }
                 posts.push(post);
             }
         }
-        // 按发布日期降序排序
-        posts.sort_by(|a, b| b.front_matter.date.cmp(&a.front_matter.date));
-        Ok(posts)
+        Ok(())
+    }
+
+    /// 重建词项别名表，并将文章中的别名与不同写法替换为规范词项
+    fn apply_term_aliases(&self, posts: &mut [Post]) {
+        let aliases = TermAliases::build(posts, &self.site.taxonomies);
+        aliases.apply(posts, &self.site.taxonomies);
+        let aliases = Arc::new(aliases);
+        match TERM_ALIASES.write() {
+            Ok(mut current) => *current = aliases,
+            Err(poisoned) => *poisoned.into_inner() = aliases,
+        }
+    }
+
+    /// 为所有分类法的词项重新分配URL别名
+    fn build_term_slugs(&self, posts: &[Post]) {
+        let slugs = Arc::new(TermSlugs::build(posts, &self.site.taxonomies));
+        match TERM_SLUGS.write() {
+            Ok(mut current) => *current = slugs,
+            Err(poisoned) => *poisoned.into_inner() = slugs,
+        }
+    }
+
+    /// 重新读取词项说明文件
+    ///
+    /// 正文与文章使用同一渲染管线（短代码、双链、代码高亮、HTML过滤等），
+    /// 因此需要在双链索引与外部链接策略重建之后执行。
+    async fn build_term_meta(&self, wiki: &Arc<WikiIndex>) {
+        let taxonomies = self.site.taxonomies.clone();
+        let markdown = self.site.markdown.clone();
+        let (slugs, aliases) = (term_slugs(), term_aliases());
+        let shortcodes = Arc::clone(&self.template_service);
+        let wiki = Arc::clone(wiki);
+        let links = Arc::clone(&*LINK_POLICY.read().await);
+        let built = tokio::task::spawn_blocking(move || {
+            let render = |body: &str| {
+                Post::render_trusted(body, &markdown, shortcodes.as_ref(), &wiki, &links).html
+            };
+            TermMetaIndex::load(Path::new(TERM_CONTENT_DIR), &taxonomies, &slugs, &aliases, render)
+        })
+        .await;
+        match built {
+            Ok(index) => *TERM_META.write().await = Arc::new(index),
+            Err(e) => tracing::warn!("Term metadata task failed: {}", e),
+        }
+    }
+
+    /// 重建双链索引、文章链接关系与输入建议索引，并检查文章中无法解析的双链
+    async fn build_wiki_index(&self, posts: &[Post]) -> Arc<WikiIndex> {
+        let published: Vec<Post> = posts
+            .iter()
+            .filter(|p| !p.front_matter.draft)
+            .cloned()
+            .collect();
+        // 遍历静态目录收集附件，放到阻塞线程中执行
+        let built =
+            tokio::task::spawn_blocking(move || WikiIndex::new(published, Path::new("static"))).await;
+        let wiki = match built {
+            Ok(wiki) => wiki,
+            Err(e) => {
+                tracing::warn!("Wiki index task failed: {}", e);
+                return Arc::clone(&*WIKI_INDEX.read().await);
+            }
+        };
+        for post in posts {
+            for diagnostic in obsidian::diagnose(&post.content, &wiki) {
+                tracing::warn!("Post `{}`: {}", post.url, diagnostic);
+            }
+        }
+        let graph = LinkGraph::build(&wiki, &self.site.url);
+        *SUGGEST_INDEX.write().await =
+            Arc::new(SuggestIndex::build(posts, &graph, &term_slugs()));
+        *LINK_GRAPH.write().await = Arc::new(graph);
+        let wiki = Arc::new(wiki);
+        *WIKI_INDEX.write().await = Arc::clone(&wiki);
+        wiki
+    }
+
+    /// 根据站点地址与友链重建外部链接处理策略
+    async fn build_link_policy(&self) {
+        let friends = FriendLinkService::load_friend_links();
+        let policy = LinkPolicy::new(
+            &self.site.markdown.links,
+            &self.site.url,
+            friends.iter().map(|friend| friend.url.as_str()),
+        );
+        *LINK_POLICY.write().await = Arc::new(policy);
+    }
+
+    /// 重建站内搜索索引与静态搜索索引
+    ///
+    /// 分词较耗时，在阻塞线程池中执行；失败时保留原有索引。
+    /// 配置了 `[search.export] output` 时，同时将静态索引写入该文件。
+    async fn build_search_index(&self, posts: &[Post]) {
+        let posts = posts.to_vec();
+        let markdown = self.site.markdown.clone();
+        let config = self.site.search.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let export = SearchExport::build(&posts, &markdown, &config.export);
+            (SearchIndex::build(&posts, &markdown, &config), export)
+        })
+        .await;
+        let export = match result {
+            Ok((index, export)) => {
+                *SEARCH_INDEX.write().await = Arc::new(index);
+                export
+            }
+            Err(e) => {
+                tracing::warn!("Search index task failed: {}", e);
+                return;
+            }
+        };
+
+        let output = Path::new(&self.site.search.export.output);
+        if !output.as_os_str().is_empty() {
+            if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+                if let Err(e) = fs::create_dir_all(parent).await {
+                    tracing::warn!("Failed to create {}: {}", parent.display(), e);
+                }
+            }
+            if let Err(e) = fs::write(output, &export.json).await {
+                tracing::warn!("Failed to write search index to {}: {}", output.display(), e);
+            }
+        }
+        *SEARCH_EXPORT.write().await = Arc::new(export);
+    }
+
+    /// 重建相关文章索引
+    ///
+    /// 文本相似度计算较耗时，在阻塞线程池中执行；失败时保留原有索引。
+    async fn build_related_index(&self, posts: &[Post]) {
+        let posts = posts.to_vec();
+        let markdown = self.site.markdown.clone();
+        let config = self.site.related.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            RelatedIndex::build(&posts, &markdown, &config)
+        })
+        .await;
+        match result {
+            Ok(index) => *RELATED_INDEX.write().await = Arc::new(index),
+            Err(e) => tracing::warn!("Related posts task failed: {}", e),
+        }
+    }
+
+    /// 为文章中引用的本地图片生成缩略图，并重建图片登记表
+    ///
+    /// 图片处理较耗时，在阻塞线程池中执行；单张图片失败仅记录警告。
+    /// 不再被任何文章引用的图片从登记表中移除。
+    async fn prepare_images(&self, posts: &[Post], wiki: &WikiIndex) {
+        let config = self.site.markdown.images.clone();
+        if !config.enabled {
+            return;
+        }
+        let mut sources: Vec<String> = posts
+            .iter()
+            .flat_map(|post| {
+                let mut sources = images::collect_sources(&post.content);
+                sources.extend(obsidian::embedded_images(&post.content, wiki));
+                sources
+            })
+            .collect();
+        sources.sort();
+        sources.dedup();
+
+        let result = tokio::task::spawn_blocking(move || {
+            images::rebuild(&sources, Path::new("static"), &config)
+        })
+        .await;
+        if let Err(e) = result {
+            tracing::warn!("Image preparation task failed: {}", e);
+        }
     }
 
     /// 获取分页的文章归档
@@ -390,16 +916,16 @@ impl PostService {
     /// * `per_page` - 每页显示的文章数量
     ///
     /// # 返回
-    /// * `Result<(Vec<Archive>, usize)>` - 返回一个元组：
+    /// * `Result<(Vec<Archive>, Page)>` - 返回一个元组：
     ///   - 第一个元素是按年份分组的文章归档列表
-    ///   - 第二个元素是总条数
+    ///   - 第二个元素是分页信息，超出范围的页码按最后一页处理
     /// # 错误处理
     /// * 如果文章加载失败，将返回错误
     async fn get_paginated_archives(
         &self,
         page: usize,
         per_page: usize,
-    ) -> Result<(Vec<Archive>, usize)> {
+    ) -> Result<(Vec<Archive>, Page)> {
         // 加载所有文章
         let posts = self
             .load_all_posts()
@@ -407,41 +933,14 @@ impl PostService {
             .into_iter()
             .filter(|p| !p.front_matter.draft);
 
-        // 使用count()获取过滤后的数量
+        // 使用count()获取过滤后的数量，超出范围的页码按最后一页处理
         let len = posts.clone().count();
+        let page = Page::clamped(len, page, per_page);
         // 获取当前页的文章
-        let start = (page - 1) * per_page;
-        let page_posts = posts.skip(start).take(per_page);
-
-        // 将文章按年份分组
-        let mut archives: Vec<Archive> = Vec::new();
-
-        // 遍历当前页的文章，构建归档结构
-        for post in page_posts {
-            let year = post.front_matter.date.year() as u32;
-            let date = post.front_matter.date.format("%m-%d").to_string();
-
-            let archive_post = ArchivePost {
-                date,
-                title: post.front_matter.title,
-                url: post.url,
-            };
-
-            // 尝试将文章添加到已存在的年份分组中
-            if let Some(last_archive) = archives.last_mut() {
-                if last_archive.year == year {
-                    last_archive.posts.push(archive_post);
-                    continue;
-                }
-            }
-
-            // 如果没有匹配的年份，创建新的归档分组
-            archives.push(Archive {
-                year,
-                posts: vec![archive_post],
-            });
-        }
+        let start = usize::from(page.current.max(1) - 1) * per_page;
+        let page_posts: Vec<Post> = posts.skip(start).take(per_page).collect();
 
-        Ok((archives, len))
+        // 将当前页的文章按年份分组
+        Ok((group_posts(&page_posts, false), page))
     }
 }
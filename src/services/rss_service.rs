@@ -1,11 +1,14 @@
 // RSS服务模块：负责生成网站的RSS订阅源
-use crate::models::{RssFeed, RssItem, Site};
-use crate::services::PostService;
+use crate::models::{Post, RssFeed, RssItem, Site, TaxonomyConfig, TermMeta};
+use crate::services::{post_service, PostService};
 use crate::utils::html::escape_html;
 use anyhow::Result;
 use chrono::{DateTime, FixedOffset};
 use std::sync::Arc;
 
+/// 未指定描述时订阅源的默认描述
+const DEFAULT_DESCRIPTION: &str = "Recent content on P.X.C";
+
 /// RSS服务模块
 ///
 /// # 功能说明
@@ -48,17 +51,7 @@ impl RssService {
         let posts = self.post_service.load_all_posts().await?;
 
         // 获取所有文章，转换为RSS项目格式
-        let items: Vec<RssItem> = posts
-            .iter()
-            .filter(|p| !p.front_matter.draft)
-            // .take(self.site.rss_count)
-            .map(|post| RssItem {
-                title: post.front_matter.title.clone(),
-                link: format!("{}/post/{}/", self.site.url, post.url),
-                pub_date: self.format_datetime(post.front_matter.date),
-                description: escape_html(post.generate_description(200).as_str()),
-            })
-            .collect();
+        let items = self.to_items(&posts);
 
         // 获取当前时间作为RSS最后更新时间
         // let last_build_date = self.format_datetime(Local::now().with_timezone(&Local).into());
@@ -71,9 +64,25 @@ impl RssService {
             last_build_date,
             site_url: self.site.url.clone(),
             site_title: self.site.title.clone(),
+            description: DEFAULT_DESCRIPTION.to_string(),
         })
     }
 
+    /// 将已发布文章转换为RSS条目
+    fn to_items(&self, posts: &[Post]) -> Vec<RssItem> {
+        posts
+            .iter()
+            .filter(|p| !p.front_matter.draft)
+            // .take(self.site.rss_count)
+            .map(|post| RssItem {
+                title: post.front_matter.title.clone(),
+                link: format!("{}/post/{}/", self.site.url, post.url),
+                pub_date: self.format_datetime(post.front_matter.date),
+                description: escape_html(post.generate_description(200, &self.site.markdown).as_str()),
+            })
+            .collect()
+    }
+
     /// 格式化日期时间为RSS规范格式
     ///
     /// # 参数
@@ -95,19 +104,71 @@ impl RssService {
     /// * `Result<String>` - RSS XML字符串或错误
     pub async fn generate_feed_xml(&self) -> Result<String> {
         let feed = self.generate_feed().await?;
+        let self_link = format!("{}/index.xml", feed.site_url);
+        Ok(Self::render_feed_xml(&feed, &self_link))
+    }
 
+    /// 生成分类法词项的RSS XML内容，如 `/tags/{tag}/index.xml`
+    ///
+    /// # 参数
+    /// * `taxonomy` - 分类法配置
+    /// * `term` - 词项名称
+    /// * `posts` - 词项下的文章，按日期降序排列
+    /// * `meta` - 词项说明页面，提供订阅源的标题与描述
+    ///
+    /// # 返回
+    /// * `String` - RSS XML字符串
+    pub fn generate_term_feed_xml(
+        &self,
+        taxonomy: &TaxonomyConfig,
+        term: &str,
+        posts: &[Post],
+        meta: Option<&TermMeta>,
+    ) -> String {
+        let link = format!(
+            "{}{}",
+            self.site.url,
+            post_service::term_slugs().url(&taxonomy.name, term)
+        );
+        let last_build_date = posts
+            .first()
+            .map(|post| post.front_matter.date)
+            .unwrap_or_else(|| chrono::Local::now().fixed_offset());
+        let feed = RssFeed {
+            items: self.to_items(posts),
+            last_build_date: self.format_datetime(last_build_date),
+            site_url: link,
+            site_title: escape_html(&format!(
+                "{} - {}",
+                meta.and_then(|m| m.title.as_deref()).unwrap_or(term),
+                self.site.title
+            )),
+            description: meta
+                .and_then(|m| m.description.as_deref())
+                .map(escape_html)
+                .unwrap_or_else(|| DEFAULT_DESCRIPTION.to_string()),
+        };
+        Self::render_feed_xml(&feed, &format!("{}index.xml", feed.site_url))
+    }
+
+    /// 将RSS订阅源数据渲染为符合RSS 2.0规范的XML
+    ///
+    /// # 参数
+    /// * `feed` - 订阅源数据
+    /// * `self_link` - 订阅源自身的地址
+    fn render_feed_xml(feed: &RssFeed, self_link: &str) -> String {
         let xml = format!(
             r#"<?xml version="1.0" encoding="utf-8" standalone="yes"?>
 <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
   <channel>
     <title>{}</title>
     <link>{}</link>
-    <description>Recent content on P.X.C</description>
+    <description>{}</description>
     <generator>P.X.C Blog Engine</generator>
     <language>zh-cn</language>
     <lastBuildDate>{}</lastBuildDate>
-    <atom:link href="{}/index.xml" rel="self" type="application/rss+xml" />"#,
-            feed.site_title, feed.site_url, feed.last_build_date, feed.site_url
+    <atom:link href="{}" rel="self" type="application/rss+xml" />"#,
+            feed.site_title, feed.site_url, feed.description, feed.last_build_date, self_link
         );
 
         let items: String = feed
@@ -127,6 +188,6 @@ impl RssService {
             })
             .collect();
 
-        Ok(format!("{}\n{}\n  </channel>\n</rss>", xml, items))
+        format!("{}\n{}\n  </channel>\n</rss>", xml, items)
     }
 }
@@ -0,0 +1,83 @@
+use super::post_service::PostService;
+use super::TemplateService;
+use crate::models::{Page, Site, Suggestion};
+use anyhow::Result;
+use std::sync::Arc;
+use tera::Context;
+
+/// 站内搜索服务
+///
+/// # 功能说明
+/// - 使用文章服务在加载时构建的倒排索引检索文章
+/// - 渲染带分页的搜索结果页面
+/// - 按汉字、全拼或拼音首字母提供标题与标签的输入建议
+///
+/// # 字段说明
+/// * `template_service` - 模板服务实例，用于渲染搜索页面
+/// * `post_service` - 文章服务实例，用于获取搜索索引
+/// * `site` - 站点配置，包含搜索配置
+#[derive(Clone, Debug)]
+pub struct SearchService {
+    template_service: Arc<TemplateService>,
+    post_service: Arc<PostService>,
+    site: Site,
+}
+
+impl SearchService {
+    /// 创建搜索服务实例
+    ///
+    /// # 参数
+    /// * `template_service` - 模板服务Arc指针
+    /// * `post_service` - 文章服务Arc指针
+    /// * `site` - 站点配置
+    pub fn new(
+        template_service: Arc<TemplateService>,
+        post_service: Arc<PostService>,
+        site: Site,
+    ) -> Self {
+        Self {
+            template_service,
+            post_service,
+            site,
+        }
+    }
+
+    /// 渲染搜索结果页面
+    ///
+    /// # 参数
+    /// * `query` - 查询语句，为空时只显示搜索框
+    /// * `page` - 页码，从1开始
+    ///
+    /// # 返回
+    /// * `Result<String>` - 渲染后的HTML或错误
+    pub async fn render_search(&self, query: &str, page: usize) -> Result<String> {
+        let mut context = Context::new();
+        let query = query.trim();
+        let per_page = self.site.search.per_page.max(1);
+        let hits = self.post_service.search_index().await?.search(query);
+
+        let page = Page::clamped(hits.len(), page, per_page);
+        let results: Vec<_> = hits
+            .iter()
+            .skip((page.current.max(1) as usize - 1) * per_page)
+            .take(per_page)
+            .collect();
+        context.insert("query", query);
+        context.insert("total", &hits.len());
+        context.insert("results", &results);
+        context.insert("page", &page);
+        self.template_service.render("search.html.tera", &context)
+    }
+
+    /// 查找标题与标签的输入建议
+    ///
+    /// # 参数
+    /// * `query` - 用户输入，可以是汉字、全拼或拼音首字母
+    ///
+    /// # 返回
+    /// * `Result<Vec<Suggestion>>` - 按匹配方式与热度排序的建议，最多 `suggest_limit` 条
+    pub async fn suggest(&self, query: &str) -> Result<Vec<Suggestion>> {
+        let index = self.post_service.suggest_index().await?;
+        Ok(index.suggest(query, self.site.search.suggest_limit))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::models::{Site, Sitemap, SitemapUrl};
-use crate::services::{PostService, TagService, CategoryService};
+use crate::services::{PostService, TaxonomyService};
 use chrono::{DateTime, FixedOffset, Local};
 use std::sync::Arc;
 use anyhow::Result;
@@ -13,13 +13,11 @@ use anyhow::Result;
 /// 
 /// # 字段说明
 /// * `post_service` - 文章服务实例
-/// * `tag_service` - 标签服务实例
-/// * `category_service` - 分类服务实例
+/// * `taxonomy_service` - 分类法服务实例
 /// * `site` - 网站配置信息
 pub struct SitemapService {
     post_service: Arc<PostService>,
-    tag_service: Arc<TagService>,
-    category_service: Arc<CategoryService>,
+    taxonomy_service: Arc<TaxonomyService>,
     site: Site,
 }
 
@@ -28,22 +26,19 @@ impl SitemapService {
     /// 
     /// # 参数
     /// * `post_service` - 文章服务Arc指针
-    /// * `tag_service` - 标签服务Arc指针
-    /// * `category_service` - 分类服务Arc指针
+    /// * `taxonomy_service` - 分类法服务Arc指针
     /// * `site` - 站点配置信息
     /// 
     /// # 返回
     /// * `Self` - 站点地图服务实例
     pub fn new(
         post_service: Arc<PostService>,
-        tag_service: Arc<TagService>,
-        category_service: Arc<CategoryService>,
+        taxonomy_service: Arc<TaxonomyService>,
         site: Site,
     ) -> Self {
         Self {
             post_service,
-            tag_service,
-            category_service,
+            taxonomy_service,
             site,
         }
     }
@@ -92,24 +87,25 @@ impl SitemapService {
             });
         }
 
-        // Add tag pages
-        let tags = self.tag_service.get_all_tags().await;
-        for tag in tags {
-            urls.push(SitemapUrl {
-                loc: format!("{}/tags/{}/", self.site.url, tag.name),
-                lastmod: current_time.clone(),
-                priority: self.site.priority.clone(),
-            });
-        }
-
-        // Add category pages
-        let categories = self.category_service.get_all_categories().await;
-        for category in categories {
-            urls.push(SitemapUrl {
-                loc: format!("{}/categories/{}/", self.site.url, category.name),
-                lastmod: current_time.clone(),
-                priority: self.site.priority.clone(),
-            });
+        // Add taxonomy term pages
+        for taxonomy in self.taxonomy_service.taxonomies() {
+            if !taxonomy.sitemap {
+                continue;
+            }
+            for term in self.taxonomy_service.get_terms(taxonomy).await {
+                // 说明页面可以排除词项或指定优先级
+                let meta = self.taxonomy_service.get_term_meta(taxonomy, &term.name).await;
+                if meta.as_ref().is_some_and(|m| m.noindex) {
+                    continue;
+                }
+                urls.push(SitemapUrl {
+                    loc: format!("{}/{}/{}/", self.site.url, taxonomy.name, term.slug),
+                    lastmod: current_time.clone(),
+                    priority: meta
+                        .and_then(|m| m.priority)
+                        .unwrap_or_else(|| self.site.priority.clone()),
+                });
+            }
         }
 
         let sitemap = Sitemap { urls };
@@ -129,7 +125,9 @@ impl SitemapService {
         for url in &sitemap.urls {
             xml.push_str(&format!(
                 r#"<url><loc>{}</loc><lastmod>{}</lastmod><priority>{}</priority></url>"#,
-                url.loc, url.lastmod, url.priority
+                url.escaped_loc(),
+                url.lastmod,
+                url.priority
             ));
         }
 
@@ -0,0 +1,106 @@
+use super::post_service::{term_slugs, PostService};
+use super::TemplateService;
+use crate::error::AppError;
+use crate::models::archive::count_by_month;
+use crate::models::{Site, SiteStats, YearReview};
+use anyhow::Result;
+use std::sync::Arc;
+use tera::Context;
+
+/// 写作统计服务
+///
+/// # 功能说明
+/// - 从文章快照统计每年、每月的文章数与字数、常用词项、最长文章与写作热力图
+/// - 渲染统计页面 `/stats/` 与年度回顾页面 `/stats/{year}/`
+///
+/// # 字段说明
+/// * `template_service` - 模板服务实例，用于渲染统计页面
+/// * `post_service` - 文章服务实例，用于获取文章
+/// * `site` - 站点配置，包含 Markdown 与分类法配置
+#[derive(Clone, Debug)]
+pub struct StatsService {
+    template_service: Arc<TemplateService>,
+    post_service: Arc<PostService>,
+    site: Site,
+}
+
+impl StatsService {
+    /// 创建统计服务实例
+    ///
+    /// # 参数
+    /// * `template_service` - 模板服务Arc指针
+    /// * `post_service` - 文章服务Arc指针
+    /// * `site` - 站点配置
+    pub fn new(
+        template_service: Arc<TemplateService>,
+        post_service: Arc<PostService>,
+        site: Site,
+    ) -> Self {
+        Self {
+            template_service,
+            post_service,
+            site,
+        }
+    }
+
+    /// 统计全站文章，热力图截至站点时区的今天
+    ///
+    /// # 返回
+    /// * `Result<SiteStats>` - 全站写作统计
+    pub async fn stats(&self) -> Result<SiteStats> {
+        let posts = self.post_service.load_all_posts().await?;
+        let markdown = self.site.markdown.clone();
+        let taxonomies = self.site.taxonomies.clone();
+        let slugs = term_slugs();
+        let today = self.site.today();
+        Ok(tokio::task::spawn_blocking(move || {
+            SiteStats::build(&posts, &markdown, &taxonomies, &slugs, today)
+        })
+        .await?)
+    }
+
+    /// 渲染统计页面 `/stats/`
+    ///
+    /// # 返回
+    /// * `Result<String>` - 渲染后的HTML或错误
+    pub async fn render_stats(&self) -> Result<String> {
+        let mut context = Context::new();
+        context.insert("stats", &self.stats().await?);
+        context.insert("taxonomies", &self.site.taxonomies);
+        self.template_service.render("stats.html.tera", &context)
+    }
+
+    /// 渲染年度回顾页面 `/stats/{year}/`
+    ///
+    /// # 参数
+    /// * `year` - 年份
+    ///
+    /// # 返回
+    /// * `Result<String, AppError>` - 渲染后的HTML，该年没有文章时返回 NotFound
+    pub async fn render_year_review(&self, year: u32) -> Result<String, AppError> {
+        let posts = self.post_service.load_all_posts().await?;
+        // 相邻的有文章的年份，用于年度之间的导航
+        let years: Vec<u32> = count_by_month(posts.iter().filter(|p| !p.front_matter.draft))
+            .iter()
+            .map(|y| y.year)
+            .collect();
+        let markdown = self.site.markdown.clone();
+        let taxonomies = self.site.taxonomies.clone();
+        let slugs = term_slugs();
+        let review = tokio::task::spawn_blocking(move || {
+            YearReview::build(year, &posts, &markdown, &taxonomies, &slugs)
+        })
+        .await
+        .map_err(anyhow::Error::from)?
+        .ok_or_else(|| AppError::NotFound(format!("/stats/{}/", year)))?;
+
+        let index = years.iter().position(|&y| y == year);
+        let mut context = Context::new();
+        context.insert("review", &review);
+        context.insert("newer", &index.and_then(|i| i.checked_sub(1)).map(|i| years[i]));
+        context.insert("older", &index.and_then(|i| years.get(i + 1)));
+        context.insert("taxonomies", &self.site.taxonomies);
+        context.insert("site_title", &format!("{}年度回顾 - ", year));
+        Ok(self.template_service.render("stats_year.html.tera", &context)?)
+    }
+}
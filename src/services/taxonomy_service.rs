@@ -0,0 +1,218 @@
+use super::post_service::{self, PostService};
+use super::TemplateService;
+use crate::error::AppError;
+use crate::models::taxonomy::{
+    breadcrumbs, count_terms, group_by_letter, sort_by_pinyin, term_tree, TermOrder,
+};
+use crate::models::query::PostQuery;
+use crate::models::{Page, Post, Site, TaxonomyConfig, TermCount, TermMeta};
+use anyhow::Result;
+use std::sync::Arc;
+use tera::Context;
+
+/// 请求中词项路径的解析结果
+#[derive(Debug, PartialEq, Eq)]
+pub enum TermPath {
+    /// 路径为词项的别名，值为词项名
+    Term(String),
+    /// 路径为词项的原始名称（如中文），值为应跳转到的别名
+    Moved(String),
+}
+
+/// 分类法服务
+///
+/// # 功能说明
+/// - 标签、分类以及配置中声明的其他分类法共用同一套实现
+/// - 统计词项的文章数，渲染词项列表与词项文章列表（分页）
+/// - 层级分类法的词项列表以树形展示，词项页面带面包屑导航
+/// - 词项页面显示 `_index.md` 说明页面的标题、简介、封面与正文
+/// - 词项地址使用拼音别名，原始名称及词项别名的地址跳转到规范词项的别名地址
+/// - 为订阅源与站点地图提供词项数据
+///
+/// # 字段说明
+/// * `template_service` - 模板服务实例，按分类法配置选择模板渲染页面
+/// * `post_service` - 文章服务实例，用于获取文章数据
+/// * `site` - 站点配置，包含分类法配置
+#[derive(Clone, Debug)]
+pub struct TaxonomyService {
+    template_service: Arc<TemplateService>,
+    post_service: Arc<PostService>,
+    site: Site,
+}
+
+impl TaxonomyService {
+    /// 创建分类法服务实例
+    ///
+    /// # 参数
+    /// * `template_service` - 模板服务Arc指针
+    /// * `post_service` - 文章服务Arc指针
+    /// * `site` - 站点配置
+    pub fn new(
+        template_service: Arc<TemplateService>,
+        post_service: Arc<PostService>,
+        site: Site,
+    ) -> Self {
+        Self {
+            template_service,
+            post_service,
+            site,
+        }
+    }
+
+    /// 所有已配置的分类法
+    pub fn taxonomies(&self) -> &[TaxonomyConfig] {
+        &self.site.taxonomies
+    }
+
+    /// 查找分类法配置
+    ///
+    /// # 返回
+    /// * `Result<&TaxonomyConfig, AppError>` - 未配置该分类法时返回 NotFound
+    pub fn taxonomy(&self, name: &str) -> Result<&TaxonomyConfig, AppError> {
+        self.site
+            .taxonomies
+            .iter()
+            .find(|taxonomy| taxonomy.name == name)
+            .ok_or_else(|| AppError::NotFound(format!("/{}/", name)))
+    }
+
+    /// 获取分类法下所有词项及其文章数
+    ///
+    /// # 返回
+    /// * `Vec<TermCount>` - 按文章数降序、名称升序排列的词项，加载失败时为空
+    pub async fn get_terms(&self, taxonomy: &TaxonomyConfig) -> Vec<TermCount> {
+        match self.post_service.load_all_posts().await {
+            Ok(posts) => count_terms(&posts, taxonomy, &post_service::term_slugs()),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 解析请求路径中的词项
+    ///
+    /// # 参数
+    /// * `taxonomy` - 分类法配置
+    /// * `path` - 路径中的词项部分，可以是别名或原始名称
+    ///
+    /// # 返回
+    /// * `Result<TermPath, AppError>` - 词项名或应跳转到的别名，词项不存在时返回 NotFound
+    pub async fn resolve_term(
+        &self,
+        taxonomy: &TaxonomyConfig,
+        path: &str,
+    ) -> Result<TermPath, AppError> {
+        // 确保文章已加载，别名随文章一同生成
+        self.post_service.load_all_posts().await?;
+        let slugs = post_service::term_slugs();
+        if let Some(term) = slugs.term(&taxonomy.name, path) {
+            return Ok(TermPath::Term(term.to_string()));
+        }
+        // 原始名称，或已合并到规范词项的别名与不同写法
+        let term = post_service::term_aliases()
+            .canonical(&taxonomy.name, path)
+            .map(str::to_string)
+            .unwrap_or_else(|| path.to_string());
+        match slugs.slug(&taxonomy.name, &term) {
+            Some(slug) => Ok(TermPath::Moved(slug.to_string())),
+            None => Err(AppError::NotFound(format!("/{}/{}/", taxonomy.name, path))),
+        }
+    }
+
+    /// 获取包含指定词项的所有已发布文章
+    ///
+    /// # 参数
+    /// * `taxonomy` - 分类法配置
+    /// * `term` - 词项名称
+    pub async fn get_posts_by_term(&self, taxonomy: &TaxonomyConfig, term: &str) -> Vec<Post> {
+        match self.post_service.query(&PostQuery::new().term(&taxonomy.name, term)).await {
+            Ok(result) => result.posts,
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 获取词项的说明页面（`_index.md`），没有说明文件时返回 `None`
+    ///
+    /// # 参数
+    /// * `taxonomy` - 分类法配置
+    /// * `term` - 词项名称
+    pub async fn get_term_meta(&self, taxonomy: &TaxonomyConfig, term: &str) -> Option<TermMeta> {
+        post_service::term_meta().await.get(&taxonomy.name, term).cloned()
+    }
+
+    /// 渲染分类法的词项列表页面
+    ///
+    /// # 参数
+    /// * `name` - 分类法名称
+    /// * `order` - 词项排序方式
+    ///
+    /// # 返回
+    /// * `Result<String, AppError>` - 渲染后的HTML，未配置该分类法时返回 NotFound
+    ///
+    /// 模板中另有 `{分类法}_by_letter`（如 `tags_by_letter`），为按拼音首字母分组的词项。
+    pub async fn render_terms(&self, name: &str, order: TermOrder) -> Result<String, AppError> {
+        let taxonomy = self.taxonomy(name)?;
+        let mut terms = self.get_terms(taxonomy).await;
+        let mut context = Context::new();
+        context.insert(
+            format!("{}_by_letter", taxonomy.name),
+            &group_by_letter(&terms),
+        );
+        if order == TermOrder::Pinyin {
+            sort_by_pinyin(&mut terms);
+        }
+        context.insert("taxonomy", taxonomy);
+        context.insert("sort", &order);
+        context.insert("terms", &terms);
+        context.insert("count", &terms.len());
+        if taxonomy.hierarchical {
+            context.insert("tree", &term_tree(&terms));
+        }
+        Ok(self.template_service.render(&taxonomy.template, &context)?)
+    }
+
+    /// 渲染词项下的文章列表页面
+    ///
+    /// # 参数
+    /// * `name` - 分类法名称
+    /// * `term` - 词项名称（已由 [`Self::resolve_term`] 解析）
+    /// * `page` - 页码，从1开始
+    ///
+    /// # 返回
+    /// * `Result<String, AppError>` - 渲染后的HTML，未配置该分类法时返回 NotFound
+    pub async fn render_term_posts(
+        &self,
+        name: &str,
+        term: &str,
+        page: usize,
+    ) -> Result<String, AppError> {
+        let taxonomy = self.taxonomy(name)?;
+        let per_page = taxonomy.per_page.max(1);
+        let query = PostQuery::new().term(&taxonomy.name, term).page(page.max(1), per_page);
+        let result = self.post_service.query(&query).await?;
+        let page = Page::clamped(result.total, result.current, per_page);
+        let datas = result.posts;
+
+        let mut context = Context::new();
+        context.insert("taxonomy", taxonomy);
+        let slugs = post_service::term_slugs();
+        context.insert("term", term);
+        context.insert("slug", &slugs.path(&taxonomy.name, term));
+        context.insert("breadcrumbs", &breadcrumbs(taxonomy, term, &slugs));
+        // 词项说明页面：标题、简介、封面与正文，简介与关键词同时用于页面 meta 标签
+        if let Some(meta) = self.get_term_meta(taxonomy, term).await {
+            context.insert("meta", &meta);
+            if let Some(description) = &meta.description {
+                context.insert("description", description);
+            }
+            if let Some(keywords) = &meta.keywords {
+                context.insert("keywords", keywords);
+            }
+            context.insert("noindex", &meta.noindex);
+        }
+        context.insert("posts", &datas);
+        context.insert("page", &page);
+        if page.current > 1 {
+            context.insert("site_title", &format!("第{}页 - ", page.current));
+        }
+        Ok(self.template_service.render(&taxonomy.term_template, &context)?)
+    }
+}
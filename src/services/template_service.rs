@@ -1,7 +1,14 @@
 use std::sync::Arc;
 use tera::{Tera, Value, try_get_value};
 use anyhow::Result;
-use crate::{models::Site, utils::date};
+use crate::{
+    models::Site,
+    services::post_service,
+    utils::{
+        date,
+        shortcode::{Shortcode, ShortcodeRenderer},
+    },
+};
 
 /// 模板渲染服务
 /// 
@@ -39,6 +46,7 @@ impl TemplateService {
         let mut tera = Tera::new("templates/**/*")?;
         tera.register_filter("format_date", format_date_filter);
         tera.register_filter("nl2p", nl2p_filter);
+        tera.register_filter("term_url", term_url_filter);
         // 从环境变量加载站点配置
         let site = Site::from_env();
         
@@ -62,6 +70,40 @@ impl TemplateService {
         context.insert("site", &self.site);
         Ok(self.tera.render(template_name, &context)?)
     }
+
+    /// 判断短代码模板是否存在
+    ///
+    /// # 参数
+    /// * `name` - 短代码名称
+    ///
+    /// # 返回
+    /// * `bool` - `templates/shortcodes/<name>.html.tera` 存在时返回true
+    pub fn has_shortcode(&self, name: &str) -> bool {
+        let template_name = shortcode_template(name);
+        self.tera
+            .get_template_names()
+            .any(|name| name == template_name)
+    }
+}
+
+impl ShortcodeRenderer for TemplateService {
+    /// 使用 `templates/shortcodes/` 下的同名模板渲染短代码
+    ///
+    /// 模板上下文中可使用 `args`（命名参数）、`positional`（位置参数）、
+    /// `inner`（成对短代码之间的内容）与 `site`。
+    fn render_shortcode(&self, shortcode: &Shortcode) -> Result<String> {
+        let mut context = tera::Context::new();
+        context.insert("name", &shortcode.name);
+        context.insert("args", &shortcode.args);
+        context.insert("positional", &shortcode.positional);
+        context.insert("inner", &shortcode.inner);
+        self.render(&shortcode_template(&shortcode.name), &context)
+    }
+}
+
+/// 短代码对应的模板名称
+fn shortcode_template(name: &str) -> String {
+    format!("shortcodes/{}.html.tera", name)
 }
 
 /// 日期格式化过滤器
@@ -94,4 +136,21 @@ fn format_date_filter(value: &Value, args: &std::collections::HashMap<String, Va
 fn nl2p_filter(value: &Value, _: &std::collections::HashMap<String, Value>) -> tera::Result<Value> {
     let text = try_get_value!("nl2p", "value", String, value);
     Ok(Value::String(text.replace('\n', "</p><p>")))
-}
\ No newline at end of file
+}
+
+/// 词项地址过滤器，如 `{{ tag | term_url(taxonomy="tags") }}` 输出 `/tags/lan-an-shu-xia/`
+///
+/// # 参数
+/// * `value` - 词项名称
+/// * `args` - 过滤器参数，`taxonomy` 指定分类法名称
+///
+/// # 返回
+/// * `tera::Result<Value>` - 使用拼音别名的词项页面地址
+fn term_url_filter(value: &Value, args: &std::collections::HashMap<String, Value>) -> tera::Result<Value> {
+    let term = try_get_value!("term_url", "value", String, value);
+    let taxonomy = args
+        .get("taxonomy")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("Filter `term_url` expected an arg called `taxonomy`"))?;
+    Ok(Value::String(post_service::term_slugs().url(taxonomy, &term)))
+}
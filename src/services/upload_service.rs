@@ -5,6 +5,12 @@ use axum::extract::Multipart;
 use std::path::Path;
 use tokio::fs;
 
+/// 上传文章的保存目录
+///
+/// 该目录中的文章视为不可信内容，渲染时使用严格的HTML过滤策略；
+/// 审核后移动到 `post/` 目录即视为站长撰写的文章。
+pub const UPLOAD_DIR: &str = "post/uploads";
+
 /// 文件上传服务
 ///
 /// 该服务用于接收并处理客户端上传的文件，
@@ -26,7 +32,7 @@ impl UploadService {
 
     /// 处理文件上传请求
     ///
-    /// 该方法从 Multipart 表单中提取文件，并使用文件的原始文件名进行保存。
+    /// 该方法从 Multipart 表单中提取文件，并使用文件的原始文件名保存到 [`UPLOAD_DIR`]。
     ///
     /// # 参数
     /// - `multipart` - 上传的 Multipart 数据结构
@@ -36,7 +42,8 @@ impl UploadService {
     ///
     /// # 错误
     /// - 当上传的字段缺失文件名时，返回错误。
-    /// - 当文件已存在时，返回文件已存在错误。
+    /// - 当文件名无效（如包含路径）时，返回错误。
+    /// - 当上传目录或 `post/` 中已存在同名文件时，返回文件已存在错误。
     pub async fn upload(&self, mut multipart: Multipart) -> Result<String> {
         // 保存上传的文件数据、原始文件名和自定义保存文件名
         let mut file_bytes = None;
@@ -76,15 +83,24 @@ impl UploadService {
             Some(ref name) if !name.trim().is_empty() => name.clone(),
             _ => orig_name,
         };
+        // 只取文件名部分，防止通过 `../` 写入其他目录
+        let final_name = Path::new(&final_name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.starts_with('.'))
+            .ok_or_else(|| anyhow::anyhow!("Invalid file name `{}`", final_name))?
+            .to_string();
 
         // 构造保存文件的本地路径
-        let file_path = format!("post/{}", final_name);
-        // 检查文件是否已存在，防止覆盖
-        if Path::new(&file_path).exists() || fs::metadata(&file_path).await.is_ok() {
+        let file_path = format!("{}/{}", UPLOAD_DIR, final_name);
+        // 检查文件是否已存在，防止覆盖；同名文章的URL相同，也不允许与 post/ 中的文章重名
+        let trusted_path = format!("post/{}", final_name);
+        if Path::new(&file_path).exists() || Path::new(&trusted_path).exists() {
             return Err(anyhow::anyhow!("File `{}` already exists", final_name));
         }
         let length = data.len();
         // 将文件数据写入本地文件系统
+        fs::create_dir_all(UPLOAD_DIR).await?;
         fs::write(&file_path, data).await?;
         Ok(format!("File `{}` saved, {} bytes", final_name, length))
     }
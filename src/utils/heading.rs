@@ -0,0 +1,171 @@
+use crate::utils::html::escape_html;
+use crate::utils::pinyin;
+use pulldown_cmark::{CowStr, Event, Tag, TagEnd};
+use std::collections::HashSet;
+
+/// 文章中的一个标题
+///
+/// # 字段说明
+/// * `level` - 标题层级（1表示h1）
+/// * `id` - 标题锚点ID，同一篇文章内唯一
+/// * `title` - 去除行内标记后的纯文本标题
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    pub level: u8,
+    pub id: String,
+    pub title: String,
+}
+
+/// 处理Markdown事件流中的标题
+///
+/// 在一次遍历中为每个标题生成唯一ID，并收集标题信息用于生成目录。
+/// 标题内的行内标记（代码、强调、链接等）原样保留，由HTML渲染器输出。
+///
+/// ID生成规则：
+/// - 标题已通过 `{#id}` 指定ID时使用指定值
+/// - 否则使用 [`pinyin::slugify`] 生成：拉丁文字按单词、汉字按拼音
+/// - 与前文重复时追加 `-1`、`-2` 等后缀
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流
+/// * `anchors` - 是否在标题末尾追加指向自身的锚点链接
+///
+/// # 返回
+/// * `(Vec<Event>, Vec<Heading>)` - 处理后的事件列表与文章中的所有标题
+pub fn process_headings<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    anchors: bool,
+) -> (Vec<Event<'a>>, Vec<Heading>) {
+    let mut output = Vec::new();
+    let mut headings = Vec::new();
+    let mut used_ids = HashSet::new();
+
+    // 当前正在收集的标题：开始标签与内部事件
+    let mut current: Option<(Tag<'a>, Vec<Event<'a>>)> = None;
+
+    for event in events {
+        match (event, current.as_mut()) {
+            (Event::Start(tag @ Tag::Heading { .. }), None) => {
+                current = Some((tag, Vec::new()));
+            }
+            (Event::End(TagEnd::Heading(level)), Some(_)) => {
+                let Some((Tag::Heading { id, classes, attrs, .. }, inner)) = current.take()
+                else {
+                    unreachable!("only heading tags are collected");
+                };
+                let title = plain_text(&inner);
+                let base = match id {
+                    Some(id) => id.to_string(),
+                    None => pinyin::slugify(&title),
+                };
+                let id = unique_id(&mut used_ids, base);
+
+                output.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(CowStr::from(id.clone())),
+                    classes,
+                    attrs,
+                }));
+                output.extend(inner);
+                if anchors {
+                    output.push(Event::InlineHtml(CowStr::from(format!(
+                        r##"<a class="heading-anchor" href="#{}" aria-hidden="true">#</a>"##,
+                        escape_html(&id)
+                    ))));
+                }
+                output.push(Event::End(TagEnd::Heading(level)));
+
+                headings.push(Heading {
+                    level: level as u8,
+                    id,
+                    title,
+                });
+            }
+            (event, Some((_, inner))) => inner.push(event),
+            (event, None) => output.push(event),
+        }
+    }
+
+    (output, headings)
+}
+
+/// 提取标题内的纯文本（文本与行内代码）
+fn plain_text(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 生成文章内唯一的ID，重复时追加数字后缀
+fn unique_id(used_ids: &mut HashSet<String>, base: String) -> String {
+    let base = if base.is_empty() {
+        "section".to_string()
+    } else {
+        base
+    };
+    let mut id = base.clone();
+    let mut suffix = 1;
+    while used_ids.contains(&id) {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    used_ids.insert(id.clone());
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{html, Options, Parser};
+
+    fn render(content: &str, anchors: bool) -> (String, Vec<Heading>) {
+        let parser = Parser::new_ext(content, Options::ENABLE_HEADING_ATTRIBUTES);
+        let (events, headings) = process_headings(parser, anchors);
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        (output, headings)
+    }
+
+    #[test]
+    fn test_inline_markup_preserved() {
+        let (html, headings) = render("## 使用 `tokio` 的 *异步* [运行时](https://tokio.rs)", false);
+        assert_eq!(
+            html,
+            "<h2 id=\"shi-yong-tokio-de-yi-bu-yun-xing-shi\">使用 <code>tokio</code> 的 <em>异步</em> <a href=\"https://tokio.rs\">运行时</a></h2>\n"
+        );
+        assert_eq!(headings[0].title, "使用 tokio 的 异步 运行时");
+    }
+
+    #[test]
+    fn test_latin_slug_is_word_level() {
+        let (html, _) = render("# Hello World", false);
+        assert!(html.contains(r#"<h1 id="hello-world">"#));
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let (_, headings) = render("## 总结\n\n## 总结\n\n## 总结 {#zong-jie-1}\n", false);
+        let ids: Vec<_> = headings.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, vec!["zong-jie", "zong-jie-1", "zong-jie-1-1"]);
+    }
+
+    #[test]
+    fn test_explicit_id_and_classes() {
+        let (html, headings) = render("## 标题 {#custom .lead}", false);
+        assert_eq!(html, "<h2 id=\"custom\" class=\"lead\">标题</h2>\n");
+        assert_eq!(headings[0].id, "custom");
+    }
+
+    #[test]
+    fn test_permalink_anchor() {
+        let (html, _) = render("## 标题", true);
+        assert_eq!(
+            html,
+            "<h2 id=\"biao-ti\">标题<a class=\"heading-anchor\" href=\"#biao-ti\" aria-hidden=\"true\">#</a></h2>\n"
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use crate::models::markdown::{HighlightConfig, HighlightMode};
+use crate::utils::html::escape_html;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Tag, TagEnd};
+use std::ops::RangeInclusive;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, Theme, ThemeSet};
+use syntect::html::{
+    append_highlighted_html_for_styled_line, css_for_theme_with_class_style,
+    line_tokens_to_classed_spans, ClassStyle, IncludeBackground,
+};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// 内置语法定义，首次使用时加载
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+/// 内置配色主题，首次使用时加载
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+/// 主题未找到时使用的默认配色
+const FALLBACK_THEME: &str = "InspiredGitHub";
+/// CSS类名前缀，避免与站点样式冲突
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "syn-" };
+
+/// 代码块围栏上的属性
+///
+/// 围栏信息格式：```` ```rust title="main.rs" linenos hl_lines="1 3-5" ````
+///
+/// # 字段说明
+/// * `lang` - 代码语言
+/// * `title` - 代码块标题，通常为文件名
+/// * `linenos` - 是否显示行号
+/// * `hl_lines` - 需要高亮的行号范围（从1开始），保留范围而不展开，避免超大范围占用内存
+#[derive(Debug, Default, PartialEq)]
+struct FenceInfo {
+    lang: Option<String>,
+    title: Option<String>,
+    linenos: bool,
+    hl_lines: Vec<RangeInclusive<usize>>,
+}
+
+impl FenceInfo {
+    /// 解析围栏信息字符串
+    fn parse(info: &str) -> Self {
+        let mut fence = FenceInfo::default();
+        for (index, token) in split_info(info).into_iter().enumerate() {
+            let (key, value) = match token.split_once('=') {
+                Some((key, value)) => (key, Some(value.trim_matches('"'))),
+                None => (token.as_str(), None),
+            };
+            match (key, value) {
+                ("title", Some(value)) => fence.title = Some(value.to_string()),
+                ("linenos", None) | ("linenos", Some("true")) => fence.linenos = true,
+                ("hl_lines", Some(value)) => fence.hl_lines = parse_line_ranges(value),
+                (lang, None) if index == 0 => fence.lang = Some(lang.to_string()),
+                _ => {}
+            }
+        }
+        fence
+    }
+}
+
+/// 按空白切分围栏信息，双引号内的空白不切分
+fn split_info(info: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in info.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                token.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// 解析行号范围，如 `1 3-5` 或 `1,3-5`
+fn parse_line_ranges(value: &str) -> Vec<RangeInclusive<usize>> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|part| match part.split_once('-') {
+            Some((start, end)) => Some(start.parse().ok()?..=end.parse().ok()?),
+            None => {
+                let line = part.parse().ok()?;
+                Some(line..=line)
+            }
+        })
+        .collect()
+}
+
+/// 根据配置获取配色主题，主题不存在时回退到默认主题
+fn theme(name: &str) -> &'static Theme {
+    THEME_SET.themes.get(name).unwrap_or_else(|| {
+        tracing::warn!("Unknown highlight theme `{}`, using {}", name, FALLBACK_THEME);
+        &THEME_SET.themes[FALLBACK_THEME]
+    })
+}
+
+/// 根据语言标记查找语法定义，找不到时按纯文本处理
+fn syntax(lang: Option<&str>) -> &'static SyntaxReference {
+    lang.and_then(|lang| SYNTAX_SET.find_syntax_by_token(lang))
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+/// 将颜色转换为CSS颜色值
+fn css_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// 对Markdown事件流中的围栏代码块进行语法高亮
+///
+/// 围栏代码块被替换为高亮后的HTML，支持以下围栏属性：
+/// - `title="main.rs"`：在代码上方显示标题
+/// - `linenos`：显示行号
+/// - `hl_lines="1 3-5"`：高亮指定的行
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流
+/// * `config` - 语法高亮配置，未启用时事件流原样返回
+///
+/// # 返回
+/// * `Vec<Event>` - 处理后的事件列表
+pub fn process_code_blocks<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    config: &HighlightConfig,
+) -> Vec<Event<'a>> {
+    if !config.enabled {
+        return events.collect();
+    }
+
+    let mut output = Vec::new();
+    // 当前正在收集的代码块：围栏信息与代码文本
+    let mut current: Option<(FenceInfo, String)> = None;
+
+    for event in events {
+        match (event, current.as_mut()) {
+            (Event::Start(Tag::CodeBlock(kind)), None) => {
+                let fence = match kind {
+                    CodeBlockKind::Fenced(info) => FenceInfo::parse(&info),
+                    CodeBlockKind::Indented => FenceInfo::default(),
+                };
+                current = Some((fence, String::new()));
+            }
+            (Event::Text(text), Some((_, code))) => code.push_str(&text),
+            (Event::End(TagEnd::CodeBlock), Some(_)) => {
+                if let Some((fence, code)) = current.take() {
+                    let html = highlight_code(&code, &fence, config).unwrap_or_else(|e| {
+                        tracing::warn!("Failed to highlight code block: {}", e);
+                        format!("<pre><code>{}</code></pre>\n", escape_html(&code))
+                    });
+                    output.push(Event::Html(CowStr::from(html)));
+                }
+            }
+            (event, _) => output.push(event),
+        }
+    }
+
+    output
+}
+
+/// 生成一个代码块的高亮HTML
+fn highlight_code(code: &str, fence: &FenceInfo, config: &HighlightConfig) -> Result<String> {
+    let theme = theme(&config.theme);
+    let syntax = syntax(fence.lang.as_deref());
+    let lines = match config.mode {
+        HighlightMode::Inline => inline_lines(code, syntax, theme)?,
+        HighlightMode::Classes => classed_lines(code, syntax)?,
+    };
+
+    let mut html = String::from(r#"<div class="highlight">"#);
+    if let Some(title) = &fence.title {
+        html.push_str(&format!(
+            r#"<div class="code-title">{}</div>"#,
+            escape_html(title)
+        ));
+    }
+    match (config.mode, theme.settings.background) {
+        (HighlightMode::Inline, Some(bg)) => html.push_str(&format!(
+            r#"<pre style="background-color:{};">"#,
+            css_color(bg)
+        )),
+        (HighlightMode::Inline, None) => html.push_str("<pre>"),
+        // 主题的前景色与背景色由样式表中的 `.syn-code` 提供
+        (HighlightMode::Classes, _) => html.push_str(r#"<pre class="syn-code">"#),
+    }
+    match &fence.lang {
+        Some(lang) => html.push_str(&format!(
+            r#"<code class="language-{0}" data-lang="{0}">"#,
+            escape_html(lang)
+        )),
+        None => html.push_str("<code>"),
+    }
+
+    let line_highlight = theme.settings.line_highlight.map(css_color);
+    for (index, line) in lines.iter().enumerate() {
+        let number = index + 1;
+        if fence.hl_lines.iter().any(|range| range.contains(&number)) {
+            match (config.mode, &line_highlight) {
+                (HighlightMode::Inline, Some(color)) => html.push_str(&format!(
+                    r#"<span class="line hl" style="background-color:{};">"#,
+                    color
+                )),
+                _ => html.push_str(r#"<span class="line hl">"#),
+            }
+        } else {
+            html.push_str(r#"<span class="line">"#);
+        }
+        if fence.linenos {
+            html.push_str(&format!(r#"<span class="line-number">{}</span>"#, number));
+        }
+        html.push_str(line);
+        html.push_str("</span>\n");
+    }
+
+    html.push_str("</code></pre></div>\n");
+    Ok(html)
+}
+
+/// 使用内联样式逐行高亮，每行的HTML标签自行闭合
+fn inline_lines(code: &str, syntax: &SyntaxReference, theme: &Theme) -> Result<Vec<String>> {
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(code) {
+        let regions: Vec<_> = highlighter
+            .highlight_line(line, &SYNTAX_SET)?
+            .into_iter()
+            .map(|(style, text)| (style, text.trim_end_matches(['\r', '\n'])))
+            .collect();
+        let mut html = String::new();
+        append_highlighted_html_for_styled_line(&regions, IncludeBackground::No, &mut html)?;
+        lines.push(html);
+    }
+    Ok(lines)
+}
+
+/// 使用CSS类名逐行高亮
+///
+/// 跨行的作用域在行尾闭合、下一行开头重新打开，保证每行的HTML标签自行闭合。
+fn classed_lines(code: &str, syntax: &SyntaxReference) -> Result<Vec<String>> {
+    let mut state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(code) {
+        let ops = state.parse_line(line, &SYNTAX_SET)?;
+        let text = line.trim_end_matches(['\r', '\n']);
+        // 行尾换行符处的操作提前到文本末尾执行，避免换行符落入标签内
+        let ops: Vec<_> = ops
+            .into_iter()
+            .map(|(index, op)| (index.min(text.len()), op))
+            .collect();
+
+        let mut html = String::new();
+        for scope in stack.as_slice() {
+            let classes: Vec<String> = scope
+                .build_string()
+                .split('.')
+                .map(|atom| format!("syn-{}", atom))
+                .collect();
+            html.push_str(&format!(r#"<span class="{}">"#, classes.join(" ")));
+        }
+        let (spans, _) = line_tokens_to_classed_spans(text, &ops, CLASS_STYLE, &mut stack)?;
+        html.push_str(&spans);
+        html.push_str(&"</span>".repeat(stack.len()));
+        lines.push(html);
+    }
+    Ok(lines)
+}
+
+/// 生成配色主题对应的CSS样式表
+///
+/// 用于 `classes` 输出方式，包含语法配色以及标题、行号和高亮行的样式。
+///
+/// # 参数
+/// * `config` - 语法高亮配置
+///
+/// # 返回
+/// * `Result<String>` - CSS样式表内容
+pub fn theme_css(config: &HighlightConfig) -> Result<String> {
+    let theme = theme(&config.theme);
+    let mut css = css_for_theme_with_class_style(theme, CLASS_STYLE)
+        .map_err(|e| anyhow!("Failed to generate highlight css: {}", e))?;
+    if let Some(color) = theme.settings.line_highlight {
+        css.push_str(&format!(
+            ".highlight .hl {{ background-color: {}; }}\n",
+            css_color(color)
+        ));
+    }
+    css.push_str(
+        ".highlight .code-title { font-size: 0.9em; padding: 0.3em 1em; opacity: 0.8; }\n\
+         .highlight .hl { display: inline-block; min-width: 100%; }\n\
+         .highlight .line-number { display: inline-block; min-width: 2em; margin-right: 1em; \
+         text-align: right; opacity: 0.5; user-select: none; }\n",
+    );
+    Ok(css)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{html, Parser};
+
+    fn render(content: &str, mode: HighlightMode) -> String {
+        let config = HighlightConfig {
+            mode,
+            ..HighlightConfig::default()
+        };
+        let events = process_code_blocks(Parser::new(content), &config);
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        output
+    }
+
+    #[test]
+    fn test_parse_fence_info() {
+        let fence = FenceInfo::parse(r#"rust title="src/main.rs" linenos hl_lines="1 3-4""#);
+        assert_eq!(fence.lang.as_deref(), Some("rust"));
+        assert_eq!(fence.title.as_deref(), Some("src/main.rs"));
+        assert!(fence.linenos);
+        assert_eq!(fence.hl_lines, vec![1..=1, 3..=4]);
+        assert_eq!(FenceInfo::parse(""), FenceInfo::default());
+    }
+
+    #[test]
+    fn test_classes_mode() {
+        let html = render("```rust\nfn main() {}\n```\n", HighlightMode::Classes);
+        assert!(html.contains(r#"<code class="language-rust" data-lang="rust">"#));
+        assert!(html.contains(r#"<span class="syn-storage syn-type syn-function syn-rust">fn</span>"#));
+        assert!(html.contains(r#"<pre class="syn-code">"#));
+        assert!(!html.contains("style="));
+    }
+
+    #[test]
+    fn test_classes_mode_multiline_scope() {
+        let html = render("```rust\n/* a\nb */\n```\n", HighlightMode::Classes);
+        // 跨行注释在每行内各自闭合
+        for line in html.split("<span class=\"line\">").skip(1) {
+            let line = line.split('\n').next().unwrap();
+            assert_eq!(line.matches("<span").count() + 1, line.matches("</span>").count());
+        }
+    }
+
+    #[test]
+    fn test_inline_mode() {
+        let html = render("```rust\nlet x = 1;\n```\n", HighlightMode::Inline);
+        assert!(html.contains(r#"<pre style="background-color:#ffffff;">"#));
+        assert!(html.contains("<span style=\"color:"));
+    }
+
+    #[test]
+    fn test_fence_attributes() {
+        let html = render(
+            "```python title=\"a<b>.py\" linenos hl_lines=\"2\"\nx = 1\ny = 2\n```\n",
+            HighlightMode::Classes,
+        );
+        assert!(html.contains(r#"<div class="code-title">a&lt;b&gt;.py</div>"#));
+        assert!(html.contains(r#"<span class="line"><span class="line-number">1</span>"#));
+        assert!(html.contains(r#"<span class="line hl"><span class="line-number">2</span>"#));
+    }
+
+    #[test]
+    fn test_huge_line_range() {
+        let fence = FenceInfo::parse(r#"rust hl_lines="1-4000000000 18446744073709551615""#);
+        assert_eq!(fence.hl_lines, vec![1..=4_000_000_000, usize::MAX..=usize::MAX]);
+        let html = render(
+            "```rust hl_lines=\"2-4000000000\"\nlet a = 1;\nlet b = 2;\n```\n",
+            HighlightMode::Classes,
+        );
+        assert!(html.contains(r#"<span class="line">"#));
+        assert_eq!(html.matches(r#"<span class="line hl">"#).count(), 1);
+    }
+
+    #[test]
+    fn test_unknown_language_is_escaped() {
+        let html = render("```nosuchlang\n<script>\n```\n", HighlightMode::Inline);
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let config = HighlightConfig {
+            enabled: false,
+            ..HighlightConfig::default()
+        };
+        let events = process_code_blocks(Parser::new("```rust\nfn main() {}\n```\n"), &config);
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        assert_eq!(
+            output,
+            "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_theme_css() {
+        let css = theme_css(&HighlightConfig::default()).unwrap();
+        assert!(css.contains(".syn-comment"));
+        assert!(css.contains(".highlight .line-number"));
+    }
+}
@@ -0,0 +1,455 @@
+use crate::models::markdown::ImageConfig;
+use crate::utils::html::escape_html;
+use anyhow::Result;
+use image::{codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageFormat};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{CowStr, Event, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::BufWriter;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+/// 缩略图的访问路径前缀，对应配置中的缓存目录
+pub const URL_PREFIX: &str = "/images";
+
+/// 已处理的本地图片，键为文章中引用的图片地址
+static IMAGES: Lazy<RwLock<HashMap<String, ImageInfo>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 本地图片的尺寸与缩略图
+///
+/// # 字段说明
+/// * `fingerprint` - 由文件路径、大小与修改时间计算，文件变化后重新生成缩略图
+/// * `width` / `height` - 原图尺寸
+/// * `variants` - 与原图格式相同的缩略图，按宽度升序
+/// * `webp` - WebP格式的图片（含原图尺寸），按宽度升序
+#[derive(Debug, Clone)]
+struct ImageInfo {
+    fingerprint: u64,
+    width: u32,
+    height: u32,
+    variants: Vec<Variant>,
+    webp: Vec<Variant>,
+}
+
+/// 一张缩略图
+#[derive(Debug, Clone)]
+struct Variant {
+    width: u32,
+    url: String,
+}
+
+/// 提取Markdown中引用的所有图片地址
+pub fn collect_sources(content: &str) -> Vec<String> {
+    Parser::new(content)
+        .filter_map(|event| match event {
+            Event::Start(Tag::Image { dest_url, .. }) => Some(dest_url.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 为文章引用的本地图片重建图片登记表
+///
+/// 文件未变化的图片沿用已有的尺寸与缩略图，不再被引用的图片从登记表中移除。
+/// 单张图片处理失败时记录警告并跳过，不影响其他图片。
+///
+/// # 参数
+/// * `sources` - 文章中引用的所有图片地址
+/// * `static_dir` - 静态文件目录，`/static/` 开头的地址对应其中的文件
+/// * `config` - 图片处理配置
+pub fn rebuild(sources: &[String], static_dir: &Path, config: &ImageConfig) {
+    let previous = IMAGES.read().expect("image registry lock poisoned").clone();
+    let mut images = HashMap::new();
+    for src in sources {
+        match prepare(src, static_dir, config, &previous) {
+            Ok(Some(info)) => {
+                images.insert(src.clone(), info);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to prepare image `{}`: {}", src, e),
+        }
+    }
+    *IMAGES.write().expect("image registry lock poisoned") = images;
+}
+
+/// 为本地图片读取尺寸并生成缩略图
+///
+/// 仅处理 `/static/` 下的图片，其他地址返回 `None`。缩略图写入缓存目录，
+/// 文件名包含原图指纹，已存在时不会重复生成。GIF 可能是动图，只记录尺寸。
+///
+/// # 参数
+/// * `src` - 文章中引用的图片地址
+/// * `static_dir` - 静态文件目录
+/// * `config` - 图片处理配置
+/// * `previous` - 上次处理的结果，指纹相同时直接复用
+///
+/// # 错误
+/// * 图片无法读取、解码或缩略图无法写入时返回错误
+fn prepare(
+    src: &str,
+    static_dir: &Path,
+    config: &ImageConfig,
+    previous: &HashMap<String, ImageInfo>,
+) -> Result<Option<ImageInfo>> {
+    let Some(path) = local_path(src, static_dir) else {
+        return Ok(None);
+    };
+    let metadata = fs::metadata(&path)?;
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    metadata.len().hash(&mut hasher);
+    metadata.modified().ok().hash(&mut hasher);
+    let fingerprint = hasher.finish();
+
+    if let Some(info) = previous.get(src).filter(|info| info.fingerprint == fingerprint) {
+        return Ok(Some(info.clone()));
+    }
+
+    let format = ImageFormat::from_path(&path)?;
+    let (width, height) = image::image_dimensions(&path)?;
+    let mut info = ImageInfo {
+        fingerprint,
+        width,
+        height,
+        variants: Vec::new(),
+        webp: Vec::new(),
+    };
+
+    if matches!(format, ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::WebP) {
+        let cache_dir = Path::new(&config.cache_dir);
+        fs::create_dir_all(cache_dir)?;
+        let mut generator = Generator {
+            path: &path,
+            cache_dir,
+            fingerprint,
+            quality: config.quality,
+            source: None,
+        };
+
+        let mut widths: Vec<u32> = config
+            .widths
+            .iter()
+            .copied()
+            .filter(|&w| w > 0 && w < width)
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+        widths.push(width);
+
+        for w in widths {
+            let original_size = if w < width {
+                let (url, size) = generator.generate(w, format)?;
+                info.variants.push(Variant { width: w, url });
+                size
+            } else {
+                metadata.len()
+            };
+            if config.webp && format != ImageFormat::WebP {
+                let (url, size) = generator.generate(w, ImageFormat::WebP)?;
+                // 无损WebP可能比有损JPEG更大，此时不提供WebP版本
+                if size < original_size {
+                    info.webp.push(Variant { width: w, url });
+                }
+            }
+        }
+    }
+
+    Ok(Some(info))
+}
+
+/// 缩略图生成器，按需解码原图并复用解码结果
+struct Generator<'a> {
+    path: &'a Path,
+    cache_dir: &'a Path,
+    fingerprint: u64,
+    quality: u8,
+    source: Option<DynamicImage>,
+}
+
+impl Generator<'_> {
+    /// 生成指定宽度与格式的图片，返回访问地址与文件大小
+    fn generate(&mut self, width: u32, format: ImageFormat) -> Result<(String, u64)> {
+        let extension = format.extensions_str()[0];
+        let file_name = format!("{:016x}-{}.{}", self.fingerprint, width, extension);
+        let file = self.cache_dir.join(&file_name);
+
+        if !file.exists() {
+            let source = match self.source.take() {
+                Some(source) => source,
+                None => image::open(self.path)?,
+            };
+            let resized = if width < source.width() {
+                source.resize(width, u32::MAX, FilterType::Lanczos3)
+            } else {
+                source.clone()
+            };
+            self.source = Some(source);
+
+            // 先写入临时文件再重命名，避免并发加载时读到不完整的图片
+            let temp = file.with_extension(format!("{}.tmp", extension));
+            let mut writer = BufWriter::new(fs::File::create(&temp)?);
+            match format {
+                ImageFormat::Jpeg => {
+                    let encoder = JpegEncoder::new_with_quality(&mut writer, self.quality);
+                    DynamicImage::ImageRgb8(resized.to_rgb8()).write_with_encoder(encoder)?;
+                }
+                ImageFormat::WebP if resized.color().has_alpha() => {
+                    DynamicImage::ImageRgba8(resized.to_rgba8()).write_to(&mut writer, format)?
+                }
+                ImageFormat::WebP => {
+                    DynamicImage::ImageRgb8(resized.to_rgb8()).write_to(&mut writer, format)?
+                }
+                _ => resized.write_to(&mut writer, format)?,
+            }
+            drop(writer);
+            fs::rename(&temp, &file)?;
+        }
+
+        let size = fs::metadata(&file)?.len();
+        Ok((format!("{}/{}", URL_PREFIX, file_name), size))
+    }
+}
+
+/// 将文章中的图片地址转换为静态文件目录下的文件路径
+///
+/// 只接受 `/static/` 开头的地址，拒绝包含 `..` 等非常规路径成分的地址。
+fn local_path(src: &str, static_dir: &Path) -> Option<PathBuf> {
+    let path = src.split(['?', '#']).next()?;
+    let relative = path.strip_prefix("/static/")?;
+    let relative = PathBuf::from(percent_decode(relative));
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| static_dir.join(relative))
+}
+
+/// 解码URL中的百分号编码，如中文文件名
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 处理Markdown事件流中的图片
+///
+/// - 已处理的本地图片输出 `srcset`/`sizes` 与 `width`/`height`，有WebP版本时使用 `<picture>`
+/// - 所有图片添加 `loading="lazy"` 与 `decoding="async"`
+/// - 单独成段且带有替代文本的图片包裹在 `<figure>` 中，替代文本作为 `<figcaption>`
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流
+/// * `config` - 图片处理配置
+///
+/// # 返回
+/// * `Vec<Event>` - 处理后的事件列表
+pub fn process_images<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    config: &ImageConfig,
+) -> Vec<Event<'a>> {
+    if !config.enabled {
+        return events.collect();
+    }
+
+    let mut output = Vec::new();
+    let mut events = events.peekable();
+    while let Some(event) = events.next() {
+        let Event::Start(Tag::Image {
+            dest_url, title, ..
+        }) = event
+        else {
+            output.push(event);
+            continue;
+        };
+
+        // 收集替代文本，直到图片结束
+        let mut alt = String::new();
+        let mut depth = 1;
+        for event in events.by_ref() {
+            match event {
+                Event::Start(Tag::Image { .. }) => depth += 1,
+                Event::End(TagEnd::Image) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                _ => {}
+            }
+        }
+
+        let img = image_html(&dest_url, &alt, &title, config);
+        let standalone = matches!(output.last(), Some(Event::Start(Tag::Paragraph)))
+            && matches!(events.peek(), Some(Event::End(TagEnd::Paragraph)));
+        if standalone && !alt.is_empty() {
+            output.pop();
+            events.next();
+            output.push(Event::Html(CowStr::from(format!(
+                "<figure class=\"image\">{}<figcaption>{}</figcaption></figure>\n",
+                img,
+                escape_html(&alt)
+            ))));
+        } else {
+            output.push(Event::InlineHtml(CowStr::from(img)));
+        }
+    }
+    output
+}
+
+/// 生成单张图片的HTML
+fn image_html(src: &str, alt: &str, title: &str, config: &ImageConfig) -> String {
+    let info = IMAGES
+        .read()
+        .expect("image registry lock poisoned")
+        .get(src)
+        .cloned();
+
+    let mut img = format!(
+        r#"<img src="{}" alt="{}""#,
+        escape_html(src),
+        escape_html(alt)
+    );
+    if !title.is_empty() {
+        img.push_str(&format!(r#" title="{}""#, escape_html(title)));
+    }
+    let Some(info) = info else {
+        img.push_str(r#" loading="lazy" decoding="async" />"#);
+        return img;
+    };
+
+    let sizes = escape_html(&config.sizes);
+    if !info.variants.is_empty() {
+        let original = Variant {
+            width: info.width,
+            url: src.to_string(),
+        };
+        let srcset = srcset(info.variants.iter().chain([&original]));
+        img.push_str(&format!(r#" srcset="{}" sizes="{}""#, srcset, sizes));
+    }
+    img.push_str(&format!(
+        r#" width="{}" height="{}" loading="lazy" decoding="async" />"#,
+        info.width, info.height
+    ));
+
+    if info.webp.is_empty() {
+        img
+    } else {
+        format!(
+            r#"<picture><source type="image/webp" srcset="{}" sizes="{}" />{}</picture>"#,
+            srcset(info.webp.iter()),
+            sizes,
+            img
+        )
+    }
+}
+
+/// 生成 `srcset` 属性值
+fn srcset<'a>(variants: impl Iterator<Item = &'a Variant>) -> String {
+    variants
+        .map(|v| format!("{} {}w", escape_html(&v.url), v.width))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use pulldown_cmark::html;
+
+    fn render(content: &str, config: &ImageConfig) -> String {
+        let events = process_images(Parser::new(content), config);
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        output
+    }
+
+    #[test]
+    fn test_local_path() {
+        let static_dir = Path::new("static");
+        assert_eq!(
+            local_path("/static/img/%E5%9B%BE.png?v=1", static_dir),
+            Some(PathBuf::from("static/img/图.png"))
+        );
+        assert_eq!(local_path("/static/../Cargo.toml", static_dir), None);
+        assert_eq!(local_path("https://example.com/static/a.png", static_dir), None);
+        assert_eq!(local_path("/upload/a.png", static_dir), None);
+    }
+
+    #[test]
+    fn test_remote_image() {
+        let html = render(
+            "![远程图片](https://example.com/a.png)\n\n文字 ![](https://example.com/b.png) 文字\n",
+            &ImageConfig::default(),
+        );
+        assert_eq!(
+            html,
+            "<figure class=\"image\"><img src=\"https:&#x2F;&#x2F;example.com&#x2F;a.png\" alt=\"远程图片\" loading=\"lazy\" decoding=\"async\" /><figcaption>远程图片</figcaption></figure>\n\
+             <p>文字 <img src=\"https:&#x2F;&#x2F;example.com&#x2F;b.png\" alt=\"\" loading=\"lazy\" decoding=\"async\" /> 文字</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_local_image_variants() {
+        let root = std::env::temp_dir().join(format!("au92-blog-images-{}", std::process::id()));
+        let static_dir = root.join("static");
+        fs::create_dir_all(static_dir.join("test-images")).unwrap();
+        let mut image = RgbImage::new(1000, 500);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgb([(x % 256) as u8, (y % 256) as u8, ((x * y) % 256) as u8]);
+        }
+        image.save(static_dir.join("test-images/photo.jpg")).unwrap();
+
+        let cache_dir = root.join("cache");
+        let config = ImageConfig {
+            widths: vec![480, 800, 1200],
+            cache_dir: cache_dir.to_string_lossy().to_string(),
+            ..ImageConfig::default()
+        };
+        let src = "/static/test-images/photo.jpg";
+        let sources = vec![src.to_string(), "/static/missing.png".to_string()];
+        rebuild(&sources, &static_dir, &config);
+
+        let info = IMAGES.read().unwrap().get(src).cloned().unwrap();
+        assert_eq!((info.width, info.height), (1000, 500));
+        let widths: Vec<_> = info.variants.iter().map(|v| v.width).collect();
+        assert_eq!(widths, vec![480, 800]);
+        for variant in &info.variants {
+            let file = cache_dir.join(variant.url.trim_start_matches("/images/"));
+            assert_eq!(image::image_dimensions(file).unwrap().0, variant.width);
+        }
+
+        let html = render(&format!("![照片]({})\n", src), &config);
+        assert!(html.starts_with("<figure class=\"image\">"));
+        assert!(html.contains(r#"width="1000" height="500""#));
+        assert!(html.contains("480w, "));
+        assert!(html.contains(r#"&#x2F;static&#x2F;test-images&#x2F;photo.jpg 1000w""#));
+        assert!(html.contains("<figcaption>照片</figcaption>"));
+
+        // 不再被引用的图片从登记表中移除
+        rebuild(&[], &static_dir, &config);
+        assert!(IMAGES.read().unwrap().get(src).is_none());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::models::markdown::LinkConfig;
+use crate::utils::html::escape_html;
+use pulldown_cmark::{CowStr, Event, Tag};
+
+/// 外部链接处理策略
+///
+/// 由链接配置、站点地址与信任域名构建，渲染文章时用于区分站内与外部链接。
+///
+/// # 字段说明
+/// * `config` - 外部链接配置
+/// * `site_host` - 站点主机名（小写），为空时所有绝对地址都视为外部链接
+/// * `trusted` - 信任的域名（小写），这些域名及其子域名的链接不添加 `nofollow`
+#[derive(Debug, Clone, Default)]
+pub struct LinkPolicy {
+    config: LinkConfig,
+    site_host: String,
+    trusted: Vec<String>,
+}
+
+impl LinkPolicy {
+    /// 创建外部链接处理策略
+    ///
+    /// # 参数
+    /// * `config` - 外部链接配置
+    /// * `site_url` - 站点地址
+    /// * `friend_urls` - 友链地址，`trust_friends` 开启时其域名加入信任列表
+    pub fn new<'a>(
+        config: &LinkConfig,
+        site_url: &str,
+        friend_urls: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let mut trusted: Vec<String> = config
+            .allowlist
+            .iter()
+            .map(|domain| domain.trim().trim_start_matches("*.").to_lowercase())
+            .filter(|domain| !domain.is_empty())
+            .collect();
+        if config.trust_friends {
+            trusted.extend(friend_urls.into_iter().filter_map(host).map(str::to_lowercase));
+        }
+        trusted.sort();
+        trusted.dedup();
+
+        Self {
+            config: config.clone(),
+            site_host: host(site_url).unwrap_or_default().to_lowercase(),
+            trusted,
+        }
+    }
+
+    /// 判断链接是否指向外部站点
+    ///
+    /// 只有 `http(s)://` 与 `//` 开头的地址可能是外部链接，
+    /// 相对地址、锚点与 `mailto:` 等其他协议都不处理。
+    pub fn is_external(&self, url: &str) -> bool {
+        host(url).is_some_and(|host| !host.eq_ignore_ascii_case(&self.site_host))
+    }
+
+    /// 判断外部链接的域名是否受信任
+    fn is_trusted(&self, url: &str) -> bool {
+        let Some(host) = host(url).map(str::to_lowercase) else {
+            return false;
+        };
+        self.trusted.iter().any(|domain| {
+            host == *domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+
+    /// 外部链接的 `rel` 属性值，信任的域名不含 `nofollow`
+    fn rel(&self, url: &str) -> String {
+        let trusted = self.is_trusted(url);
+        self.config
+            .rel
+            .iter()
+            .filter(|rel| !(trusted && rel.eq_ignore_ascii_case("nofollow")))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// 提取地址中的主机名，不含端口与用户信息
+fn host(url: &str) -> Option<&str> {
+    let url = url.trim();
+    let rest = ["https://", "http://", "//"].iter().find_map(|prefix| {
+        url.get(..prefix.len())
+            .filter(|head| head.eq_ignore_ascii_case(prefix))
+            .map(|_| &url[prefix.len()..])
+    })?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    let host = authority.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// 处理Markdown事件流中的外部链接
+///
+/// 外部链接按配置添加 `rel`、`target` 与CSS类名，站内链接保持不变。
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流
+/// * `policy` - 外部链接处理策略
+///
+/// # 返回
+/// * `Vec<Event>` - 处理后的事件列表
+pub fn process_links<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    policy: &LinkPolicy,
+) -> Vec<Event<'a>> {
+    if !policy.config.enabled {
+        return events.collect();
+    }
+
+    events
+        .map(|event| match event {
+            Event::Start(Tag::Link {
+                dest_url, title, ..
+            }) if policy.is_external(&dest_url) => {
+                Event::InlineHtml(CowStr::from(external_link_html(&dest_url, &title, policy)))
+            }
+            event => event,
+        })
+        .collect()
+}
+
+/// 生成外部链接的起始标签，结束标签由原事件流中的 `End(Link)` 输出
+fn external_link_html(url: &str, title: &str, policy: &LinkPolicy) -> String {
+    let mut html = format!(r#"<a href="{}""#, escape_html(url));
+    if !title.is_empty() {
+        html.push_str(&format!(r#" title="{}""#, escape_html(title)));
+    }
+    if !policy.config.class.is_empty() {
+        html.push_str(&format!(r#" class="{}""#, escape_html(&policy.config.class)));
+    }
+    let rel = policy.rel(url);
+    if !rel.is_empty() {
+        html.push_str(&format!(r#" rel="{}""#, escape_html(&rel)));
+    }
+    if !policy.config.target.is_empty() {
+        html.push_str(&format!(r#" target="{}""#, escape_html(&policy.config.target)));
+    }
+    html.push('>');
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{html, Parser};
+
+    fn policy(config: &LinkConfig) -> LinkPolicy {
+        LinkPolicy::new(
+            config,
+            "https://example.com/",
+            ["https://www.friend.com/", "not a url"],
+        )
+    }
+
+    fn render(content: &str, policy: &LinkPolicy) -> String {
+        let events = process_links(Parser::new(content), policy);
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        output
+    }
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("https://Example.com:8080/a?b"), Some("Example.com"));
+        assert_eq!(host("//cdn.example.com/x.js"), Some("cdn.example.com"));
+        assert_eq!(host("http://user@example.com"), Some("example.com"));
+        assert_eq!(host("/post/a/"), None);
+        assert_eq!(host("mailto:a@example.com"), None);
+        assert_eq!(host("https://"), None);
+    }
+
+    #[test]
+    fn test_internal_links_unchanged() {
+        let policy = policy(&LinkConfig::default());
+        assert_eq!(
+            render("[a](/post/a/) [b](https://EXAMPLE.com/post/b/) [c](#x)", &policy),
+            "<p><a href=\"/post/a/\">a</a> <a href=\"https://EXAMPLE.com/post/b/\">b</a> <a href=\"#x\">c</a></p>\n"
+        );
+        assert_eq!(
+            render("<me@other.com> [m](mailto:me@other.com)", &policy),
+            "<p><a href=\"mailto:me@other.com\">me@other.com</a> <a href=\"mailto:me@other.com\">m</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_external_links() {
+        let policy = policy(&LinkConfig::default());
+        assert_eq!(
+            render("[Rust](https://www.rust-lang.org \"官网\")", &policy),
+            "<p><a href=\"https:&#x2F;&#x2F;www.rust-lang.org\" title=\"官网\" class=\"external-link\" \
+             rel=\"nofollow noopener noreferrer\" target=\"_blank\">Rust</a></p>\n"
+        );
+        // 友链域名不添加 nofollow，自动链接同样处理
+        assert!(render("<https://www.friend.com/about>", &policy)
+            .contains("rel=\"noopener noreferrer\""));
+    }
+
+    #[test]
+    fn test_allowlist_and_options() {
+        let config = LinkConfig {
+            rel: vec!["nofollow".to_string()],
+            target: String::new(),
+            class: String::new(),
+            allowlist: vec!["*.GitHub.com".to_string()],
+            trust_friends: false,
+            ..LinkConfig::default()
+        };
+        let policy = policy(&config);
+        assert_eq!(
+            render("[gh](https://docs.github.com/)", &policy),
+            "<p><a href=\"https:&#x2F;&#x2F;docs.github.com&#x2F;\">gh</a></p>\n"
+        );
+        assert!(render("[gh](https://notgithub.com/)", &policy).contains("rel=\"nofollow\""));
+        assert!(render("[f](https://www.friend.com/)", &policy).contains("rel=\"nofollow\""));
+
+        let disabled = LinkConfig {
+            enabled: false,
+            ..LinkConfig::default()
+        };
+        assert_eq!(
+            render("[x](https://other.com/)", &LinkPolicy::new(&disabled, "", [])),
+            "<p><a href=\"https://other.com/\">x</a></p>\n"
+        );
+    }
+}
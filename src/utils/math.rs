@@ -0,0 +1,882 @@
+use crate::utils::html::escape_html;
+use anyhow::{anyhow, bail, Result};
+use pulldown_cmark::{CowStr, Event};
+
+/// MathML命名空间
+const MATHML_NS: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// 公式允许的最大嵌套层数，超过时视为无法解析，避免递归过深导致栈溢出
+const MAX_DEPTH: usize = 64;
+
+/// 将Markdown事件流中的数学公式转换为MathML
+///
+/// 行内公式 `$...$` 与块级公式 `$$...$$` 分别输出为 `display="inline"` 与
+/// `display="block"` 的 `<math>` 元素，原始LaTeX保存在 `<annotation>` 中。
+/// 公式无法解析时输出带 `math-error` 类名的原始公式，便于作者定位问题。
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流（需启用 `math` 扩展）
+///
+/// # 返回
+/// * `Vec<Event>` - 处理后的事件列表
+pub fn process_math<'a>(events: impl Iterator<Item = Event<'a>>) -> Vec<Event<'a>> {
+    events
+        .map(|event| match event {
+            Event::InlineMath(latex) => Event::InlineHtml(CowStr::from(render(&latex, false))),
+            Event::DisplayMath(latex) => Event::InlineHtml(CowStr::from(render(&latex, true))),
+            other => other,
+        })
+        .collect()
+}
+
+/// 渲染单个公式，解析失败时回退为显示源码
+fn render(latex: &str, display: bool) -> String {
+    latex_to_mathml(latex, display).unwrap_or_else(|e| {
+        tracing::debug!("Failed to parse math `{}`: {}", latex, e);
+        let (class, delimiter) = if display {
+            ("math-error math-display", "$$")
+        } else {
+            ("math-error", "$")
+        };
+        format!(
+            r#"<code class="{}" title="{}">{}{}{}</code>"#,
+            class,
+            escape_html(&e.to_string()),
+            delimiter,
+            escape_html(latex),
+            delimiter
+        )
+    })
+}
+
+/// 将LaTeX公式转换为MathML
+///
+/// 支持常用的LaTeX数学语法：上下标、分式、根式、希腊字母与常用符号、
+/// 函数名、`\left`/`\right` 定界符、字体命令、重音符号、`\text`、
+/// 间距命令以及 `matrix`/`pmatrix`/`bmatrix`/`cases`/`aligned` 等环境。
+///
+/// # 参数
+/// * `latex` - LaTeX公式源码（不含 `$` 定界符）
+/// * `display` - 是否为块级公式
+///
+/// # 返回
+/// * `Result<String>` - `<math>` 元素，遇到不支持的语法时返回错误
+pub fn latex_to_mathml(latex: &str, display: bool) -> Result<String> {
+    let mut parser = MathParser::new(latex, display);
+    let row = parser.parse_row()?;
+    if let Some(c) = parser.peek() {
+        bail!("unexpected `{}` at position {}", c, parser.pos);
+    }
+    Ok(format!(
+        r#"<math xmlns="{}" display="{}"><semantics>{}<annotation encoding="application/x-tex">{}</annotation></semantics></math>"#,
+        MATHML_NS,
+        if display { "block" } else { "inline" },
+        mrow(row),
+        escape_html(latex.trim())
+    ))
+}
+
+/// 公式中的一个元素
+///
+/// # 字段说明
+/// * `html` - 元素的MathML
+/// * `limits` - 是否为块级公式中上下标写在正上方/正下方的大型运算符（如 `\sum`）
+struct Item {
+    html: String,
+    limits: bool,
+}
+
+impl Item {
+    fn new(html: String) -> Self {
+        Self {
+            html,
+            limits: false,
+        }
+    }
+}
+
+/// 将多个元素包装为一个 `<mrow>`，只有一个元素时直接返回
+fn mrow(items: Vec<Item>) -> String {
+    match items.len() {
+        1 => items.into_iter().next().map(|item| item.html).unwrap_or_default(),
+        _ => format!(
+            "<mrow>{}</mrow>",
+            items.into_iter().map(|item| item.html).collect::<String>()
+        ),
+    }
+}
+
+/// LaTeX数学公式的递归下降解析器，直接输出MathML
+struct MathParser {
+    chars: Vec<char>,
+    pos: usize,
+    display: bool,
+    /// 当前字体命令（如 `\mathbf`）对应的 `mathvariant`
+    variant: Option<&'static str>,
+    /// 当前嵌套层数
+    depth: usize,
+}
+
+impl MathParser {
+    fn new(latex: &str, display: bool) -> Self {
+        Self {
+            chars: latex.chars().collect(),
+            pos: 0,
+            display,
+            variant: None,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => bail!("expected `{}`, found `{}`", expected, c),
+            None => bail!("expected `{}`, found end of formula", expected),
+        }
+    }
+
+    /// 当前位置是否为指定的命令（如 `\end`），命令名之后不能紧跟字母
+    fn at_command(&self, name: &str) -> bool {
+        let name: Vec<char> = name.chars().collect();
+        let end = self.pos + 1 + name.len();
+        self.peek() == Some('\\')
+            && self.chars.get(self.pos + 1..end) == Some(&name[..])
+            && !self.chars.get(end).is_some_and(|c| c.is_ascii_alphabetic())
+    }
+
+    /// 当前位置是否为换行 `\\`
+    fn at_line_break(&self) -> bool {
+        self.peek() == Some('\\') && self.chars.get(self.pos + 1) == Some(&'\\')
+    }
+
+    /// 读取命令名：字母序列或单个非字母字符
+    fn command_name(&mut self) -> Result<String> {
+        match self.next() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut name = c.to_string();
+                while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+                    name.push(c);
+                    self.pos += 1;
+                }
+                Ok(name)
+            }
+            Some(c) => Ok(c.to_string()),
+            None => bail!("unexpected end after `\\`"),
+        }
+    }
+
+    /// 读取花括号内的原始文本（用于 `\text`、环境名等）
+    fn raw_group(&mut self) -> Result<String> {
+        self.expect('{')?;
+        let mut depth = 0;
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('{') => {
+                    depth += 1;
+                    text.push('{');
+                }
+                Some('}') if depth == 0 => return Ok(text),
+                Some('}') => {
+                    depth -= 1;
+                    text.push('}');
+                }
+                // 转义字符只保留被转义的字符
+                Some('\\') => text.extend(self.next()),
+                Some(c) => text.push(c),
+                None => bail!("unclosed `{{`"),
+            }
+        }
+    }
+
+    /// 生成带字体变体的标记元素
+    fn token(&self, tag: &str, text: &str) -> Item {
+        let text = escape_html(text);
+        Item::new(match (tag, self.variant) {
+            ("mi" | "mn", Some(variant)) => {
+                format!(r#"<{0} mathvariant="{1}">{2}</{0}>"#, tag, variant, text)
+            }
+            _ => format!("<{0}>{1}</{0}>", tag, text),
+        })
+    }
+
+    /// 解析一行公式，遇到 `}`、`&`、`\\`、`\right`、`\end` 或结尾时停止（不消费）
+    fn parse_row(&mut self) -> Result<Vec<Item>> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some('}') | Some('&') => break,
+                Some('\\')
+                    if self.at_line_break() || self.at_command("right") || self.at_command("end") =>
+                {
+                    break
+                }
+                Some('^') | Some('_') => {
+                    let base = items.pop().unwrap_or_else(|| Item::new("<mrow></mrow>".into()));
+                    items.push(self.parse_scripts(base)?);
+                }
+                Some(_) => items.push(self.parse_atom(false)?),
+            }
+        }
+        Ok(items)
+    }
+
+    /// 解析紧跟在元素后的上标与下标
+    fn parse_scripts(&mut self, base: Item) -> Result<Item> {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            let slot = match self.peek() {
+                Some('_') => &mut sub,
+                Some('^') => &mut sup,
+                _ => break,
+            };
+            if slot.is_some() {
+                bail!("double subscript or superscript");
+            }
+            self.pos += 1;
+            *slot = Some(self.parse_arg()?);
+        }
+
+        let (under, over, both) = if base.limits && self.display {
+            ("munder", "mover", "munderover")
+        } else {
+            ("msub", "msup", "msubsup")
+        };
+        Ok(Item::new(match (sub, sup) {
+            (Some(sub), Some(sup)) => {
+                format!("<{0}>{1}{2}{3}</{0}>", both, base.html, sub, sup)
+            }
+            (Some(sub), None) => format!("<{0}>{1}{2}</{0}>", under, base.html, sub),
+            (None, Some(sup)) => format!("<{0}>{1}{2}</{0}>", over, base.html, sup),
+            (None, None) => base.html,
+        }))
+    }
+
+    /// 解析命令参数：花括号分组或单个记号
+    fn parse_arg(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        Ok(self.parse_atom(true)?.html)
+    }
+
+    /// 解析单个元素
+    ///
+    /// `single` 为 true 时数字只读取一位，如 `x^23` 中上标只有 `2`
+    fn parse_atom(&mut self, single: bool) -> Result<Item> {
+        // 分组、命令参数与环境都经由这里递归，在此统一限制嵌套层数
+        if self.depth >= MAX_DEPTH {
+            bail!("formula nested deeper than {} levels", MAX_DEPTH);
+        }
+        self.depth += 1;
+        let item = self.parse_atom_inner(single);
+        self.depth -= 1;
+        item
+    }
+
+    /// [`Self::parse_atom`] 的解析逻辑，不检查嵌套层数
+    fn parse_atom_inner(&mut self, single: bool) -> Result<Item> {
+        self.skip_whitespace();
+        let c = self
+            .next()
+            .ok_or_else(|| anyhow!("unexpected end of formula"))?;
+        match c {
+            '{' => {
+                let row = self.parse_row()?;
+                self.expect('}')?;
+                Ok(Item::new(mrow(row)))
+            }
+            '\\' => self.parse_command(),
+            '}' => bail!("unmatched `}}`"),
+            '&' => bail!("unexpected `&` outside of an environment"),
+            '~' => Ok(Item::new(r#"<mspace width="0.3333em"/>"#.into())),
+            c if c.is_ascii_digit()
+                || (c == '.' && self.peek().is_some_and(|d| d.is_ascii_digit())) =>
+            {
+                let mut number = c.to_string();
+                if !single {
+                    while let Some(d) = self.peek().filter(|d| d.is_ascii_digit() || *d == '.') {
+                        number.push(d);
+                        self.pos += 1;
+                    }
+                }
+                Ok(self.token("mn", &number))
+            }
+            c if c.is_alphabetic() => Ok(self.token("mi", &c.to_string())),
+            '-' => Ok(self.token("mo", "\u{2212}")),
+            '*' => Ok(self.token("mo", "\u{2217}")),
+            '\'' => Ok(self.token("mo", "\u{2032}")),
+            c => Ok(self.token("mo", &c.to_string())),
+        }
+    }
+
+    /// 解析 `\` 开头的命令
+    fn parse_command(&mut self) -> Result<Item> {
+        let name = self.command_name()?;
+        if let Some(symbol) = greek(&name) {
+            return Ok(self.token("mi", symbol));
+        }
+        if let Some(symbol) = identifier(&name) {
+            return Ok(self.token("mi", symbol));
+        }
+        if let Some(symbol) = operator(&name) {
+            return Ok(self.token("mo", symbol));
+        }
+        if let Some((symbol, limits)) = big_operator(&name) {
+            return Ok(Item {
+                html: format!("<mo>{}</mo>", symbol),
+                limits,
+            });
+        }
+        if let Some(limits) = function(&name) {
+            return Ok(Item {
+                html: format!("<mi>{}</mi>", name),
+                limits,
+            });
+        }
+        if let Some(width) = space(&name) {
+            return Ok(Item::new(format!(r#"<mspace width="{}"/>"#, width)));
+        }
+        if let Some(variant) = font_variant(&name) {
+            let saved = self.variant.replace(variant);
+            let arg = self.parse_arg();
+            self.variant = saved;
+            return Ok(Item::new(arg?));
+        }
+        if let Some((accent, over)) = accent(&name) {
+            let arg = self.parse_arg()?;
+            let tag = if over { "mover" } else { "munder" };
+            let attr = if over { "accent" } else { "accentunder" };
+            return Ok(Item::new(format!(
+                r#"<{0} {1}="true">{2}<mo>{3}</mo></{0}>"#,
+                tag, attr, arg, accent
+            )));
+        }
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_arg()?;
+                let denominator = self.parse_arg()?;
+                Ok(Item::new(format!("<mfrac>{}{}</mfrac>", numerator, denominator)))
+            }
+            "binom" => {
+                let top = self.parse_arg()?;
+                let bottom = self.parse_arg()?;
+                Ok(Item::new(format!(
+                    r#"<mrow><mo>(</mo><mfrac linethickness="0">{}{}</mfrac><mo>)</mo></mrow>"#,
+                    top, bottom
+                )))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.peek() == Some('[') {
+                    self.pos += 1;
+                    let mut items = Vec::new();
+                    loop {
+                        self.skip_whitespace();
+                        match self.peek() {
+                            Some(']') => break,
+                            None => bail!("unclosed `[` in `\\sqrt`"),
+                            _ => items.push(self.parse_atom(false)?),
+                        }
+                    }
+                    self.pos += 1;
+                    Some(mrow(items))
+                } else {
+                    None
+                };
+                let radicand = self.parse_arg()?;
+                Ok(Item::new(match index {
+                    Some(index) => format!("<mroot>{}{}</mroot>", radicand, index),
+                    None => format!("<msqrt>{}</msqrt>", radicand),
+                }))
+            }
+            "text" | "textrm" | "mbox" | "textnormal" => {
+                let text = self.raw_group()?;
+                Ok(Item::new(format!("<mtext>{}</mtext>", escape_html(&text))))
+            }
+            "textbf" => {
+                let text = self.raw_group()?;
+                Ok(Item::new(format!(
+                    r#"<mtext mathvariant="bold">{}</mtext>"#,
+                    escape_html(&text)
+                )))
+            }
+            "operatorname" => {
+                let text = self.raw_group()?;
+                Ok(Item::new(format!("<mi>{}</mi>", escape_html(&text))))
+            }
+            "left" => {
+                let open = self.delimiter()?;
+                let row = self.parse_row()?;
+                if !self.at_command("right") {
+                    bail!("missing `\\right`");
+                }
+                self.pos += "\\right".len();
+                let close = self.delimiter()?;
+                Ok(Item::new(format!(
+                    "<mrow>{}{}{}</mrow>",
+                    fence(&open),
+                    mrow(row),
+                    fence(&close)
+                )))
+            }
+            "begin" => self.parse_environment(),
+            "{" | "}" | "|" | "#" | "%" | "$" | "&" | "_" => Ok(self.token(
+                "mo",
+                if name == "|" { "\u{2016}" } else { name.as_str() },
+            )),
+            _ => bail!("unsupported command `\\{}`", name),
+        }
+    }
+
+    /// 读取 `\left`/`\right` 之后的定界符，`.` 表示空定界符
+    fn delimiter(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        match self.next() {
+            Some('.') => Ok(String::new()),
+            Some('\\') => {
+                let name = self.command_name()?;
+                let symbol = match name.as_str() {
+                    "{" | "lbrace" => "{",
+                    "}" | "rbrace" => "}",
+                    "|" | "Vert" | "lVert" | "rVert" => "\u{2016}",
+                    "vert" | "lvert" | "rvert" => "|",
+                    "langle" => "\u{27e8}",
+                    "rangle" => "\u{27e9}",
+                    "lfloor" => "\u{230a}",
+                    "rfloor" => "\u{230b}",
+                    "lceil" => "\u{2308}",
+                    "rceil" => "\u{2309}",
+                    _ => bail!("unsupported delimiter `\\{}`", name),
+                };
+                Ok(symbol.to_string())
+            }
+            Some(c) if "()[]|/<>".contains(c) => Ok(match c {
+                '<' => "\u{27e8}".to_string(),
+                '>' => "\u{27e9}".to_string(),
+                c => c.to_string(),
+            }),
+            Some(c) => bail!("unsupported delimiter `{}`", c),
+            None => bail!("missing delimiter"),
+        }
+    }
+
+    /// 解析 `\begin{env} ... \end{env}` 环境
+    fn parse_environment(&mut self) -> Result<Item> {
+        let env = self.raw_group()?;
+        let (open, close, align) = match env.as_str() {
+            "matrix" | "smallmatrix" => ("", "", None),
+            "pmatrix" => ("(", ")", None),
+            "bmatrix" => ("[", "]", None),
+            "Bmatrix" => ("{", "}", None),
+            "vmatrix" => ("|", "|", None),
+            "Vmatrix" => ("\u{2016}", "\u{2016}", None),
+            "cases" => ("{", "", Some("left left")),
+            "aligned" | "align" | "align*" | "split" | "gathered" => ("", "", Some("right left")),
+            "array" => {
+                // 列格式说明仅影响对齐方式，这里忽略
+                self.raw_group()?;
+                ("", "", None)
+            }
+            _ => bail!("unsupported environment `{}`", env),
+        };
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        loop {
+            let mut cells = Vec::new();
+            loop {
+                cells.push(mrow(self.parse_row()?));
+                if self.peek() == Some('&') {
+                    self.pos += 1;
+                } else {
+                    break;
+                }
+            }
+            rows.push(cells);
+            if self.at_line_break() {
+                self.pos += 2;
+            } else if self.at_command("end") {
+                self.pos += "\\end".len();
+                let end = self.raw_group()?;
+                if end != env {
+                    bail!("`\\begin{{{}}}` closed by `\\end{{{}}}`", env, end);
+                }
+                break;
+            } else {
+                bail!("missing `\\end{{{}}}`", env);
+            }
+        }
+        // 末尾的 `\\` 会产生一个空行
+        if rows.last().is_some_and(|cells| cells.len() == 1 && cells[0] == "<mrow></mrow>") {
+            rows.pop();
+        }
+
+        let mut table = match align {
+            Some(align) => format!(r#"<mtable columnalign="{}">"#, align),
+            None => "<mtable>".to_string(),
+        };
+        for cells in rows {
+            table.push_str("<mtr>");
+            for cell in cells {
+                table.push_str(&format!("<mtd>{}</mtd>", cell));
+            }
+            table.push_str("</mtr>");
+        }
+        table.push_str("</mtable>");
+
+        Ok(Item::new(format!(
+            "<mrow>{}{}{}</mrow>",
+            fence(open),
+            table,
+            fence(close)
+        )))
+    }
+}
+
+/// 生成定界符元素，空定界符不输出
+fn fence(symbol: &str) -> String {
+    if symbol.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<mo fence="true">{}</mo>"#, escape_html(symbol))
+    }
+}
+
+/// 希腊字母
+fn greek(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        _ => return None,
+    })
+}
+
+/// 作为标识符显示的符号
+fn identifier(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "emptyset" | "varnothing" => "∅",
+        "ell" => "ℓ",
+        "hbar" => "ℏ",
+        "aleph" => "ℵ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "wp" => "℘",
+        _ => return None,
+    })
+}
+
+/// 运算符与关系符号
+fn operator(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "pm" => "±",
+        "mp" => "∓",
+        "times" => "×",
+        "div" => "÷",
+        "cdot" => "⋅",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "ll" => "≪",
+        "gg" => "≫",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "supset" => "⊃",
+        "subseteq" => "⊆",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "neg" | "lnot" => "¬",
+        "forall" => "∀",
+        "exists" => "∃",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "mid" => "∣",
+        "parallel" => "∥",
+        "perp" => "⊥",
+        "angle" => "∠",
+        "triangle" => "△",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "prime" => "′",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "vert" => "|",
+        "Vert" => "‖",
+        "colon" => ":",
+        _ => return None,
+    })
+}
+
+/// 大型运算符，返回符号以及块级公式中是否把上下标放在正上方/正下方
+fn big_operator(name: &str) -> Option<(&'static str, bool)> {
+    Some(match name {
+        "sum" => ("∑", true),
+        "prod" => ("∏", true),
+        "coprod" => ("∐", true),
+        "bigcup" => ("⋃", true),
+        "bigcap" => ("⋂", true),
+        "bigoplus" => ("⨁", true),
+        "bigotimes" => ("⨂", true),
+        "int" => ("∫", false),
+        "iint" => ("∬", false),
+        "iiint" => ("∭", false),
+        "oint" => ("∮", false),
+        _ => return None,
+    })
+}
+
+/// 函数名，返回块级公式中是否把下标放在正下方（如 `\lim`）
+fn function(name: &str) -> Option<bool> {
+    match name {
+        "lim" | "limsup" | "liminf" | "max" | "min" | "sup" | "inf" | "det" | "gcd" | "Pr"
+        | "argmax" | "argmin" => Some(true),
+        "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "arcsin" | "arccos" | "arctan"
+        | "sinh" | "cosh" | "tanh" | "log" | "ln" | "lg" | "exp" | "arg" | "deg" | "dim"
+        | "ker" | "hom" | "mod" => Some(false),
+        _ => None,
+    }
+}
+
+/// 间距命令对应的宽度
+fn space(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "," | "thinspace" => "0.1667em",
+        ":" | ">" | "medspace" => "0.2222em",
+        ";" | "thickspace" => "0.2778em",
+        " " => "0.3333em",
+        "quad" => "1em",
+        "qquad" => "2em",
+        "!" | "negthinspace" => "-0.1667em",
+        _ => return None,
+    })
+}
+
+/// 字体命令对应的 `mathvariant`
+fn font_variant(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "mathbf" => "bold",
+        "mathit" => "italic",
+        "mathrm" => "normal",
+        "mathbb" => "double-struck",
+        "mathcal" => "script",
+        "mathfrak" => "fraktur",
+        "mathsf" => "sans-serif",
+        "mathtt" => "monospace",
+        "boldsymbol" | "bm" => "bold-italic",
+        _ => return None,
+    })
+}
+
+/// 重音命令，返回重音符号以及是否位于上方
+fn accent(name: &str) -> Option<(&'static str, bool)> {
+    Some(match name {
+        "hat" | "widehat" => ("^", true),
+        "bar" => ("¯", true),
+        "overline" => ("‾", true),
+        "vec" | "overrightarrow" => ("→", true),
+        "overleftarrow" => ("←", true),
+        "dot" => ("˙", true),
+        "ddot" => ("¨", true),
+        "tilde" | "widetilde" => ("~", true),
+        "check" => ("ˇ", true),
+        "breve" => ("˘", true),
+        "underline" => ("_", false),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pulldown_cmark::{html, Options, Parser};
+
+    /// 只取 `<semantics>` 内的公式部分，便于断言
+    fn body(latex: &str) -> String {
+        let mathml = latex_to_mathml(latex, false).unwrap();
+        let start = mathml.find("<semantics>").unwrap() + "<semantics>".len();
+        let end = mathml.find("<annotation").unwrap();
+        mathml[start..end].to_string()
+    }
+
+    #[test]
+    fn test_scripts_and_numbers() {
+        assert_eq!(
+            body("x^2 + y_{10}"),
+            "<mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><msub><mi>y</mi><mn>10</mn></msub></mrow>"
+        );
+        assert_eq!(
+            body("a_i^2"),
+            "<msubsup><mi>a</mi><mi>i</mi><mn>2</mn></msubsup>"
+        );
+        assert_eq!(body("x^23"), "<mrow><msup><mi>x</mi><mn>2</mn></msup><mn>3</mn></mrow>");
+    }
+
+    #[test]
+    fn test_frac_sqrt_greek() {
+        assert_eq!(
+            body(r"\frac{\alpha}{2}"),
+            "<mfrac><mi>α</mi><mn>2</mn></mfrac>"
+        );
+        assert_eq!(body(r"\frac12"), "<mfrac><mn>1</mn><mn>2</mn></mfrac>");
+        assert_eq!(
+            body(r"\sqrt[3]{x}"),
+            "<mroot><mi>x</mi><mn>3</mn></mroot>"
+        );
+    }
+
+    #[test]
+    fn test_big_operator_limits() {
+        let display = latex_to_mathml(r"\sum_{i=1}^n i", true).unwrap();
+        assert!(display.contains(r#"display="block""#));
+        assert!(display.contains("<munderover><mo>∑</mo>"));
+        assert!(body(r"\sum_{i=1}^n i").contains("<msubsup><mo>∑</mo>"));
+    }
+
+    #[test]
+    fn test_left_right_and_text() {
+        assert_eq!(
+            body(r"\left( x \right.\text{ if } x<0"),
+            "<mrow><mrow><mo fence=\"true\">(</mo><mi>x</mi></mrow><mtext> if </mtext><mi>x</mi><mo>&lt;</mo><mn>0</mn></mrow>"
+        );
+    }
+
+    #[test]
+    fn test_font_variant() {
+        assert_eq!(
+            body(r"\mathbb{R}"),
+            r#"<mi mathvariant="double-struck">R</mi>"#
+        );
+    }
+
+    #[test]
+    fn test_environment() {
+        assert_eq!(
+            body(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"),
+            "<mrow><mo fence=\"true\">(</mo><mtable><mtr><mtd><mi>a</mi></mtd><mtd><mi>b</mi></mtd></mtr><mtr><mtd><mi>c</mi></mtd><mtd><mi>d</mi></mtd></mtr></mtable><mo fence=\"true\">)</mo></mrow>"
+        );
+        assert!(body("\\begin{cases} 1 & x>0 \\\\ 0 & x\\le 0 \\\\ \\end{cases}").contains("columnalign"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(latex_to_mathml(r"\unknown", false).is_err());
+        assert!(latex_to_mathml(r"\frac{1}", false).is_err());
+        assert!(latex_to_mathml("x}", false).is_err());
+        assert!(latex_to_mathml("x^1^2", false).is_err());
+        assert!(latex_to_mathml(r"\begin{matrix} a", false).is_err());
+
+        // 嵌套过深时返回错误而不是栈溢出
+        let deep = format!("{}x{}", "{".repeat(5000), "}".repeat(5000));
+        assert!(latex_to_mathml(&deep, false).is_err());
+        let deep = format!("{}x{}", r"\sqrt{".repeat(5000), "}".repeat(5000));
+        assert!(latex_to_mathml(&deep, false).is_err());
+        let nested = format!("{}x{}", "{".repeat(10), "}".repeat(10));
+        assert!(latex_to_mathml(&nested, false).is_ok());
+    }
+
+    #[test]
+    fn test_process_math_events() {
+        let parser = Parser::new_ext("质能方程 $E=mc^2$，以及 $\\bad$。\n\n$$\\int_0^1 x\\,dx$$", Options::ENABLE_MATH);
+        let mut output = String::new();
+        html::push_html(&mut output, process_math(parser).into_iter());
+        assert!(output.contains(r#"<math xmlns="http://www.w3.org/1998/Math/MathML" display="inline">"#));
+        assert!(output.contains(r#"<annotation encoding="application/x-tex">E=mc^2</annotation>"#));
+        assert!(output.contains(r#"display="block""#));
+        assert!(output.contains(r#"<code class="math-error" title="unsupported command `\bad`">$\bad$</code>"#));
+    }
+}
@@ -1,4 +1,12 @@
 pub mod date_format;
 pub mod date;
 pub mod html;
-pub mod pinyin;
\ No newline at end of file
+pub mod pinyin;
+pub mod heading;
+pub mod highlight;
+pub mod math;
+pub mod shortcode;
+pub mod images;
+pub mod obsidian;
+pub mod links;
+pub mod sanitize;
@@ -0,0 +1,630 @@
+use crate::models::wiki::WikiIndex;
+use crate::models::Post;
+use crate::utils::html::escape_html;
+use crate::utils::pinyin;
+use pulldown_cmark::{
+    BlockQuoteKind, CowStr, Event, LinkType, Options, Parser, Tag, TagEnd, TextMergeStream,
+};
+
+/// 处理Markdown事件流中的 Obsidian 语法
+///
+/// - `[[文章]]`、`[[文章|别名]]`、`[[文章#小节]]` 转换为指向文章的链接，
+///   按标题、slug或文件名查找，找不到时输出带 `wikilink-unresolved` 类名的文本
+/// - `![[图片.png]]` 转换为图片，交由后续的图片处理生成缩略图
+/// - 单独成段的 `![[文章]]` 嵌入被引用文章的正文，`embed_note` 为 `None` 时输出为链接
+/// - `> [!note] 标题` 形式的提示框转换为 admonition 块，`[!note]-`、`[!note]+`
+///   以及 `spoiler` 类型使用可折叠的 `<details>`
+///
+/// 代码块与已有链接中的内容不做处理。
+///
+/// # 参数
+/// * `events` - Markdown解析器产生的事件流
+/// * `wiki` - 双链索引
+/// * `embed_note` - 渲染被嵌入文章的正文HTML
+///
+/// # 返回
+/// * `Vec<Event>` - 处理后的事件列表
+pub fn process_obsidian<'a>(
+    events: impl Iterator<Item = Event<'a>>,
+    wiki: &WikiIndex,
+    embed_note: Option<&dyn Fn(&Post) -> String>,
+) -> Vec<Event<'a>> {
+    let mut output = Vec::new();
+    // 每层引用块是否为提示框：None 为普通引用块，Some(true) 为可折叠提示框
+    let mut blockquotes: Vec<Option<bool>> = Vec::new();
+    let mut code_depth = 0;
+    let mut link_depth = 0;
+
+    let mut events = TextMergeStream::new(events).peekable();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(Tag::BlockQuote(kind)) => {
+                let callout = match kind {
+                    Some(kind) => Some((Callout::from_kind(kind), None)),
+                    None if matches!(events.peek(), Some(Event::Start(Tag::Paragraph))) => {
+                        let paragraph = events.next();
+                        let callout = match events.peek() {
+                            Some(Event::Text(text)) => Callout::parse(text),
+                            _ => None,
+                        };
+                        match callout {
+                            Some((callout, rest)) => {
+                                events.next();
+                                Some((callout, Some(rest)))
+                            }
+                            None => {
+                                output.push(Event::Start(Tag::BlockQuote(None)));
+                                output.extend(paragraph);
+                                blockquotes.push(None);
+                                continue;
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                let Some((callout, title)) = callout else {
+                    output.push(Event::Start(Tag::BlockQuote(kind)));
+                    blockquotes.push(None);
+                    continue;
+                };
+
+                // 标题为标记之后到行尾的内容
+                let mut title_events = Vec::new();
+                let mut reopen_paragraph = false;
+                if let Some(rest) = title {
+                    if !rest.is_empty() {
+                        push_text(&mut title_events, &rest, wiki);
+                    }
+                    for event in events.by_ref() {
+                        match event {
+                            Event::SoftBreak | Event::HardBreak => {
+                                reopen_paragraph = true;
+                                break;
+                            }
+                            Event::End(TagEnd::Paragraph) => break,
+                            Event::Text(text) => push_text(&mut title_events, &text, wiki),
+                            event => title_events.push(event),
+                        }
+                    }
+                }
+                if title_events.is_empty() {
+                    title_events.push(Event::Text(CowStr::from(callout.default_title())));
+                }
+
+                let (open, title_tag) = if callout.collapsible() {
+                    (
+                        format!(
+                            "<details class=\"admonition {}\"{}>",
+                            callout.kind,
+                            if callout.expanded { " open" } else { "" }
+                        ),
+                        "summary",
+                    )
+                } else {
+                    (format!("<div class=\"admonition {}\">", callout.kind), "p")
+                };
+                output.push(Event::Html(CowStr::from(format!(
+                    "{}<{} class=\"admonition-title\">",
+                    open, title_tag
+                ))));
+                output.extend(title_events);
+                output.push(Event::Html(CowStr::from(format!("</{}>\n", title_tag))));
+                if reopen_paragraph {
+                    output.push(Event::Start(Tag::Paragraph));
+                }
+                blockquotes.push(Some(callout.collapsible()));
+            }
+            Event::End(TagEnd::BlockQuote(kind)) => match blockquotes.pop().flatten() {
+                Some(true) => output.push(Event::Html(CowStr::from("</details>\n"))),
+                Some(false) => output.push(Event::Html(CowStr::from("</div>\n"))),
+                None => output.push(Event::End(TagEnd::BlockQuote(kind))),
+            },
+            Event::Start(tag @ Tag::CodeBlock(_)) => {
+                code_depth += 1;
+                output.push(Event::Start(tag));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                code_depth -= 1;
+                output.push(Event::End(TagEnd::CodeBlock));
+            }
+            Event::Start(tag @ (Tag::Link { .. } | Tag::Image { .. })) => {
+                link_depth += 1;
+                output.push(Event::Start(tag));
+            }
+            Event::End(end @ (TagEnd::Link | TagEnd::Image)) => {
+                link_depth -= 1;
+                output.push(Event::End(end));
+            }
+            Event::Text(text) if code_depth == 0 && link_depth == 0 => {
+                // 单独成段的文章嵌入替换整个段落
+                let standalone = matches!(output.last(), Some(Event::Start(Tag::Paragraph)))
+                    && matches!(events.peek(), Some(Event::End(TagEnd::Paragraph)));
+                let embedded = match (standalone, embed_note, split_wikilinks(&text).as_slice()) {
+                    (true, Some(embed_note), [Piece::Link(link)])
+                        if link.embed && !WikiIndex::is_attachment(link.target) =>
+                    {
+                        wiki.resolve(link.target).map(|post| {
+                            format!(
+                                "<div class=\"embed\"><p class=\"embed-title\"><a href=\"{}\">{}</a></p>\n{}</div>\n",
+                                post_url(post, None),
+                                escape_html(&post.front_matter.title),
+                                embed_note(post)
+                            )
+                        })
+                    }
+                    _ => None,
+                };
+                match embedded {
+                    Some(html) => {
+                        output.pop();
+                        events.next();
+                        output.push(Event::Html(CowStr::from(html)));
+                    }
+                    None => push_text(&mut output, &text, wiki),
+                }
+            }
+            event => output.push(event),
+        }
+    }
+    output
+}
+
+/// 检查文本中的双链，返回无法解析的链接与附件
+///
+/// # 参数
+/// * `content` - Markdown 原文
+/// * `wiki` - 双链索引
+pub fn diagnose(content: &str, wiki: &WikiIndex) -> Vec<String> {
+    let mut diagnostics = Vec::new();
+    for_each_wikilink(content, |link| {
+        if WikiIndex::is_attachment(link.target) {
+            if wiki.attachment(link.target).is_none() {
+                diagnostics.push(format!("missing attachment `{}`", link.source));
+            }
+        } else if !link.target.is_empty() && wiki.resolve(link.target).is_none() {
+            diagnostics.push(format!("unresolved wikilink `{}`", link.source));
+        }
+    });
+    diagnostics
+}
+
+/// 提取 `![[图片]]` 嵌入的图片地址，供图片处理预先生成缩略图
+pub fn embedded_images(content: &str, wiki: &WikiIndex) -> Vec<String> {
+    let mut sources = Vec::new();
+    for_each_wikilink(content, |link| {
+        if link.embed && WikiIndex::is_attachment(link.target) {
+            sources.extend(wiki.attachment(link.target).map(str::to_string));
+        }
+    });
+    sources
+}
+
+/// 遍历正文中（代码块之外）的所有双链
+fn for_each_wikilink(content: &str, mut f: impl FnMut(&WikiLink)) {
+    let mut code_depth = 0;
+    for event in TextMergeStream::new(Parser::new_ext(content, Options::empty())) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => code_depth += 1,
+            Event::End(TagEnd::CodeBlock) => code_depth -= 1,
+            Event::Text(text) if code_depth == 0 => {
+                for piece in split_wikilinks(&text) {
+                    if let Piece::Link(link) = piece {
+                        f(&link);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 文章的访问地址，可带小节锚点
+fn post_url(post: &Post, heading: Option<&str>) -> String {
+    match heading {
+        Some(heading) => format!("/post/{}/#{}", post.url, pinyin::slugify(heading)),
+        None => format!("/post/{}/", post.url),
+    }
+}
+
+/// 输出文本，其中的双链转换为链接或图片
+fn push_text<'a>(output: &mut Vec<Event<'a>>, text: &str, wiki: &WikiIndex) {
+    let pieces = split_wikilinks(text);
+    if let [Piece::Text(_)] = pieces.as_slice() {
+        output.push(Event::Text(CowStr::from(text.to_string())));
+        return;
+    }
+    for piece in pieces {
+        match piece {
+            Piece::Text(text) => output.push(Event::Text(CowStr::from(text.to_string()))),
+            Piece::Link(link) => push_link(output, &link, wiki),
+        }
+    }
+}
+
+/// 输出一个双链
+fn push_link<'a>(output: &mut Vec<Event<'a>>, link: &WikiLink, wiki: &WikiIndex) {
+    if WikiIndex::is_attachment(link.target) {
+        let Some(url) = wiki.attachment(link.target) else {
+            output.push(unresolved(link.source));
+            return;
+        };
+        // `![[图片.png|300]]` 中的尺寸由图片处理决定，不作为替代文本
+        let text = link
+            .alias
+            .filter(|alias| !alias.chars().all(|c| c.is_ascii_digit() || c == 'x'))
+            .unwrap_or_default();
+        let tag = if link.embed {
+            Tag::Image {
+                link_type: LinkType::Inline,
+                dest_url: CowStr::from(url.to_string()),
+                title: CowStr::Borrowed(""),
+                id: CowStr::Borrowed(""),
+            }
+        } else {
+            Tag::Link {
+                link_type: LinkType::Inline,
+                dest_url: CowStr::from(url.to_string()),
+                title: CowStr::Borrowed(""),
+                id: CowStr::Borrowed(""),
+            }
+        };
+        let end = if link.embed { TagEnd::Image } else { TagEnd::Link };
+        let text = if text.is_empty() && !link.embed {
+            link.target
+        } else {
+            text
+        };
+        output.push(Event::Start(tag));
+        if !text.is_empty() {
+            output.push(Event::Text(CowStr::from(text.to_string())));
+        }
+        output.push(Event::End(end));
+        return;
+    }
+
+    let (url, default_text) = if link.target.is_empty() {
+        // `[[#小节]]` 指向当前文章
+        let heading = link.heading.unwrap_or_default();
+        (format!("#{}", pinyin::slugify(heading)), heading.to_string())
+    } else {
+        let Some(post) = wiki.resolve(link.target) else {
+            output.push(unresolved(link.alias.unwrap_or(link.source)));
+            return;
+        };
+        let text = match link.heading {
+            Some(heading) => format!("{} > {}", link.target, heading),
+            None => link.target.to_string(),
+        };
+        (post_url(post, link.heading), text)
+    };
+
+    output.push(Event::Start(Tag::Link {
+        link_type: LinkType::Inline,
+        dest_url: CowStr::from(url),
+        title: CowStr::Borrowed(""),
+        id: CowStr::Borrowed(""),
+    }));
+    output.push(Event::Text(CowStr::from(
+        link.alias.map(str::to_string).unwrap_or(default_text),
+    )));
+    output.push(Event::End(TagEnd::Link));
+}
+
+/// 无法解析的双链
+fn unresolved<'a>(text: &str) -> Event<'a> {
+    Event::InlineHtml(CowStr::from(format!(
+        "<span class=\"wikilink-unresolved\" title=\"未找到对应的文章或文件\">{}</span>",
+        escape_html(text)
+    )))
+}
+
+/// 文本片段：普通文本或双链
+#[derive(Debug, PartialEq)]
+pub enum Piece<'a> {
+    Text(&'a str),
+    Link(WikiLink<'a>),
+}
+
+/// 双链 `[[目标#小节|别名]]`
+///
+/// # 字段说明
+/// * `embed` - 是否为 `![[...]]` 嵌入
+/// * `target` - 目标文章或文件，`[[#小节]]` 时为空
+/// * `heading` - 小节标题
+/// * `alias` - 显示文本
+/// * `source` - 原文
+#[derive(Debug, PartialEq)]
+pub struct WikiLink<'a> {
+    pub embed: bool,
+    pub target: &'a str,
+    pub heading: Option<&'a str>,
+    pub alias: Option<&'a str>,
+    pub source: &'a str,
+}
+
+/// 将文本切分为普通文本与双链
+pub fn split_wikilinks(text: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut start = 0; // 尚未输出的文本起点
+    let mut offset = 0; // 下一次查找的起点
+
+    while let Some(open) = text[offset..].find("[[").map(|i| offset + i) {
+        let Some(close) = text[open + 2..].find("]]").map(|i| open + 2 + i) else {
+            break;
+        };
+        let inner = &text[open + 2..close];
+        if inner.trim().is_empty() || inner.contains('[') {
+            offset = open + 2;
+            continue;
+        }
+
+        let embed = text[..open].ends_with('!');
+        let link_start = if embed { open - 1 } else { open };
+        if link_start > start {
+            pieces.push(Piece::Text(&text[start..link_start]));
+        }
+
+        let (target, alias) = match inner.split_once('|') {
+            // 表格中的双链写作 `[[目标\|别名]]`
+            Some((target, alias)) => (target.trim_end_matches('\\'), Some(alias.trim())),
+            None => (inner, None),
+        };
+        let (target, heading) = match target.split_once('#') {
+            Some((target, heading)) => (target, Some(heading.trim())),
+            None => (target, None),
+        };
+        pieces.push(Piece::Link(WikiLink {
+            embed,
+            target: target.trim(),
+            heading,
+            alias: alias.filter(|alias| !alias.is_empty()),
+            source: &text[link_start..close + 2],
+        }));
+        start = close + 2;
+        offset = start;
+    }
+
+    if start < text.len() || pieces.is_empty() {
+        pieces.push(Piece::Text(&text[start..]));
+    }
+    pieces
+}
+
+/// Obsidian 提示框
+///
+/// # 字段说明
+/// * `kind` - 类型，别名已归一化，如 `hint` 归为 `tip`
+/// * `foldable` - 是否带有 `+`/`-` 折叠标记
+/// * `expanded` - 可折叠时是否默认展开
+#[derive(Debug, PartialEq)]
+struct Callout {
+    kind: String,
+    foldable: bool,
+    expanded: bool,
+}
+
+impl Callout {
+    /// 解析段落开头的 `[!type]` 标记，返回提示框与标记之后的标题文本
+    fn parse(text: &str) -> Option<(Self, String)> {
+        let rest = text.strip_prefix("[!")?;
+        let end = rest.find(']')?;
+        let kind = rest[..end].trim().to_lowercase();
+        if kind.is_empty()
+            || !kind
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return None;
+        }
+
+        let rest = &rest[end + 1..];
+        let (foldable, expanded, title) = match rest.chars().next() {
+            Some('-') => (true, false, &rest[1..]),
+            Some('+') => (true, true, &rest[1..]),
+            _ => (false, false, rest),
+        };
+        Some((
+            Self {
+                kind: Self::normalize(&kind).to_string(),
+                foldable,
+                expanded,
+            },
+            title.trim_start().to_string(),
+        ))
+    }
+
+    /// GitHub 风格的 `> [!NOTE]` 提示框
+    fn from_kind(kind: BlockQuoteKind) -> Self {
+        let kind = match kind {
+            BlockQuoteKind::Note => "note",
+            BlockQuoteKind::Tip | BlockQuoteKind::Important => "tip",
+            BlockQuoteKind::Warning => "warning",
+            BlockQuoteKind::Caution => "danger",
+        };
+        Self {
+            kind: kind.to_string(),
+            foldable: false,
+            expanded: false,
+        }
+    }
+
+    /// 将 Obsidian 的类型别名归一化
+    fn normalize(kind: &str) -> &str {
+        match kind {
+            "hint" | "important" => "tip",
+            "caution" | "attention" => "warning",
+            "error" => "danger",
+            "summary" | "tldr" => "abstract",
+            "todo" => "info",
+            "check" | "done" => "success",
+            "help" | "faq" => "question",
+            "fail" | "missing" => "failure",
+            "cite" => "quote",
+            kind => kind,
+        }
+    }
+
+    /// 剧透提示框总是可折叠
+    fn collapsible(&self) -> bool {
+        self.foldable || self.kind == "spoiler"
+    }
+
+    /// 未填写标题时使用的默认标题
+    fn default_title(&self) -> String {
+        let title = match self.kind.as_str() {
+            "note" => "注意",
+            "tip" => "提示",
+            "info" => "信息",
+            "warning" => "警告",
+            "danger" => "危险",
+            "spoiler" => "剧透",
+            "abstract" => "摘要",
+            "success" => "成功",
+            "question" => "问题",
+            "failure" => "失败",
+            "bug" => "缺陷",
+            "example" => "示例",
+            "quote" => "引用",
+            kind => kind,
+        };
+        title.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::test_util::post;
+    use pulldown_cmark::html;
+
+    fn wiki() -> WikiIndex {
+        WikiIndex::new(
+            vec![
+                post("rust-async", "title: Rust 异步编程", "异步正文"),
+                post("day1", "title: 蓝桉树下", "故事"),
+            ],
+            std::path::Path::new("static"),
+        )
+    }
+
+    fn render(content: &str) -> String {
+        let wiki = wiki();
+        let embed = |post: &Post| format!("<p>{}</p>\n", post.content);
+        let events = process_obsidian(
+            Parser::new_ext(content, Options::ENABLE_GFM),
+            &wiki,
+            Some(&embed),
+        );
+        let mut output = String::new();
+        html::push_html(&mut output, events.into_iter());
+        output
+    }
+
+    #[test]
+    fn test_split_wikilinks() {
+        let pieces = split_wikilinks("见 ![[图.png|300]] 与 [[文章#小节|别名]]");
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(
+            pieces[3],
+            Piece::Link(WikiLink {
+                embed: false,
+                target: "文章",
+                heading: Some("小节"),
+                alias: Some("别名"),
+                source: "[[文章#小节|别名]]",
+            })
+        );
+        let Piece::Link(embed) = &pieces[1] else {
+            panic!("expected link");
+        };
+        assert!(embed.embed);
+        assert_eq!(split_wikilinks("[[ ]] 与 [x]"), vec![Piece::Text("[[ ]] 与 [x]")]);
+    }
+
+    #[test]
+    fn test_resolve_by_title_slug_and_file_name() {
+        assert_eq!(
+            render("[[蓝桉树下]] [[rust-yi-bu-bian-cheng|异步]] [[day1.md#第一章]]"),
+            "<p><a href=\"/post/day1/\">蓝桉树下</a> <a href=\"/post/rust-async/\">异步</a> \
+             <a href=\"/post/day1/#di-yi-zhang\">day1.md &gt; 第一章</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_unresolved_and_code() {
+        assert_eq!(
+            render("[[不存在|别名]] `[[代码]]`\n\n```\n[[代码块]]\n```\n"),
+            "<p><span class=\"wikilink-unresolved\" title=\"未找到对应的文章或文件\">别名</span> <code>[[代码]]</code></p>\n\
+             <pre><code>[[代码块]]\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn test_embed_note() {
+        assert_eq!(
+            render("![[蓝桉树下]]\n\n正文 ![[蓝桉树下]]"),
+            "<div class=\"embed\"><p class=\"embed-title\"><a href=\"/post/day1/\">蓝桉树下</a></p>\n<p>故事</p>\n</div>\n\
+             <p>正文 <a href=\"/post/day1/\">蓝桉树下</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_embed_image() {
+        let html = render("![[favicon-32x32.png]]\n\n![[favicon-16x16.png|图标]]");
+        assert_eq!(
+            html,
+            "<p><img src=\"/static/favicon-32x32.png\" alt=\"\" /></p>\n\
+             <p><img src=\"/static/favicon-16x16.png\" alt=\"图标\" /></p>\n"
+        );
+        assert!(render("![[missing.png]]").contains("wikilink-unresolved"));
+    }
+
+    #[test]
+    fn test_callouts() {
+        assert_eq!(
+            render("> [!hint] 小技巧 **加粗**\n> 内容 [[蓝桉树下]]\n"),
+            "<div class=\"admonition tip\"><p class=\"admonition-title\">小技巧 <strong>加粗</strong></p>\n\
+             <p>内容 <a href=\"/post/day1/\">蓝桉树下</a></p>\n</div>\n"
+        );
+        assert_eq!(
+            render("> [!warning]-\n> 折叠内容\n"),
+            "<details class=\"admonition warning\"><summary class=\"admonition-title\">警告</summary>\n\
+             <p>折叠内容</p>\n</details>\n"
+        );
+        assert_eq!(
+            render("> [!spoiler]+ 结局\n>\n> 凶手是管家\n"),
+            "<details class=\"admonition spoiler\" open><summary class=\"admonition-title\">结局</summary>\n\
+             <p>凶手是管家</p>\n</details>\n"
+        );
+        // GitHub 风格的提示框与普通引用块
+        assert_eq!(
+            render("> [!NOTE]\n> 内容\n\n> 普通引用\n"),
+            "<div class=\"admonition note\"><p class=\"admonition-title\">注意</p>\n<p>内容</p>\n</div>\n\
+             <blockquote>\n<p>普通引用</p>\n</blockquote>\n"
+        );
+    }
+
+    #[test]
+    fn test_embedded_images() {
+        assert_eq!(
+            embedded_images("![[favicon-32x32.png]] [[favicon-16x16.png]]", &wiki()),
+            vec!["/static/favicon-32x32.png".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let diagnostics = diagnose(
+            "[[蓝桉树下]] [[不存在]] [[#本文小节]] ![[missing.png]]\n\n```\n[[忽略]]\n```\n",
+            &wiki(),
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                "unresolved wikilink `[[不存在]]`".to_string(),
+                "missing attachment `![[missing.png]]`".to_string(),
+            ]
+        );
+    }
+}
@@ -20,6 +20,43 @@ pub fn to_pinyin(text: &str) -> Vec<String> {
     result
 }
 
+/// 将标题等文本转换为URL友好的slug
+///
+/// - 汉字转换为不带声调的拼音，每个字一个音节，`ü` 写作 `v`
+/// - 拉丁字母和数字按单词保留并转为小写，带变音符号的字母去掉变音符号（`Café` → `cafe`）
+/// - 假名、谚文等其他文字音译为ASCII后并入当前单词，无法音译的字符丢弃
+/// - 空白与标点作为分隔符，各部分之间以 `-` 连接
+///
+/// 结果只包含ASCII小写字母、数字和 `-`，可能为空字符串
+pub fn slugify(text: &str) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut word = String::new();
+
+    for c in text.chars() {
+        if let Some(pinyin) = c.to_pinyin() {
+            if !word.is_empty() {
+                parts.push(std::mem::take(&mut word));
+            }
+            parts.push(pinyin.plain().replace('ü', "v"));
+        } else if c.is_ascii_alphanumeric() {
+            word.push(c.to_ascii_lowercase());
+        } else if let Some(ascii) = deunicode::deunicode_char(c).filter(|_| c.is_alphanumeric()) {
+            word.extend(
+                ascii
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .map(|c| c.to_ascii_lowercase()),
+            );
+        } else if !word.is_empty() {
+            parts.push(std::mem::take(&mut word));
+        }
+    }
+    if !word.is_empty() {
+        parts.push(word);
+    }
+    parts.join("-")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,4 +95,32 @@ mod tests {
             vec!["H", "e", "l", "l", "o",  " ", "zhong", "guo"]
         );
     }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("蓝桉树下"), "lan-an-shu-xia");
+        assert_eq!(slugify("Hello World"), "hello-world");
+        assert_eq!(slugify("Rust 异步编程: async/await"), "rust-yi-bu-bian-cheng-async-await");
+        assert_eq!(slugify("Tokio中的Runtime"), "tokio-zhong-de-runtime");
+        assert_eq!(slugify("共振频率"), "gong-zhen-pin-lv");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_slugify_is_ascii() {
+        assert_eq!(slugify("Café au lait"), "cafe-au-lait");
+        assert_eq!(slugify("カタカナ"), "katakana");
+        assert_eq!(slugify("한글 Rust"), "hangeul-rust");
+        assert_eq!(slugify("Ünïcödé蓝桉"), "unicode-lan-an");
+        assert_eq!(slugify("🦀"), "");
+        for text in ["Straße", "Ελληνικά", "русский", "𠀀㐀", "١٢٣"] {
+            let slug = slugify(text);
+            assert!(
+                slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+                "{} → {}",
+                text,
+                slug
+            );
+        }
+    }
 }
@@ -0,0 +1,196 @@
+use crate::models::markdown::HtmlPolicy;
+use ammonia::Builder;
+use once_cell::sync::Lazy;
+
+/// 渲染器生成的 MathML 标签
+const MATHML_TAGS: &[&str] = &[
+    "math", "semantics", "annotation", "mrow", "mi", "mn", "mo", "ms", "mtext", "mspace",
+    "msub", "msup", "msubsup", "mfrac", "msqrt", "mroot", "munder", "mover", "munderover",
+    "mtable", "mtr", "mtd", "mstyle", "mpadded", "mphantom", "menclose", "merror",
+];
+
+/// MathML 标签允许的属性
+const MATHML_ATTRIBUTES: &[&str] = &[
+    "display", "xmlns", "encoding", "mathvariant", "fence", "separator", "stretchy",
+    "linethickness", "columnalign", "width", "lspace", "rspace", "accent", "accentunder",
+];
+
+/// 严格策略下 `style` 属性允许的CSS属性：表格对齐与代码高亮的内联样式
+const STRICT_STYLE_PROPERTIES: &[&str] = &[
+    "text-align",
+    "color",
+    "background-color",
+    "font-weight",
+    "font-style",
+    "text-decoration",
+];
+
+/// 严格策略：ammonia 默认白名单加上渲染器自身生成的标签与属性
+static STRICT: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = base_builder();
+    builder.filter_style_properties(STRICT_STYLE_PROPERTIES.iter().copied().collect());
+    builder
+});
+
+/// 宽松策略：在严格策略基础上允许内嵌框架、音视频与任意样式
+static TRUSTED: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = base_builder();
+    builder
+        .add_tags(["iframe", "video", "audio", "track"])
+        .add_tag_attributes(
+            "iframe",
+            [
+                "src",
+                "width",
+                "height",
+                "allow",
+                "allowfullscreen",
+                "frameborder",
+                "loading",
+                "referrerpolicy",
+                "sandbox",
+            ],
+        )
+        .add_tag_attributes(
+            "video",
+            [
+                "src", "poster", "width", "height", "controls", "preload", "loop", "muted",
+                "autoplay", "playsinline",
+            ],
+        )
+        .add_tag_attributes("audio", ["src", "controls", "preload", "loop", "muted"])
+        .add_tag_attributes("source", ["src"])
+        .add_tag_attributes("track", ["src", "kind", "srclang", "label", "default"]);
+    builder
+});
+
+/// 两种策略共用的白名单
+fn base_builder() -> Builder<'static> {
+    let mut builder = Builder::default();
+    builder
+        // 外部链接的 rel 由链接处理步骤按配置生成，不再统一覆盖
+        .link_rel(None)
+        .add_tags(["picture", "source", "input"])
+        .add_tags(MATHML_TAGS.iter().copied())
+        .add_generic_attributes(["id", "class", "style", "aria-hidden"])
+        .add_tag_attributes("a", ["rel", "target"])
+        .add_tag_attributes("img", ["srcset", "sizes", "loading", "decoding"])
+        .add_tag_attributes("source", ["srcset", "sizes", "type", "media"])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .add_tag_attributes("details", ["open"])
+        .add_tag_attributes("code", ["data-lang"]);
+    for tag in MATHML_TAGS {
+        builder.add_tag_attributes(tag, MATHML_ATTRIBUTES.iter().copied());
+    }
+    builder
+}
+
+/// 按策略过滤HTML片段
+///
+/// # 参数
+/// * `html` - 渲染后的正文HTML
+/// * `policy` - 过滤策略
+///
+/// # 返回
+/// * `String` - 过滤后的HTML
+pub fn sanitize(html: &str, policy: HtmlPolicy) -> String {
+    match policy {
+        HtmlPolicy::Off => html.to_string(),
+        HtmlPolicy::Trusted => TRUSTED.clean(html).to_string(),
+        HtmlPolicy::Strict => STRICT.clean(html).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 常见的XSS载荷，两种策略都必须清除
+    const XSS_PAYLOADS: &[&str] = &[
+        "<script>alert(1)</script>",
+        "<img src=x onerror=alert(1)>",
+        "<a href=\"javascript:alert(1)\">x</a>",
+        "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+        "<a href=\"&#106;avascript:alert(1)\">x</a>",
+        "<svg onload=alert(1)>",
+        "<iframe src=\"javascript:alert(1)\"></iframe>",
+        "<body onload=alert(1)>",
+        "<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>",
+        "<object data=\"data:text/html,<script>alert(1)</script>\"></object>",
+        "<form action=\"javascript:alert(1)\"><button>x</button></form>",
+        "<details open ontoggle=alert(1)>",
+        "<input autofocus onfocus=alert(1)>",
+        "<meta http-equiv=\"refresh\" content=\"0;url=javascript:alert(1)\">",
+        "<a href=\"data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==\">x</a>",
+    ];
+
+    #[test]
+    fn test_xss_payloads_removed() {
+        for policy in [HtmlPolicy::Trusted, HtmlPolicy::Strict] {
+            for payload in XSS_PAYLOADS {
+                let html = sanitize(payload, policy).to_lowercase();
+                for needle in ["<script", "javascript:", "onerror", "onload", "onfocus", "ontoggle", "data:"] {
+                    assert!(
+                        !html.contains(needle),
+                        "{:?} kept `{}` in {:?}: {}",
+                        policy,
+                        needle,
+                        payload,
+                        html
+                    );
+                }
+                assert!(!html.contains("<svg") && !html.contains("<object") && !html.contains("<meta"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_renderer_output_kept() {
+        let html = concat!(
+            "<h2 id=\"a\">标题<a class=\"heading-anchor\" href=\"#a\" aria-hidden=\"true\">#</a></h2>\n",
+            "<p><a href=\"https://x.com/\" class=\"external-link\" rel=\"nofollow noopener\" target=\"_blank\">x</a></p>\n",
+            "<pre class=\"syn-code\"><code class=\"language-rust\" data-lang=\"rust\"><span class=\"line\">fn</span></code></pre>\n",
+            "<picture><source type=\"image/webp\" srcset=\"/images/a-480.webp 480w\" sizes=\"100vw\">",
+            "<img src=\"/static/a.png\" alt=\"a\" width=\"10\" height=\"10\" loading=\"lazy\" decoding=\"async\"></picture>\n",
+            "<details class=\"admonition tip\" open=\"\"><summary class=\"admonition-title\">提示</summary></details>\n",
+            "<ul>\n<li><input disabled=\"\" type=\"checkbox\" checked=\"\">完成</li>\n</ul>\n",
+            "<table><thead><tr><th style=\"text-align: center\">列</th></tr></thead></table>\n",
+            "<math display=\"block\"><mfrac><mi>a</mi><mn>2</mn></mfrac></math>\n",
+        );
+        for policy in [HtmlPolicy::Trusted, HtmlPolicy::Strict] {
+            let cleaned = sanitize(html, policy);
+            for kept in [
+                "id=\"a\"",
+                "aria-hidden=\"true\"",
+                "rel=\"nofollow noopener\" target=\"_blank\"",
+                "data-lang=\"rust\"",
+                "<span class=\"line\">",
+                "<source type=\"image/webp\" srcset=\"/images/a-480.webp 480w\" sizes=\"100vw\">",
+                "loading=\"lazy\" decoding=\"async\"",
+                "<details class=\"admonition tip\" open=\"\">",
+                "<input disabled=\"\" type=\"checkbox\" checked=\"\">",
+                "text-align",
+                "<math display=\"block\"><mfrac><mi>a</mi><mn>2</mn></mfrac></math>",
+            ] {
+                assert!(cleaned.contains(kept), "{:?} removed `{}`: {}", policy, kept, cleaned);
+            }
+        }
+    }
+
+    #[test]
+    fn test_policies_differ() {
+        let html = "<iframe src=\"https://player.example.com/1\" allowfullscreen></iframe>\
+                    <p style=\"position: fixed; color: red\">x</p>";
+        let trusted = sanitize(html, HtmlPolicy::Trusted);
+        assert!(trusted.contains("<iframe src=\"https://player.example.com/1\" allowfullscreen"));
+        assert!(trusted.contains("position: fixed"));
+
+        let strict = sanitize(html, HtmlPolicy::Strict);
+        assert!(!strict.contains("<iframe"));
+        assert!(strict.contains("<p style=\"color:red\">x</p>"));
+        let style = "<div style=\"background:url(javascript:alert(1))\">x</div>";
+        assert_eq!(sanitize(style, HtmlPolicy::Strict), "<div style=\"\">x</div>");
+
+        assert_eq!(sanitize(html, HtmlPolicy::Off), html);
+    }
+}
@@ -0,0 +1,587 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// 短代码占位符的起止字符（Unicode 私有区），不会被 Markdown 解析器改写
+const PLACEHOLDER_START: char = '\u{E000}';
+const PLACEHOLDER_END: char = '\u{E001}';
+
+/// 一次短代码调用
+///
+/// # 字段说明
+/// * `name` - 短代码名称，对应模板 `templates/shortcodes/<name>.html.tera`
+/// * `args` - 命名参数，如 `src="a.png"`
+/// * `positional` - 位置参数，如 `{{< youtube abc >}}` 中的 `abc`
+/// * `inner` - 成对短代码之间的内容（其中嵌套的短代码已展开），单个短代码为 `None`
+/// * `markdown` - 是否为 `{{% %}}` 形式，其输出会继续作为 Markdown 解析
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Shortcode {
+    pub name: String,
+    pub args: BTreeMap<String, String>,
+    pub positional: Vec<String>,
+    pub inner: Option<String>,
+    pub markdown: bool,
+}
+
+/// 短代码渲染器
+pub trait ShortcodeRenderer {
+    /// 渲染一次短代码调用，返回输出内容
+    fn render_shortcode(&self, shortcode: &Shortcode) -> Result<String>;
+}
+
+/// 短代码展开结果
+///
+/// `{{% %}}` 短代码的输出已直接写入 Markdown 文本；`{{< >}}` 短代码的输出是
+/// 最终HTML，在 Markdown 中以占位符表示，渲染完成后由 [`Expanded::restore`] 替换回去。
+#[derive(Debug)]
+pub struct Expanded {
+    pub markdown: String,
+    outputs: Vec<String>,
+}
+
+impl Expanded {
+    /// 将渲染后HTML中的占位符替换为短代码输出
+    ///
+    /// 单独成段的占位符会连同外层 `<p>` 一起替换，避免块级元素嵌套在段落中。
+    pub fn restore(&self, mut html: String) -> String {
+        // 外层短代码的输出中可能包含内层占位符，内层先生成、编号更小，因此倒序替换
+        for (index, output) in self.outputs.iter().enumerate().rev() {
+            let placeholder = placeholder(index);
+            let block = format!("<p>{}</p>", placeholder);
+            html = html.replace(&block, output).replace(&placeholder, output);
+        }
+        html
+    }
+}
+
+/// 展开文本中的短代码
+///
+/// 支持的写法：
+/// - `{{< name key="value" >}}`：输出为HTML，不再经过 Markdown 解析
+/// - `{{% name %}}内容{{% /name %}}`：输出继续作为 Markdown 解析
+/// - `{{< name />}}`：显式的单个短代码
+/// - `{{</* name */>}}`：转义写法，原样输出 `{{< name >}}`
+///
+/// 渲染失败（如短代码模板不存在）时保留原文并记录警告。
+///
+/// # 参数
+/// * `content` - Markdown 原文
+/// * `renderer` - 短代码渲染器
+///
+/// # 返回
+/// * `Expanded` - 展开后的 Markdown 文本与待替换的HTML输出
+pub fn expand(content: &str, renderer: &impl ShortcodeRenderer) -> Expanded {
+    let (nodes, _) = parse(content);
+    let mut outputs = Vec::new();
+    let markdown = expand_nodes(&nodes, renderer, &mut outputs);
+    Expanded { markdown, outputs }
+}
+
+/// 移除文本中的短代码标签，成对短代码保留其中的内容
+///
+/// 用于字数统计与摘要生成，避免短代码语法混入正文文本。
+pub fn strip(content: &str) -> String {
+    let (nodes, _) = parse(content);
+    let mut output = String::new();
+    strip_nodes(&nodes, &mut output);
+    output
+}
+
+/// 检查文本中的短代码，返回发现的问题
+///
+/// # 参数
+/// * `content` - Markdown 原文
+/// * `exists` - 判断短代码模板是否存在
+///
+/// # 返回
+/// * `Vec<String>` - 语法错误与未知短代码的说明
+pub fn diagnose(content: &str, exists: impl Fn(&str) -> bool) -> Vec<String> {
+    let (nodes, mut diagnostics) = parse(content);
+    collect_unknown(&nodes, &exists, &mut diagnostics);
+    diagnostics
+}
+
+fn placeholder(index: usize) -> String {
+    format!("{}{}{}", PLACEHOLDER_START, index, PLACEHOLDER_END)
+}
+
+fn expand_nodes(
+    nodes: &[Node],
+    renderer: &impl ShortcodeRenderer,
+    outputs: &mut Vec<String>,
+) -> String {
+    let mut output = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Shortcode { tag, inner } => {
+                let inner_text = inner
+                    .as_ref()
+                    .map(|(nodes, _)| expand_nodes(nodes, renderer, outputs));
+                let shortcode = Shortcode {
+                    name: tag.name.to_string(),
+                    args: tag.args.clone(),
+                    positional: tag.positional.clone(),
+                    inner: inner_text.clone(),
+                    markdown: tag.markdown,
+                };
+                match renderer.render_shortcode(&shortcode) {
+                    Ok(rendered) if tag.markdown => output.push_str(&rendered),
+                    Ok(rendered) => {
+                        output.push_str(&placeholder(outputs.len()));
+                        outputs.push(rendered);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to render shortcode `{}`: {}", tag.name, e);
+                        output.push_str(tag.source);
+                        if let (Some(text), Some((_, close))) = (inner_text, inner) {
+                            output.push_str(&text);
+                            output.push_str(close);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    output
+}
+
+fn strip_nodes(nodes: &[Node], output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Shortcode { inner, .. } => {
+                if let Some((nodes, _)) = inner {
+                    strip_nodes(nodes, output);
+                }
+            }
+        }
+    }
+}
+
+fn collect_unknown(nodes: &[Node], exists: &impl Fn(&str) -> bool, diagnostics: &mut Vec<String>) {
+    for node in nodes {
+        if let Node::Shortcode { tag, inner } = node {
+            if !exists(tag.name) {
+                diagnostics.push(format!("unknown shortcode `{}`", tag.name));
+            }
+            if let Some((nodes, _)) = inner {
+                collect_unknown(nodes, exists, diagnostics);
+            }
+        }
+    }
+}
+
+/// 短代码标签
+#[derive(Debug)]
+struct Tag<'a> {
+    name: &'a str,
+    args: BTreeMap<String, String>,
+    positional: Vec<String>,
+    markdown: bool,
+    /// 标签原文，渲染失败时原样输出
+    source: &'a str,
+}
+
+/// 词法分析得到的片段
+#[derive(Debug)]
+enum Token<'a> {
+    Text(Cow<'a, str>),
+    Open { tag: Tag<'a>, self_closing: bool },
+    Close { name: &'a str, source: &'a str },
+}
+
+/// 语法树节点
+#[derive(Debug)]
+enum Node<'a> {
+    Text(Cow<'a, str>),
+    /// 短代码；成对短代码带有内部节点与结束标签原文
+    Shortcode {
+        tag: Tag<'a>,
+        inner: Option<(Vec<Node<'a>>, &'a str)>,
+    },
+}
+
+/// 解析过程中的一层：开始标签（顶层为 `None`）与已收集的节点
+type Frame<'a> = (Option<Tag<'a>>, Vec<Node<'a>>);
+
+/// 解析文本中的短代码，返回语法树与语法错误
+///
+/// 开始标签之后出现同名的结束标签时视为成对短代码，否则视为单个短代码。
+fn parse(content: &str) -> (Vec<Node<'_>>, Vec<String>) {
+    let (tokens, mut diagnostics) = tokenize(content);
+
+    // 栈底为顶层节点，其余为尚未遇到结束标签的开始标签
+    let mut stack: Vec<Frame> = vec![(None, Vec::new())];
+    for token in tokens {
+        match token {
+            Token::Text(text) => push_node(&mut stack, Node::Text(text)),
+            Token::Open {
+                tag,
+                self_closing: true,
+            } => push_node(&mut stack, Node::Shortcode { tag, inner: None }),
+            Token::Open { tag, .. } => stack.push((Some(tag), Vec::new())),
+            Token::Close { name, source } => {
+                let position = stack
+                    .iter()
+                    .rposition(|(tag, _)| tag.as_ref().is_some_and(|t| t.name == name));
+                match position {
+                    Some(position) => {
+                        while stack.len() > position + 1 {
+                            close_unpaired(&mut stack);
+                        }
+                        let (tag, nodes) = stack.pop().expect("matched frame exists");
+                        let tag = tag.expect("matched frame has a tag");
+                        push_node(
+                            &mut stack,
+                            Node::Shortcode {
+                                tag,
+                                inner: Some((nodes, source)),
+                            },
+                        );
+                    }
+                    None => {
+                        diagnostics.push(format!("closing shortcode `{}` has no opening tag", name));
+                        push_node(&mut stack, Node::Text(Cow::Borrowed(source)));
+                    }
+                }
+            }
+        }
+    }
+    while stack.len() > 1 {
+        close_unpaired(&mut stack);
+    }
+
+    let (_, nodes) = stack.pop().expect("root frame exists");
+    (nodes, diagnostics)
+}
+
+fn push_node<'a>(stack: &mut [Frame<'a>], node: Node<'a>) {
+    stack
+        .last_mut()
+        .expect("root frame exists")
+        .1
+        .push(node);
+}
+
+/// 没有结束标签的开始标签作为单个短代码，其后的内容归还给上一层
+fn close_unpaired(stack: &mut Vec<Frame<'_>>) {
+    let (tag, nodes) = stack.pop().expect("frame exists");
+    let tag = tag.expect("non-root frame has a tag");
+    push_node(stack, Node::Shortcode { tag, inner: None });
+    for node in nodes {
+        push_node(stack, node);
+    }
+}
+
+/// 将文本切分为普通文本与短代码标签
+fn tokenize(content: &str) -> (Vec<Token<'_>>, Vec<String>) {
+    let mut tokens = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = find_tag_start(rest) {
+        if start > 0 {
+            tokens.push(Token::Text(Cow::Borrowed(&rest[..start])));
+        }
+        let markdown = rest[start + 2..].starts_with('%');
+        let close = if markdown { "%}}" } else { ">}}" };
+        let body_start = start + 3;
+
+        // 转义写法 {{</* name */>}}：去掉注释符号后原样输出
+        let trimmed = rest[body_start..].trim_start();
+        if let Some(comment) = trimmed.strip_prefix("/*") {
+            let comment_end = comment
+                .find("*/")
+                .filter(|&end| comment[end + 2..].trim_start().starts_with(close));
+            if let Some(end) = comment_end {
+                let open = &rest[start..body_start];
+                tokens.push(Token::Text(Cow::Owned(format!(
+                    "{} {} {}",
+                    open,
+                    comment[..end].trim(),
+                    close
+                ))));
+                let after = &comment[end + 2..];
+                let after = after.trim_start();
+                rest = &after[close.len()..];
+                continue;
+            }
+        }
+
+        match find_tag_end(&rest[body_start..], close) {
+            Some(end) => {
+                let source = &rest[start..body_start + end + close.len()];
+                let body = rest[body_start..body_start + end].trim();
+                match parse_tag(body, markdown, source) {
+                    Ok(token) => tokens.push(token),
+                    Err(e) => {
+                        diagnostics.push(e);
+                        tokens.push(Token::Text(Cow::Borrowed(source)));
+                    }
+                }
+                rest = &rest[body_start + end + close.len()..];
+            }
+            None => {
+                diagnostics.push(format!(
+                    "shortcode `{}` is not closed with `{}`",
+                    rest[start..].lines().next().unwrap_or_default(),
+                    close
+                ));
+                tokens.push(Token::Text(Cow::Borrowed(&rest[start..body_start])));
+                rest = &rest[body_start..];
+            }
+        }
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(Cow::Borrowed(rest)));
+    }
+    (tokens, diagnostics)
+}
+
+/// 查找下一个 `{{<` 或 `{{%`
+fn find_tag_start(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(position) = text[offset..].find("{{") {
+        let start = offset + position;
+        if matches!(text.as_bytes().get(start + 2), Some(b'<' | b'%')) {
+            return Some(start);
+        }
+        offset = start + 2;
+    }
+    None
+}
+
+/// 查找标签结束符的位置，跳过引号内的内容
+fn find_tag_end(text: &str, close: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '"' || c == '`' => quote = Some(c),
+            None if text[index..].starts_with(close) => return Some(index),
+            None => {}
+        }
+    }
+    None
+}
+
+/// 解析标签内容：名称与参数
+fn parse_tag<'a>(body: &'a str, markdown: bool, source: &'a str) -> Result<Token<'a>, String> {
+    if let Some(name) = body.strip_prefix('/') {
+        let name = name.trim();
+        if !is_valid_name(name) {
+            return Err(format!("invalid closing shortcode `{}`", source));
+        }
+        return Ok(Token::Close { name, source });
+    }
+
+    let (body, self_closing) = match body.strip_suffix('/') {
+        Some(body) => (body.trim_end(), true),
+        None => (body, false),
+    };
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let name = &body[..name_end];
+    if !is_valid_name(name) {
+        return Err(format!("invalid shortcode name in `{}`", source));
+    }
+
+    let mut args = BTreeMap::new();
+    let mut positional = Vec::new();
+    let mut rest = body[name_end..].trim_start();
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '"' || c == '`')
+            .unwrap_or(rest.len());
+        if key_end > 0 && rest[key_end..].starts_with('=') {
+            let (value, remaining) = parse_value(&rest[key_end + 1..], source)?;
+            args.insert(rest[..key_end].to_string(), value);
+            rest = remaining;
+        } else {
+            let (value, remaining) = parse_value(rest, source)?;
+            positional.push(value);
+            rest = remaining;
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(Token::Open {
+        tag: Tag {
+            name,
+            args,
+            positional,
+            markdown,
+            source,
+        },
+        self_closing,
+    })
+}
+
+/// 解析一个参数值：`"带 \"转义\" 的字符串"`、`` `原样字符串` `` 或不含空白的裸值
+fn parse_value<'a>(text: &'a str, source: &str) -> Result<(String, &'a str), String> {
+    let mut chars = text.char_indices();
+    match chars.next() {
+        Some((_, '"')) => {
+            let mut value = String::new();
+            let mut escaped = false;
+            for (index, c) in chars {
+                match c {
+                    _ if escaped => {
+                        value.push(c);
+                        escaped = false;
+                    }
+                    '\\' => escaped = true,
+                    '"' => return Ok((value, &text[index + 1..])),
+                    _ => value.push(c),
+                }
+            }
+            Err(format!("unterminated string in `{}`", source))
+        }
+        Some((_, '`')) => match text[1..].find('`') {
+            Some(end) => Ok((text[1..end + 1].to_string(), &text[end + 2..])),
+            None => Err(format!("unterminated string in `{}`", source)),
+        },
+        _ => {
+            let end = text.find(char::is_whitespace).unwrap_or(text.len());
+            Ok((text[..end].to_string(), &text[end..]))
+        }
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    /// 测试用渲染器：输出短代码的名称、参数与内容
+    struct Echo;
+
+    impl ShortcodeRenderer for Echo {
+        fn render_shortcode(&self, shortcode: &Shortcode) -> Result<String> {
+            if shortcode.name == "missing" {
+                return Err(anyhow!("template not found"));
+            }
+            let args: Vec<String> = shortcode
+                .args
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .chain(shortcode.positional.iter().cloned())
+                .collect();
+            Ok(format!(
+                "<{} {}>{}</{}>",
+                shortcode.name,
+                args.join(","),
+                shortcode.inner.as_deref().unwrap_or_default(),
+                shortcode.name
+            ))
+        }
+    }
+
+    fn expand_and_restore(content: &str) -> String {
+        let expanded = expand(content, &Echo);
+        expanded.restore(expanded.markdown.clone())
+    }
+
+    #[test]
+    fn test_arguments() {
+        let html = expand_and_restore(
+            r#"{{< figure src="/a b.png" caption="说 \"你好\"" width=300 `raw text` >}}"#,
+        );
+        assert_eq!(
+            html,
+            r#"<figure caption=说 "你好",src=/a b.png,width=300,raw text></figure>"#
+        );
+    }
+
+    #[test]
+    fn test_html_output_is_placeholder() {
+        let expanded = expand("前文 {{< video id=1 >}} 后文", &Echo);
+        assert!(!expanded.markdown.contains("<video"));
+        assert_eq!(
+            expanded.restore("<p>".to_string() + &expanded.markdown + "</p>"),
+            "<p>前文 <video id=1></video> 后文</p>"
+        );
+
+        // 单独成段时去掉外层段落
+        let expanded = expand("{{< video id=1 >}}", &Echo);
+        let html = format!("<p>{}</p>\n", expanded.markdown);
+        assert_eq!(expanded.restore(html), "<video id=1></video>\n");
+    }
+
+    #[test]
+    fn test_paired_markdown_shortcode() {
+        let expanded = expand("{{% note title=提示 %}}\n**重点**\n{{% /note %}}", &Echo);
+        assert_eq!(expanded.markdown, "<note title=提示>\n**重点**\n</note>");
+    }
+
+    #[test]
+    fn test_nested_shortcodes() {
+        let html = expand_and_restore(
+            "{{% note %}}看图 {{< figure src=a.png >}}{{% /note %}}{{< quote >}}{{< b />}}{{< /quote >}}",
+        );
+        assert_eq!(
+            html,
+            "<note >看图 <figure src=a.png></figure></note><quote ><b ></b></quote>"
+        );
+    }
+
+    #[test]
+    fn test_unpaired_opening_tag() {
+        // 没有结束标签的开始标签视为单个短代码，后续内容不受影响
+        let html = expand_and_restore("{{< figure src=a.png >}}正文{{% note %}}内容{{% /note %}}");
+        assert_eq!(html, "<figure src=a.png></figure>正文<note >内容</note>");
+    }
+
+    #[test]
+    fn test_escaped_shortcode() {
+        let html = expand_and_restore("用法：{{</* figure src=\"a.png\" */>}}");
+        assert_eq!(html, "用法：{{< figure src=\"a.png\" >}}");
+    }
+
+    #[test]
+    fn test_render_failure_keeps_source() {
+        let html = expand_and_restore("{{% missing a=1 %}}内容{{% /missing %}}");
+        assert_eq!(html, "{{% missing a=1 %}}内容{{% /missing %}}");
+    }
+
+    #[test]
+    fn test_strip() {
+        assert_eq!(
+            strip("开头{{< figure src=a.png >}}{{% note %}}内容{{% /note %}}结尾"),
+            "开头内容结尾"
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let diagnostics = diagnose(
+            "{{< figure >}}{{< unknown >}}{{% /note %}}{{< figure src=\"a >}}",
+            |name| name == "figure" || name == "note",
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                "shortcode `{{< figure src=\"a >}}` is not closed with `>}}`".to_string(),
+                "closing shortcode `note` has no opening tag".to_string(),
+                "unknown shortcode `unknown`".to_string(),
+            ]
+        );
+    }
+}